@@ -14,6 +14,8 @@
  *  limitations under the License.
  */
 
+use crate::error::{BacktraceError, ErrorMessage};
+
 /// A struct representing a color with red, green, blue, and alpha components.
 /// Each component is stored as a float.
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -149,6 +151,253 @@ impl Color
         Color::from_int_rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
     }
 
+    /// Parses a color from a CSS-style hex string. The following formats are
+    /// accepted, each with or without a leading `#`:
+    ///
+    /// * `#RGB`
+    /// * `#RGBA`
+    /// * `#RRGGBB`
+    /// * `#RRGGBBAA`
+    ///
+    /// If the alpha component is omitted, it will be set to `0xFF` (full
+    /// opacity). An error is returned if the string is not in one of the
+    /// above formats.
+    pub fn from_hex_string(hex: &str) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        let values = digits
+            .chars()
+            .map(|c| {
+                c.to_digit(16).map(|value| value as u8).ok_or_else(|| {
+                    ErrorMessage::msg(format!(
+                        "Invalid character '{c}' in hex color string '{hex}'"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        let single = |index: usize| values[index] * 0x11;
+        let pair = |index: usize| values[index] * 0x10 + values[index + 1];
+
+        match values.len() {
+            3 => Ok(Color::from_int_rgb(single(0), single(1), single(2))),
+            4 => Ok(Color::from_int_rgba(
+                single(0),
+                single(1),
+                single(2),
+                single(3)
+            )),
+            6 => Ok(Color::from_int_rgb(pair(0), pair(2), pair(4))),
+            8 => Ok(Color::from_int_rgba(pair(0), pair(2), pair(4), pair(6))),
+            len => Err(ErrorMessage::msg(format!(
+                "Hex color string '{hex}' has {len} digits, expected 3, 4, 6, or 8"
+            )))
+        }
+    }
+
+    /// Looks up a color by its CSS name, such as `"tomato"` or
+    /// `"cornflowerblue"`. The name is case-insensitive. All of the named
+    /// colors from the CSS Color Module Level 4 specification are supported,
+    /// including `"transparent"`.
+    ///
+    /// Note that the CSS colors don't always match the constants on [Color].
+    /// For example, CSS `"green"` is `#008000`, whereas [Color::GREEN] is
+    /// `#00FF00`.
+    ///
+    /// Returns `None` if the name is not recognized.
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self>
+    {
+        let name = name.to_ascii_lowercase();
+
+        if name == "transparent" {
+            return Some(Color::TRANSPARENT);
+        }
+
+        CSS_NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |&(entry_name, _)| entry_name)
+            .ok()
+            .map(|index| Color::from_hex_rgb(CSS_NAMED_COLORS[index].1))
+    }
+
+    /// Returns the color as a hex string in the format `#RRGGBBAA`. Each
+    /// component is clamped to the range `0.0` to `1.0`, and rounded to the
+    /// nearest integer between `0x00` and `0xFF`.
+    #[must_use]
+    pub fn to_hex_string(&self) -> String
+    {
+        let to_int = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        format!(
+            "#{:02X}{:02X}{:02X}{:02X}",
+            to_int(self.r),
+            to_int(self.g),
+            to_int(self.b),
+            to_int(self.a)
+        )
+    }
+
+    /// Creates a color from the specified hue, saturation, value, and alpha
+    /// components.
+    ///
+    /// The hue is specified in degrees, and will be wrapped to the range `0.0`
+    /// to `360.0`. The other components should be in the range `0.0` to `1.0`.
+    #[must_use]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self
+    {
+        let chroma = value * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma);
+        let offset = value - chroma;
+
+        Color::from_rgba(r + offset, g + offset, b + offset, alpha)
+    }
+
+    /// Creates a color from the specified hue, saturation, lightness, and
+    /// alpha components.
+    ///
+    /// The hue is specified in degrees, and will be wrapped to the range `0.0`
+    /// to `360.0`. The other components should be in the range `0.0` to `1.0`.
+    #[must_use]
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self
+    {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma);
+        let offset = lightness - chroma / 2.0;
+
+        Color::from_rgba(r + offset, g + offset, b + offset, alpha)
+    }
+
+    /// Returns the hue, saturation, and value components of the color.
+    ///
+    /// The hue is in degrees, in the range `0.0` to `360.0` (exclusive), and
+    /// the other components are in the range `0.0` to `1.0`. For shades of
+    /// gray, the hue and saturation are both `0.0`.
+    #[must_use]
+    pub fn hsv(&self) -> (f32, f32, f32)
+    {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+
+        let saturation = if max <= 0.0 { 0.0 } else { chroma / max };
+
+        (self.hue(max, chroma), saturation, max)
+    }
+
+    /// Returns the hue, saturation, and lightness components of the color.
+    ///
+    /// The hue is in degrees, in the range `0.0` to `360.0` (exclusive), and
+    /// the other components are in the range `0.0` to `1.0`. For shades of
+    /// gray, the hue and saturation are both `0.0`.
+    #[must_use]
+    pub fn hsl(&self) -> (f32, f32, f32)
+    {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+
+        let saturation = if chroma <= 0.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        (self.hue(max, chroma), saturation, lightness)
+    }
+
+    fn hue(&self, max: f32, chroma: f32) -> f32
+    {
+        if chroma <= 0.0 {
+            return 0.0;
+        }
+
+        let sector = if max == self.r {
+            ((self.g - self.b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        } else {
+            (self.r - self.g) / chroma + 4.0
+        };
+
+        (sector * 60.0) % 360.0
+    }
+
+    /// Linearly interpolates between this color and `other`, including the
+    /// alpha component. A `t` value of `0.0` returns this color, and `1.0`
+    /// returns `other`. The value of `t` will be clamped to the range `0.0` to
+    /// `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: &Color, t: f32) -> Color
+    {
+        let t = t.clamp(0.0, 1.0);
+
+        Color::from_rgba(
+            self.r * (1.0 - t) + other.r * t,
+            self.g * (1.0 - t) + other.g * t,
+            self.b * (1.0 - t) + other.b * t,
+            self.a * (1.0 - t) + other.a * t
+        )
+    }
+
+    /// Mixes this color with `other`, where `weight` is the proportion of
+    /// `other` in the result, in the range `0.0` to `1.0`. This is equivalent
+    /// to [Color::lerp].
+    #[inline]
+    #[must_use]
+    pub fn mix(&self, other: &Color, weight: f32) -> Color
+    {
+        self.lerp(other, weight)
+    }
+
+    /// Returns this color with the red, green, and blue components multiplied
+    /// by the alpha component. This is the form expected when drawing using
+    /// premultiplied alpha, for example when creating an image using
+    /// [RGBAPremultiplied](crate::image::ImageDataType::RGBAPremultiplied)
+    /// image data.
+    #[inline]
+    #[must_use]
+    pub fn premultiplied(&self) -> Color
+    {
+        Color::from_rgba(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// Converts this color from the sRGB color space to linear RGB, using the
+    /// standard sRGB transfer function. The alpha component is unchanged.
+    ///
+    /// Colors are normally specified in sRGB, where values are perceptually
+    /// spaced. Blending and interpolation are only physically correct in
+    /// linear space: for example, interpolating between red and green in sRGB
+    /// gives a darker, muddier midpoint than doing the same in linear space.
+    ///
+    /// See [Color::from_linear].
+    #[must_use]
+    pub fn to_linear(&self) -> Color
+    {
+        Color::from_rgba(
+            srgb_component_to_linear(self.r),
+            srgb_component_to_linear(self.g),
+            srgb_component_to_linear(self.b),
+            self.a
+        )
+    }
+
+    /// Converts the specified color from linear RGB to the sRGB color space.
+    /// This is the inverse of [Color::to_linear]. The alpha component is
+    /// unchanged.
+    #[must_use]
+    pub fn from_linear(linear: Color) -> Self
+    {
+        Color::from_rgba(
+            linear_component_to_srgb(linear.r),
+            linear_component_to_srgb(linear.g),
+            linear_component_to_srgb(linear.b),
+            linear.a
+        )
+    }
+
     /// Creates a shade of gray from the specified float value, between `0.0`
     /// and `1.0`. All three RGB components will be set to this value.
     #[inline]
@@ -209,6 +458,278 @@ impl Color
     }
 }
 
+/// The direction in which a linear [Gradient] is drawn across a shape.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum GradientDirection
+{
+    /// The start of the gradient is on the left, and the end is on the right.
+    LeftToRight,
+    /// The start of the gradient is at the top, and the end is at the bottom.
+    TopToBottom
+}
+
+/// A color gradient, consisting of two or more color stops. Each stop has a
+/// position in the range `0.0` (the start of the gradient) to `1.0` (the end
+/// of the gradient). Colors are interpolated linearly between stops.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Gradient
+{
+    stops: Vec<(f32, Color)>
+}
+
+impl Gradient
+{
+    /// Creates a gradient which transitions from `start_color` (at position
+    /// `0.0`) to `end_color` (at position `1.0`).
+    #[inline]
+    #[must_use]
+    pub fn new(start_color: Color, end_color: Color) -> Self
+    {
+        Gradient {
+            stops: vec![(0.0, start_color), (1.0, end_color)]
+        }
+    }
+
+    /// Adds an additional color stop at the specified position. The position
+    /// will be clamped to the range `0.0` to `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self
+    {
+        let position = position.clamp(0.0, 1.0);
+
+        let index = self
+            .stops
+            .iter()
+            .position(|(stop_position, _)| *stop_position > position)
+            .unwrap_or(self.stops.len());
+
+        self.stops.insert(index, (position, color));
+        self
+    }
+
+    /// Returns the color stops in this gradient, ordered by position.
+    #[inline]
+    #[must_use]
+    pub fn stops(&self) -> &[(f32, Color)]
+    {
+        &self.stops
+    }
+
+    /// Returns the color of the gradient at the specified position, which
+    /// will be clamped to the range `0.0` to `1.0`.
+    #[must_use]
+    pub fn color_at(&self, position: f32) -> Color
+    {
+        let position = position.clamp(0.0, 1.0);
+
+        let mut previous = self.stops[0];
+
+        for stop in &self.stops {
+            if stop.0 >= position {
+                let range = stop.0 - previous.0;
+
+                if range <= 0.0 {
+                    return stop.1;
+                }
+
+                return previous.1.lerp(&stop.1, (position - previous.0) / range);
+            }
+
+            previous = *stop;
+        }
+
+        previous.1
+    }
+}
+
+/// Returns the red, green, and blue components for the specified hue and
+/// chroma, before the lightness offset is added.
+fn hue_to_rgb(hue: f32, chroma: f32) -> (f32, f32, f32)
+{
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x)
+    }
+}
+
+fn srgb_component_to_linear(value: f32) -> f32
+{
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_component_to_srgb(value: f32) -> f32
+{
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// The CSS named colors, sorted by name.
+const CSS_NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32)
+];
+
 #[cfg(test)]
 mod tests
 {
@@ -231,4 +752,212 @@ mod tests
             Color::from_int_rgba(0xFF, 0x55, 0x11, 0xAA)
         );
     }
+
+    #[test]
+    fn test_from_hex_string()
+    {
+        assert_eq!(
+            Color::from_hex_string("#F51").unwrap(),
+            Color::from_int_rgb(0xFF, 0x55, 0x11)
+        );
+
+        assert_eq!(
+            Color::from_hex_string("F51A").unwrap(),
+            Color::from_int_rgba(0xFF, 0x55, 0x11, 0xAA)
+        );
+
+        assert_eq!(
+            Color::from_hex_string("#ff5511").unwrap(),
+            Color::from_int_rgb(0xFF, 0x55, 0x11)
+        );
+
+        assert_eq!(
+            Color::from_hex_string("FF5511AA").unwrap(),
+            Color::from_int_rgba(0xFF, 0x55, 0x11, 0xAA)
+        );
+
+        assert!(Color::from_hex_string("").is_err());
+        assert!(Color::from_hex_string("#").is_err());
+        assert!(Color::from_hex_string("#FF55").is_ok());
+        assert!(Color::from_hex_string("#FF551").is_err());
+        assert!(Color::from_hex_string("#GG5511").is_err());
+        assert!(Color::from_hex_string("##FF5511").is_err());
+    }
+
+    #[test]
+    fn test_named()
+    {
+        assert_eq!(Some(Color::RED), Color::named("red"));
+        assert_eq!(Some(Color::from_hex_rgb(0xFF6347)), Color::named("Tomato"));
+        assert_eq!(Some(Color::TRANSPARENT), Color::named("transparent"));
+        assert_eq!(None, Color::named("notacolor"));
+        assert_eq!(None, Color::named(""));
+    }
+
+    #[test]
+    fn test_to_hex_string()
+    {
+        assert_eq!(Color::RED.to_hex_string(), "#FF0000FF");
+        assert_eq!(Color::TRANSPARENT.to_hex_string(), "#00000000");
+
+        let color = Color::from_int_rgba(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(color.to_hex_string(), "#12345678");
+        assert_eq!(
+            Color::from_hex_string(&color.to_hex_string()).unwrap(),
+            color
+        );
+    }
+
+    fn assert_near(expected: (f32, f32, f32), actual: (f32, f32, f32))
+    {
+        const EPSILON: f32 = 0.001;
+
+        assert!(
+            (expected.0 - actual.0).abs() < EPSILON
+                && (expected.1 - actual.1).abs() < EPSILON
+                && (expected.2 - actual.2).abs() < EPSILON,
+            "Expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn rgb(color: Color) -> (f32, f32, f32)
+    {
+        (color.r(), color.g(), color.b())
+    }
+
+    #[test]
+    fn test_hsv()
+    {
+        assert_near((1.0, 0.0, 0.0), rgb(Color::from_hsv(0.0, 1.0, 1.0, 1.0)));
+        assert_near((0.0, 1.0, 0.0), rgb(Color::from_hsv(120.0, 1.0, 1.0, 1.0)));
+        assert_near((0.0, 0.0, 1.0), rgb(Color::from_hsv(240.0, 1.0, 1.0, 1.0)));
+        assert_near((1.0, 0.0, 0.0), rgb(Color::from_hsv(360.0, 1.0, 1.0, 1.0)));
+        assert_near((0.0, 0.0, 1.0), rgb(Color::from_hsv(-120.0, 1.0, 1.0, 1.0)));
+        assert_near((0.5, 0.5, 0.5), rgb(Color::from_hsv(75.0, 0.0, 0.5, 1.0)));
+
+        assert_eq!(Color::from_hsv(0.0, 1.0, 1.0, 0.25).a(), 0.25);
+
+        assert_near((0.0, 0.0, 0.5), Color::GRAY.hsv());
+        assert_near((60.0, 1.0, 1.0), Color::YELLOW.hsv());
+        assert_near((300.0, 1.0, 1.0), Color::MAGENTA.hsv());
+
+        for hsv in [
+            (0.0, 0.5, 0.5),
+            (45.0, 0.25, 1.0),
+            (200.0, 1.0, 0.75),
+            (330.0, 0.8, 0.2)
+        ] {
+            assert_near(hsv, Color::from_hsv(hsv.0, hsv.1, hsv.2, 1.0).hsv());
+        }
+    }
+
+    #[test]
+    fn test_hsl()
+    {
+        assert_near((1.0, 0.0, 0.0), rgb(Color::from_hsl(0.0, 1.0, 0.5, 1.0)));
+        assert_near((0.0, 1.0, 1.0), rgb(Color::from_hsl(180.0, 1.0, 0.5, 1.0)));
+        assert_near((1.0, 1.0, 1.0), rgb(Color::from_hsl(90.0, 1.0, 1.0, 1.0)));
+        assert_near(
+            (0.25, 0.25, 0.25),
+            rgb(Color::from_hsl(90.0, 0.0, 0.25, 1.0))
+        );
+
+        assert_near((0.0, 0.0, 0.75), Color::LIGHT_GRAY.hsl());
+        assert_near((180.0, 1.0, 0.5), Color::CYAN.hsl());
+
+        for hsl in [
+            (0.0, 0.5, 0.5),
+            (45.0, 0.25, 0.9),
+            (200.0, 1.0, 0.75),
+            (330.0, 0.8, 0.2)
+        ] {
+            assert_near(hsl, Color::from_hsl(hsl.0, hsl.1, hsl.2, 1.0).hsl());
+        }
+    }
+
+    #[test]
+    fn test_lerp()
+    {
+        let start = Color::from_rgba(0.0, 0.2, 1.0, 0.0);
+        let end = Color::from_rgba(1.0, 0.6, 0.0, 1.0);
+
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, -1.0), start);
+        assert_eq!(start.lerp(&end, 2.0), end);
+
+        assert_near((0.5, 0.4, 0.5), rgb(start.lerp(&end, 0.5)));
+        assert_eq!(start.lerp(&end, 0.5).a(), 0.5);
+
+        assert_eq!(start.mix(&end, 0.25), start.lerp(&end, 0.25));
+    }
+
+    #[test]
+    fn test_linear()
+    {
+        assert_eq!(Color::BLACK.to_linear(), Color::BLACK);
+        assert_eq!(Color::WHITE.to_linear(), Color::WHITE);
+        assert_eq!(Color::TRANSPARENT.to_linear(), Color::TRANSPARENT);
+
+        let linear_gray = Color::from_rgba(0.5, 0.5, 0.5, 0.25).to_linear();
+        assert!((linear_gray.r() - 0.21404).abs() < 0.0001);
+        assert_eq!(linear_gray.a(), 0.25);
+
+        for i in 0..=255 {
+            let color = Color::from_int_rgb(i, 255 - i, i / 2);
+            let round_trip = Color::from_linear(color.to_linear());
+
+            assert!((round_trip.r() - color.r()).abs() < 0.0001);
+            assert!((round_trip.g() - color.g()).abs() < 0.0001);
+            assert!((round_trip.b() - color.b()).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_premultiplied()
+    {
+        assert_eq!(Color::RED.premultiplied(), Color::RED);
+        assert_eq!(Color::TRANSPARENT.premultiplied(), Color::TRANSPARENT);
+
+        assert_eq!(
+            Color::from_rgba(1.0, 0.5, 0.0, 0.5).premultiplied(),
+            Color::from_rgba(0.5, 0.25, 0.0, 0.5)
+        );
+
+        assert_eq!(
+            Color::from_rgba(0.2, 0.4, 0.8, 0.0).premultiplied(),
+            Color::from_rgba(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_gradient()
+    {
+        let gradient = Gradient::new(Color::BLACK, Color::WHITE);
+
+        assert_eq!(gradient.color_at(0.0), Color::BLACK);
+        assert_eq!(gradient.color_at(0.5), Color::from_gray(0.5));
+        assert_eq!(gradient.color_at(1.0), Color::WHITE);
+        assert_eq!(gradient.color_at(-1.0), Color::BLACK);
+        assert_eq!(gradient.color_at(2.0), Color::WHITE);
+
+        let gradient = Gradient::new(Color::BLACK, Color::BLACK)
+            .with_stop(0.75, Color::RED)
+            .with_stop(0.25, Color::BLUE);
+
+        assert_eq!(
+            gradient
+                .stops()
+                .iter()
+                .map(|(position, _)| *position)
+                .collect::<Vec<_>>(),
+            vec![0.0, 0.25, 0.75, 1.0]
+        );
+
+        assert_eq!(gradient.color_at(0.25), Color::BLUE);
+        assert_eq!(gradient.color_at(0.5), Color::from_rgb(0.5, 0.0, 0.5));
+        assert_eq!(gradient.color_at(0.75), Color::RED);
+    }
 }
@@ -14,12 +14,13 @@
  *  limitations under the License.
  */
 
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::num::TryFromIntError;
 
-use num_traits::{AsPrimitive, Zero};
+use num_traits::{AsPrimitive, Signed, Zero};
 use rusttype::Point;
 
-use crate::numeric::{PrimitiveZero, RoundFloat};
+use crate::numeric::{max, min, PrimitiveZero, RoundFloat};
 
 /// A vector with two f32 values.
 pub type Vec2 = Vector2<f32>;
@@ -125,6 +126,45 @@ where
     }
 }
 
+impl<T: PartialOrd + Copy> Vector2<T>
+{
+    /// Returns a vector containing the smaller of each pair of components.
+    #[inline]
+    #[must_use]
+    pub fn min(&self, other: Self) -> Self
+    {
+        Vector2::new(min(self.x, other.x), min(self.y, other.y))
+    }
+
+    /// Returns a vector containing the larger of each pair of components.
+    #[inline]
+    #[must_use]
+    pub fn max(&self, other: Self) -> Self
+    {
+        Vector2::new(max(self.x, other.x), max(self.y, other.y))
+    }
+
+    /// Restricts each component of the vector to the range between the
+    /// corresponding components of `min_value` and `max_value`.
+    #[inline]
+    #[must_use]
+    pub fn clamp(&self, min_value: Self, max_value: Self) -> Self
+    {
+        self.max(min_value).min(max_value)
+    }
+}
+
+impl<T: Signed> Vector2<T>
+{
+    /// Returns a vector containing the absolute value of each component.
+    #[inline]
+    #[must_use]
+    pub fn abs(&self) -> Self
+    {
+        Vector2::new(self.x.abs(), self.y.abs())
+    }
+}
+
 impl<T: std::ops::Neg<Output = T> + Copy> Vector2<T>
 {
     /// Rotates the vector by 90 degrees in the clockwise direction.
@@ -144,6 +184,99 @@ impl<T: std::ops::Neg<Output = T> + Copy> Vector2<T>
     }
 }
 
+impl Vec2
+{
+    /// Rotates the vector around the origin by the specified angle. As the Y
+    /// axis points downwards, positive angles result in a clockwise rotation
+    /// on the screen. This is consistent with [Matrix3::from_rotation].
+    #[inline]
+    #[must_use]
+    pub fn rotate(&self, radians: f32) -> Vec2
+    {
+        let (sin, cos) = radians.sin_cos();
+
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Returns the angle of the vector in radians, measured from the positive
+    /// X axis, in the range `-PI` to `PI`. As the Y axis points downwards,
+    /// positive angles are clockwise on the screen.
+    #[inline]
+    #[must_use]
+    pub fn angle(&self) -> f32
+    {
+        self.y.atan2(self.x)
+    }
+
+    /// Returns the angle in radians, in the range `-PI` to `PI`, by which this
+    /// vector would need to be rotated (using [Vec2::rotate]) to point in the
+    /// same direction as `other`.
+    #[inline]
+    #[must_use]
+    pub fn angle_to(&self, other: Vec2) -> f32
+    {
+        let cross = self.x * other.y - self.y * other.x;
+        let dot = self.x * other.x + self.y * other.y;
+
+        cross.atan2(dot)
+    }
+
+    /// Linearly interpolates between this vector and `other`. A `t` value of
+    /// `0.0` returns this vector, and `1.0` returns `other`. Values outside
+    /// this range extrapolate beyond the two vectors.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: Vec2, t: f32) -> Vec2
+    {
+        Vec2::new(
+            self.x * (1.0 - t) + other.x * t,
+            self.y * (1.0 - t) + other.y * t
+        )
+    }
+
+    /// Rounds each component down to the nearest integer, and returns the
+    /// result as an `IVec2`.
+    #[inline]
+    #[must_use]
+    pub fn floor_to_i32(&self) -> IVec2
+    {
+        Vector2::new(self.x.floor() as i32, self.y.floor() as i32)
+    }
+
+    /// Rounds each component up to the nearest integer, and returns the
+    /// result as an `IVec2`.
+    #[inline]
+    #[must_use]
+    pub fn ceil_to_i32(&self) -> IVec2
+    {
+        Vector2::new(self.x.ceil() as i32, self.y.ceil() as i32)
+    }
+
+    /// Rounds each component to the nearest integer, and returns the result
+    /// as an `IVec2`.
+    ///
+    /// To round the components while keeping them as `f32` values, use
+    /// [RoundFloat::round].
+    #[inline]
+    #[must_use]
+    pub fn round_to_i32(&self) -> IVec2
+    {
+        Vector2::new(self.x.round() as i32, self.y.round() as i32)
+    }
+}
+
+impl IVec2
+{
+    /// Converts this vector to a `UVec2`, replacing any negative components
+    /// with zero.
+    #[inline]
+    #[must_use]
+    pub fn into_u32_saturating(self) -> UVec2
+    {
+        Vector2::new(max(self.x, 0) as u32, max(self.y, 0) as u32)
+    }
+}
+
 impl<T: num_traits::AsPrimitive<f32>> Vector2<T>
 {
     /// Returns a new vector with each element cast to `f32`, using the `as`
@@ -191,6 +324,49 @@ impl<T: TryInto<i32>> Vector2<T>
     }
 }
 
+impl From<IVec2> for Vec2
+{
+    #[inline]
+    fn from(value: IVec2) -> Self
+    {
+        value.into_f32()
+    }
+}
+
+impl From<UVec2> for Vec2
+{
+    #[inline]
+    fn from(value: UVec2) -> Self
+    {
+        value.into_f32()
+    }
+}
+
+impl TryFrom<UVec2> for IVec2
+{
+    type Error = TryFromIntError;
+
+    #[inline]
+    fn try_from(value: UVec2) -> Result<Self, Self::Error>
+    {
+        Ok(Vector2::new(value.x.try_into()?, value.y.try_into()?))
+    }
+}
+
+impl TryFrom<IVec2> for UVec2
+{
+    type Error = TryFromIntError;
+
+    /// Converts the vector, returning an error if either component is
+    /// negative. To clamp negative components to zero instead, use
+    /// [IVec2::into_u32_saturating].
+    #[inline]
+    fn try_from(value: IVec2) -> Result<Self, Self::Error>
+    {
+        Ok(Vector2::new(value.x.try_into()?, value.y.try_into()?))
+    }
+}
+
 impl<T> From<(T, T)> for Vector2<T>
 where
     T: Copy
@@ -447,11 +623,376 @@ impl<T> From<Point<T>> for Vector2<T>
     }
 }
 
+/// A 3x3 matrix representing a 2D affine transform, such as a translation,
+/// rotation, or scale.
+///
+/// Points are treated as column vectors, so when two matrices are multiplied
+/// together (`a * b`), the resulting matrix applies `b` first, followed by `a`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Matrix3
+{
+    rows: [[f32; 3]; 3]
+}
+
+impl Matrix3
+{
+    /// The identity matrix, which leaves points unchanged.
+    pub const IDENTITY: Matrix3 =
+        Matrix3::from_rows([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    /// Creates a matrix from the specified rows.
+    #[inline]
+    #[must_use]
+    pub const fn from_rows(rows: [[f32; 3]; 3]) -> Self
+    {
+        Matrix3 { rows }
+    }
+
+    /// Creates a matrix which moves points by the specified offset.
+    #[inline]
+    #[must_use]
+    pub fn from_translation<V: Into<Vec2>>(offset: V) -> Self
+    {
+        let offset = offset.into();
+
+        Matrix3::from_rows([[1.0, 0.0, offset.x], [0.0, 1.0, offset.y], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a matrix which rotates points around the origin by the
+    /// specified angle. As the Y axis points downwards, positive angles
+    /// result in a clockwise rotation on the screen.
+    #[inline]
+    #[must_use]
+    pub fn from_rotation(radians: f32) -> Self
+    {
+        let (sin, cos) = radians.sin_cos();
+
+        Matrix3::from_rows([[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a matrix which scales points relative to the origin by the
+    /// specified horizontal and vertical factors.
+    #[inline]
+    #[must_use]
+    pub fn from_scale<V: Into<Vec2>>(factor: V) -> Self
+    {
+        let factor = factor.into();
+
+        Matrix3::from_rows([[factor.x, 0.0, 0.0], [0.0, factor.y, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Returns the rows of this matrix.
+    #[inline]
+    #[must_use]
+    pub const fn rows(&self) -> &[[f32; 3]; 3]
+    {
+        &self.rows
+    }
+
+    /// Applies this transform to the specified point.
+    #[inline]
+    #[must_use]
+    pub fn transform_point<V: Into<Vec2>>(&self, point: V) -> Vec2
+    {
+        let point = point.into();
+        let r = &self.rows;
+
+        Vec2::new(
+            r[0][0] * point.x + r[0][1] * point.y + r[0][2],
+            r[1][0] * point.x + r[1][1] * point.y + r[1][2]
+        )
+    }
+
+    /// Returns the inverse of this matrix, which undoes the transform. If the
+    /// matrix cannot be inverted (for example, if it scales by zero), `None`
+    /// is returned.
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self>
+    {
+        let r = &self.rows;
+
+        let cofactor = |row: usize, col: usize| {
+            let (r1, r2) = ((row + 1) % 3, (row + 2) % 3);
+            let (c1, c2) = ((col + 1) % 3, (col + 2) % 3);
+            r[r1][c1] * r[r2][c2] - r[r1][c2] * r[r2][c1]
+        };
+
+        let determinant = r[0][0] * cofactor(0, 0)
+            + r[0][1] * cofactor(0, 1)
+            + r[0][2] * cofactor(0, 2);
+
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let mut rows = [[0.0; 3]; 3];
+
+        for (row, output_row) in rows.iter_mut().enumerate() {
+            for (col, output) in output_row.iter_mut().enumerate() {
+                // The inverse is the transposed matrix of cofactors, divided by
+                // the determinant
+                *output = cofactor(col, row) / determinant;
+            }
+        }
+
+        Some(Matrix3::from_rows(rows))
+    }
+
+    /// If this matrix only performs a translation, returns the offset.
+    #[inline]
+    pub(crate) fn as_translation(&self) -> Option<Vec2>
+    {
+        let r = &self.rows;
+
+        if r[0][0] == 1.0 && r[0][1] == 0.0 && r[1][0] == 0.0 && r[1][1] == 1.0 {
+            Some(Vec2::new(r[0][2], r[1][2]))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Matrix3
+{
+    #[inline]
+    fn default() -> Self
+    {
+        Matrix3::IDENTITY
+    }
+}
+
+impl std::ops::Mul for Matrix3
+{
+    type Output = Matrix3;
+
+    #[must_use]
+    fn mul(self, rhs: Matrix3) -> Self::Output
+    {
+        let mut rows = [[0.0; 3]; 3];
+
+        for (row, output_row) in rows.iter_mut().enumerate() {
+            for (col, output) in output_row.iter_mut().enumerate() {
+                *output = (0..3).map(|i| self.rows[row][i] * rhs.rows[i][col]).sum();
+            }
+        }
+
+        Matrix3::from_rows(rows)
+    }
+}
+
 #[cfg(test)]
 mod test
 {
     use super::*;
 
+    #[test]
+    fn test_matrix_transform_point()
+    {
+        assert_eq!(
+            Vec2::new(3.0, 4.0),
+            Matrix3::IDENTITY.transform_point((3.0, 4.0))
+        );
+
+        assert_eq!(
+            Vec2::new(13.0, 24.0),
+            Matrix3::from_translation((10.0, 20.0)).transform_point((3.0, 4.0))
+        );
+
+        assert_eq!(
+            Vec2::new(6.0, 12.0),
+            Matrix3::from_scale((2.0, 3.0)).transform_point((3.0, 4.0))
+        );
+
+        assert_eq!(
+            Vec2::new(16.0, 28.0),
+            (Matrix3::from_translation((10.0, 20.0)) * Matrix3::from_scale((2.0, 2.0)))
+                .transform_point((3.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn test_matrix_compose_translate_then_rotate()
+    {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+
+        // Translate first, then rotate around the origin
+        let transform =
+            Matrix3::from_rotation(half_pi) * Matrix3::from_translation((10.0, 0.0));
+
+        assert_near(Vec2::new(0.0, 11.0), transform.transform_point((1.0, 0.0)));
+        assert_near(Vec2::new(-2.0, 10.0), transform.transform_point((0.0, 2.0)));
+
+        // Rotate first, then translate
+        let transform =
+            Matrix3::from_translation((10.0, 0.0)) * Matrix3::from_rotation(half_pi);
+
+        assert_near(Vec2::new(10.0, 1.0), transform.transform_point((1.0, 0.0)));
+
+        assert_eq!(Matrix3::IDENTITY, Matrix3::default());
+    }
+
+    #[test]
+    fn test_matrix_inverse()
+    {
+        let transform = Matrix3::from_translation((15.0, -7.0))
+            * Matrix3::from_rotation(0.6)
+            * Matrix3::from_scale((2.0, 0.5));
+
+        let inverse = transform.inverse().unwrap();
+        let point = Vec2::new(3.0, 4.0);
+
+        assert_near(
+            point,
+            inverse.transform_point(transform.transform_point(point))
+        );
+        assert_near(
+            point,
+            transform.transform_point(inverse.transform_point(point))
+        );
+
+        assert_eq!(
+            Some(Matrix3::from_translation((-10.0, -20.0))),
+            Matrix3::from_translation((10.0, 20.0)).inverse()
+        );
+
+        assert_eq!(Some(Matrix3::IDENTITY), Matrix3::IDENTITY.inverse());
+        assert_eq!(None, Matrix3::from_scale((0.0, 1.0)).inverse());
+    }
+
+    fn assert_near(expected: Vec2, actual: Vec2)
+    {
+        assert!(
+            (expected - actual).magnitude() < 0.0001,
+            "Expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_rotate()
+    {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+
+        assert_near(Vec2::new(0.0, 1.0), Vec2::new(1.0, 0.0).rotate(half_pi));
+        assert_near(Vec2::new(0.0, -1.0), Vec2::new(1.0, 0.0).rotate(-half_pi));
+        assert_near(
+            Vec2::new(-3.0, -4.0),
+            Vec2::new(3.0, 4.0).rotate(half_pi * 2.0)
+        );
+
+        assert_near(
+            Vec2::new(3.0, 4.0).rotate_90_degrees_clockwise(),
+            Vec2::new(3.0, 4.0).rotate(half_pi)
+        );
+
+        assert_near(
+            Matrix3::from_rotation(0.7).transform_point((3.0, 4.0)),
+            Vec2::new(3.0, 4.0).rotate(0.7)
+        );
+    }
+
+    #[test]
+    fn test_angle()
+    {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+
+        assert_eq!(0.0, Vec2::new(1.0, 0.0).angle());
+        assert!((Vec2::new(0.0, 1.0).angle() - half_pi).abs() < 0.0001);
+        assert!((Vec2::new(0.0, -1.0).angle() + half_pi).abs() < 0.0001);
+
+        assert!(
+            (Vec2::new(1.0, 0.0).angle_to(Vec2::new(0.0, 2.0)) - half_pi).abs() < 0.0001
+        );
+        assert!(
+            (Vec2::new(0.0, 2.0).angle_to(Vec2::new(1.0, 0.0)) + half_pi).abs() < 0.0001
+        );
+
+        let from = Vec2::new(3.0, 1.0);
+        let to = Vec2::new(-2.0, 5.0);
+
+        assert_near(
+            to.normalize().unwrap(),
+            from.rotate(from.angle_to(to)).normalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lerp()
+    {
+        let start = Vec2::new(10.0, 20.0);
+        let end = Vec2::new(30.0, -20.0);
+
+        assert_eq!(start, start.lerp(end, 0.0));
+        assert_eq!(end, start.lerp(end, 1.0));
+        assert_eq!(Vec2::new(20.0, 0.0), start.lerp(end, 0.5));
+        assert_eq!(Vec2::new(15.0, 10.0), start.lerp(end, 0.25));
+        assert_eq!(Vec2::new(40.0, -40.0), start.lerp(end, 1.5));
+    }
+
+    #[test]
+    fn test_conversions()
+    {
+        assert_eq!(Vec2::new(-3.0, 4.0), Vec2::from(IVec2::new(-3, 4)));
+        assert_eq!(Vec2::new(3.0, 4.0), Vec2::from(UVec2::new(3, 4)));
+
+        assert_eq!(Ok(IVec2::new(3, 4)), IVec2::try_from(UVec2::new(3, 4)));
+        assert!(IVec2::try_from(UVec2::new(u32::MAX, 4)).is_err());
+
+        assert_eq!(Ok(UVec2::new(3, 4)), UVec2::try_from(IVec2::new(3, 4)));
+        assert!(UVec2::try_from(IVec2::new(-3, 4)).is_err());
+        assert!(UVec2::try_from(IVec2::new(3, -4)).is_err());
+    }
+
+    #[test]
+    fn test_into_u32_saturating()
+    {
+        assert_eq!(UVec2::new(3, 4), IVec2::new(3, 4).into_u32_saturating());
+        assert_eq!(UVec2::new(0, 4), IVec2::new(-3, 4).into_u32_saturating());
+        assert_eq!(UVec2::new(3, 0), IVec2::new(3, -4).into_u32_saturating());
+        assert_eq!(
+            UVec2::new(0, 0),
+            IVec2::new(i32::MIN, -1).into_u32_saturating()
+        );
+        assert_eq!(
+            UVec2::new(i32::MAX as u32, 0),
+            IVec2::new(i32::MAX, 0).into_u32_saturating()
+        );
+    }
+
+    #[test]
+    fn test_componentwise()
+    {
+        let a = IVec2::new(-5, 10);
+        let b = IVec2::new(3, -2);
+
+        assert_eq!(IVec2::new(-5, -2), a.min(b));
+        assert_eq!(IVec2::new(3, 10), a.max(b));
+        assert_eq!(IVec2::new(5, 10), a.abs());
+        assert_eq!(
+            IVec2::new(0, 5),
+            a.clamp(IVec2::new(0, 0), IVec2::new(5, 5))
+        );
+
+        assert_eq!(UVec2::new(4, 2), UVec2::new(10, 2).min(UVec2::new(4, 7)));
+        assert_eq!(Vec2::new(1.5, 0.0), Vec2::new(-1.5, 0.0).abs());
+        assert_eq!(
+            Vec2::new(1.0, -1.0),
+            Vec2::new(3.0, -7.0).clamp(Vec2::new(-1.0, -1.0), Vec2::new(1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_floor_ceil_round()
+    {
+        let v = Vec2::new(1.5, -2.3);
+
+        assert_eq!(IVec2::new(1, -3), v.floor_to_i32());
+        assert_eq!(IVec2::new(2, -2), v.ceil_to_i32());
+        assert_eq!(IVec2::new(2, -2), v.round_to_i32());
+        assert_eq!(Vec2::new(2.0, -2.0), RoundFloat::round(&v));
+    }
+
     #[test]
     fn test_arithmetic()
     {
@@ -14,9 +14,9 @@
  *  limitations under the License.
  */
 
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::convert::TryInto;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::iter::Peekable;
 use std::ops::Deref;
@@ -29,7 +29,8 @@ use rusttype::Scale;
 use smallvec::{smallvec, SmallVec};
 use unicode_normalization::UnicodeNormalization;
 
-use crate::dimen::{Vec2, Vector2};
+use crate::color::Color;
+use crate::dimen::{IVec2, UVec2, Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
 use crate::shape::{Rect, Rectangle};
 
@@ -45,6 +46,22 @@ pub type UserGlyphIndex = u32;
 /// unique ID.
 pub type FontId = usize;
 
+/// The horizontal shear applied to synthetic italic glyphs, as a proportion of
+/// the distance above the baseline.
+pub(crate) const SYNTHETIC_ITALIC_SHEAR: f32 = 0.2;
+
+/// The distance (as a proportion of the font scale) by which synthetic bold
+/// glyphs are thickened.
+const SYNTHETIC_BOLD_RATIO: f32 = 1.0 / 32.0;
+
+/// The thickness (as a proportion of the font scale) of underline and
+/// strikethrough lines.
+const DECORATION_THICKNESS_RATIO: f32 = 1.0 / 16.0;
+
+/// The height of the strikethrough line above the baseline, as a proportion of
+/// the line's ascent.
+const STRIKETHROUGH_ASCENT_RATIO: f32 = 0.3;
+
 type FormattedGlyphVec = SmallVec<[FormattedGlyph; 8]>;
 type FormattedTextLineVec = SmallVec<[FormattedTextLine; 1]>;
 
@@ -241,6 +258,7 @@ struct LineLayoutMetrics
     min_descent: f32,
     max_line_gap: f32,
     last_glyph_id: Option<rusttype::GlyphId>,
+    last_glyph_x_pos: f32,
     last_font_id: Option<FontId>
 }
 
@@ -254,6 +272,7 @@ impl LineLayoutMetrics
             min_descent: 0.0,
             max_line_gap: 0.0,
             last_glyph_id: None,
+            last_glyph_x_pos: 0.0,
             last_font_id: None
         }
     }
@@ -279,7 +298,9 @@ impl LineLayoutMetrics
                     glyph.font().pair_kerning(*scale, last_glyph_id, glyph.id());
             }
 
-            self.x_pos += options.tracking;
+            // Negative tracking must not move a glyph behind the previous one
+            self.x_pos =
+                crate::numeric::max(self.x_pos + options.tracking, self.last_glyph_x_pos);
         }
 
         if self.last_font_id != Some(font_id) {
@@ -291,13 +312,15 @@ impl LineLayoutMetrics
                 crate::numeric::max(self.max_line_gap, v_metrics.line_gap);
         }
 
-        let advance_width = glyph.h_metrics().advance_width;
+        let advance_width =
+            glyph.h_metrics().advance_width + options.synthetic_bold_offset(scale);
 
         let glyph_x_pos_start = self.x_pos;
         self.x_pos += advance_width;
 
         self.last_font_id = Some(font_id);
         self.last_glyph_id = Some(glyph.id());
+        self.last_glyph_x_pos = glyph_x_pos_start;
 
         glyph_x_pos_start
     }
@@ -349,6 +372,14 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
 
     let mut glyphs = FormattedGlyphVec::new();
 
+    // The codepoint index, glyph count, and metrics at the start of the
+    // current grapheme cluster. Words are only split between clusters.
+    let mut cluster_start = (0, 0, new_word_metrics.clone());
+
+    // Set if the first cluster in the word goes over the boundary, in which
+    // case the word is split after that cluster
+    let mut split_after_cluster = false;
+
     for (
         i,
         Codepoint {
@@ -357,20 +388,39 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
         }
     ) in word.codepoints.iter().enumerate()
     {
+        if i > 0 && !continues_grapheme_cluster(word.codepoints[i - 1].codepoint, *c) {
+            if split_after_cluster {
+                remaining_words.add_pending(Word::Renderable(
+                    word.starting_from_codepoint_location(i)
+                ));
+
+                finish_word_layout(
+                    &mut glyphs,
+                    pos_y_baseline,
+                    &new_word_metrics,
+                    output
+                );
+                return WordLayoutResult::PartialWord(new_word_metrics);
+            }
+
+            cluster_start = (i, glyphs.len(), new_word_metrics.clone());
+        }
+
         // We can't modify the actual values until we're sure we can render this glyph
         let mut new_glyph_metrics = new_word_metrics.clone();
 
-        let glyph = match layout_helper.lookup_glyph_for_codepoint(*c) {
+        let (glyph, is_missing) = match layout_helper.lookup_glyph_for_codepoint(*c) {
             None => {
                 match layout_helper
                     .lookup_glyph_for_codepoint('□')
                     .or_else(|| layout_helper.lookup_glyph_for_codepoint('?'))
+                    .or_else(|| layout_helper.missing_glyph())
                 {
                     None => continue,
-                    Some(glyph) => glyph
+                    Some(glyph) => (glyph, true)
                 }
             }
-            Some(glyph) => glyph
+            Some(glyph) => (glyph, false)
         };
 
         let scaled_glyph = glyph.glyph.scaled(*scale);
@@ -385,40 +435,45 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
         let formatted_glyph = FormattedGlyph {
             user_index: *user_index,
             glyph: scaled_glyph.positioned(rusttype::point(glyph_x_pos_start, 0.0)),
-            font_id: glyph.font.id()
+            font_id: glyph.font.id(),
+            codepoint: *c,
+            synthetic_bold_offset: options.synthetic_bold_offset(scale),
+            synthetic_italic: options.italic,
+            color: None,
+            color_bitmap_font: glyph.font.color_bitmap_font.clone(),
+            is_missing
         };
 
         if let Some(pos_x_max) = pos_x_max {
-            if new_glyph_metrics.x_pos > pos_x_max {
-                return if first_word_on_line {
-                    if i == 0 {
-                        // First glyph in word, we should render it even though it goes
-                        // over the boundary
-                        glyphs.push(formatted_glyph);
-                        new_word_metrics = new_glyph_metrics;
-
-                        // If there are more codepoints, we need to split the word
-                        if word.codepoints.len() > 1 {
-                            remaining_words.add_pending(Word::Renderable(
-                                word.starting_from_codepoint_location(i + 1)
-                            ));
-                        }
-                    } else {
-                        remaining_words.add_pending(Word::Renderable(
-                            word.starting_from_codepoint_location(i)
-                        ));
+            if new_glyph_metrics.x_pos > pos_x_max && !split_after_cluster {
+                if !first_word_on_line {
+                    remaining_words.add_pending(Word::Renderable(word));
+                    return WordLayoutResult::NotEnoughSpace;
+                }
+
+                match options.overflow_wrap {
+                    // Allow the word to go over the boundary
+                    OverflowWrap::Normal => {}
+
+                    OverflowWrap::BreakWord if cluster_start.0 == 0 => {
+                        // First cluster in word, we should render it even though
+                        // it goes over the boundary
+                        split_after_cluster = true;
                     }
 
-                    glyphs.iter_mut().for_each(|glyph| {
-                        glyph.reposition_y(pos_y_baseline + new_word_metrics.max_ascent);
-                    });
+                    OverflowWrap::BreakWord => {
+                        let (split_location, glyph_count, metrics) = cluster_start;
 
-                    output.append(&mut glyphs);
-                    WordLayoutResult::PartialWord(new_word_metrics)
-                } else {
-                    remaining_words.add_pending(Word::Renderable(word));
-                    WordLayoutResult::NotEnoughSpace
-                };
+                        glyphs.truncate(glyph_count);
+
+                        remaining_words.add_pending(Word::Renderable(
+                            word.starting_from_codepoint_location(split_location)
+                        ));
+
+                        finish_word_layout(&mut glyphs, pos_y_baseline, &metrics, output);
+                        return WordLayoutResult::PartialWord(metrics);
+                    }
+                }
             }
         }
 
@@ -426,13 +481,344 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
         new_word_metrics = new_glyph_metrics;
     }
 
+    finish_word_layout(&mut glyphs, pos_y_baseline, &new_word_metrics, output);
+
+    match split_after_cluster {
+        true => WordLayoutResult::PartialWord(new_word_metrics),
+        false => WordLayoutResult::Success(new_word_metrics)
+    }
+}
+
+fn finish_word_layout(
+    glyphs: &mut FormattedGlyphVec,
+    pos_y_baseline: f32,
+    metrics: &LineLayoutMetrics,
+    output: &mut FormattedGlyphVec
+)
+{
     glyphs.iter_mut().for_each(|glyph| {
-        glyph.reposition_y(pos_y_baseline + new_word_metrics.max_ascent);
+        glyph.reposition_y(pos_y_baseline + metrics.max_ascent);
     });
 
-    output.append(&mut glyphs);
+    output.append(glyphs);
+}
+
+/// A simplified check for grapheme cluster boundaries. Returns true if
+/// `codepoint` continues the cluster containing `previous`, for example if it
+/// is a combining mark, or part of an emoji sequence.
+fn continues_grapheme_cluster(previous: char, codepoint: char) -> bool
+{
+    let is_regional_indicator = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+
+    previous == '\u{200D}'
+        || (is_regional_indicator(previous) && is_regional_indicator(codepoint))
+        || matches!(
+            codepoint,
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{200C}'..='\u{200D}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FE20}'..='\u{FE2F}'
+                | '\u{1F3FB}'..='\u{1F3FF}'
+                | '\u{E0020}'..='\u{E007F}'
+                | '\u{E0100}'..='\u{E01EF}'
+        )
+}
+
+/// A simplified version of the bidirectional character types defined by the
+/// Unicode Bidirectional Algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BidiClass
+{
+    /// Strongly left-to-right, for example Latin letters.
+    Left,
+    /// Strongly right-to-left, for example Hebrew and Arabic letters.
+    Right,
+    /// Digits, which are always laid out left-to-right, but take on the
+    /// direction of the surrounding text.
+    Number,
+    /// Whitespace and punctuation, which take on the direction of the
+    /// surrounding text.
+    Neutral
+}
+
+impl BidiClass
+{
+    fn of(codepoint: char) -> Self
+    {
+        match codepoint {
+            '0'..='9' | '\u{0660}'..='\u{0669}' | '\u{06F0}'..='\u{06F9}' => {
+                BidiClass::Number
+            }
+            '\u{0590}'..='\u{08FF}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}' => BidiClass::Right,
+            c if c.is_alphabetic() => BidiClass::Left,
+            _ => BidiClass::Neutral
+        }
+    }
+}
+
+fn mirrored_codepoint(codepoint: char) -> Option<char>
+{
+    match codepoint {
+        '(' => Some(')'),
+        ')' => Some('('),
+        '[' => Some(']'),
+        ']' => Some('['),
+        '{' => Some('}'),
+        '}' => Some('{'),
+        '<' => Some('>'),
+        '>' => Some('<'),
+        '«' => Some('»'),
+        '»' => Some('«'),
+        '‹' => Some('›'),
+        '›' => Some('‹'),
+        _ => None
+    }
+}
+
+/// Reorders the glyphs in a line from logical order to visual order, using a
+/// simplified version of the Unicode Bidirectional Algorithm, and mirrors
+/// brackets in right-to-left runs.
+///
+/// If the glyphs were moved, they are repositioned, and the new width of the
+/// line is returned.
+fn reorder_bidi_line<T: TextLayout + ?Sized>(
+    layout_helper: &T,
+    glyphs: &mut FormattedGlyphVec,
+    scale: &Scale,
+    options: &TextOptions,
+    right_to_left: bool
+) -> Option<f32>
+{
+    let classes: Vec<BidiClass> = glyphs
+        .iter()
+        .map(|glyph| BidiClass::of(glyph.codepoint))
+        .collect();
+
+    if !right_to_left && !classes.contains(&BidiClass::Right) {
+        return None;
+    }
+
+    let base_level: u8 = if right_to_left { 1 } else { 0 };
+
+    // The direction of each glyph, or `None` for neutral glyphs. Numbers take
+    // on the direction of the preceding strong character.
+    let mut previous_rtl = right_to_left;
+
+    let directions: Vec<Option<bool>> = classes
+        .iter()
+        .map(|class| match class {
+            BidiClass::Left => {
+                previous_rtl = false;
+                Some(false)
+            }
+            BidiClass::Right => {
+                previous_rtl = true;
+                Some(true)
+            }
+            BidiClass::Number => Some(previous_rtl),
+            BidiClass::Neutral => None
+        })
+        .collect();
+
+    // Neutral glyphs between two glyphs of the same direction take on that
+    // direction, otherwise they take on the direction of the paragraph
+    let mut levels: Vec<u8> = Vec::with_capacity(glyphs.len());
+    let mut previous_direction = right_to_left;
+
+    for (i, class) in classes.iter().enumerate() {
+        let rtl = match directions[i] {
+            Some(rtl) => {
+                previous_direction = rtl;
+                rtl
+            }
+            None => {
+                let next_direction = directions[i + 1..]
+                    .iter()
+                    .find_map(|direction| *direction)
+                    .unwrap_or(right_to_left);
+
+                if previous_direction == next_direction {
+                    next_direction
+                } else {
+                    right_to_left
+                }
+            }
+        };
+
+        levels.push(match (class, rtl) {
+            (BidiClass::Number, true) => 2,
+            (_, true) => 1,
+            (_, false) => base_level + base_level % 2
+        });
+    }
+
+    // Trailing whitespace always takes the paragraph direction
+    for (glyph, level) in glyphs.iter().zip(levels.iter_mut()).rev() {
+        if !glyph.codepoint.is_whitespace() {
+            break;
+        }
+
+        *level = base_level;
+    }
+
+    for (glyph, level) in glyphs.iter_mut().zip(levels.iter()) {
+        if level % 2 == 1 {
+            if let Some(font_glyph) = mirrored_codepoint(glyph.codepoint)
+                .and_then(|mirrored| layout_helper.lookup_glyph_for_codepoint(mirrored))
+            {
+                glyph.replace_glyph(font_glyph, scale);
+            }
+        }
+    }
+
+    // From the highest level to the lowest odd level, reverse any sequence of
+    // glyphs at that level or higher
+    let mut visual_order: Vec<(u8, usize)> = levels.into_iter().zip(0..).collect();
+
+    let max_level = visual_order.iter().map(|(level, _)| *level).max()?;
+
+    for level in (1..=max_level).rev() {
+        let mut start = 0;
+
+        while start < visual_order.len() {
+            if visual_order[start].0 < level {
+                start += 1;
+                continue;
+            }
+
+            let mut end = start;
+
+            while end < visual_order.len() && visual_order[end].0 >= level {
+                end += 1;
+            }
+
+            visual_order[start..end].reverse();
+            start = end;
+        }
+    }
+
+    if visual_order
+        .iter()
+        .enumerate()
+        .all(|(position, (_, index))| position == *index)
+    {
+        return None;
+    }
+
+    let mut logical_glyphs: Vec<Option<FormattedGlyph>> =
+        std::mem::take(glyphs).into_iter().map(Some).collect();
+
+    let mut x_pos = 0.0;
+
+    for (position, (_, index)) in visual_order.iter().enumerate() {
+        let mut glyph = logical_glyphs[*index].take().unwrap();
+
+        if position > 0 {
+            x_pos += options.tracking;
+        }
+
+        glyph.set_position_x(x_pos);
+        x_pos += glyph.advance_width();
+
+        glyphs.push(glyph);
+    }
+
+    Some(x_pos)
+}
+
+/// Removes glyphs from the end of the line until there is enough space to
+/// append an ellipsis without exceeding `max_width`, then appends the
+/// ellipsis. The font's `…` glyph is used if present, otherwise three full
+/// stops are used instead.
+fn truncate_line_with_ellipsis<T: TextLayout + ?Sized>(
+    layout_helper: &T,
+    glyphs: &mut FormattedGlyphVec,
+    line_metrics: &mut LineLayoutMetrics,
+    scale: &Scale,
+    options: &TextOptions,
+    pos_y_baseline: f32,
+    max_width: f32
+)
+{
+    let ellipsis: &[char] = match layout_helper.lookup_glyph_for_codepoint('…') {
+        Some(_) => &['…'],
+        None => &['.', '.', '.']
+    };
+
+    let mut ellipsis_user_index =
+        glyphs.last().map(|glyph| glyph.user_index).unwrap_or(0);
+
+    loop {
+        while glyphs
+            .last()
+            .map(|glyph| glyph.codepoint.is_whitespace())
+            .unwrap_or(false)
+        {
+            ellipsis_user_index = glyphs.pop().unwrap().user_index;
+        }
+
+        let mut metrics = line_metrics.clone();
+
+        match glyphs.last() {
+            None => {
+                metrics.x_pos = 0.0;
+                metrics.last_glyph_id = None;
+                metrics.last_glyph_x_pos = 0.0;
+                metrics.last_font_id = None;
+            }
+            Some(last) => {
+                metrics.x_pos = last.position_x() + last.advance_width();
+                metrics.last_glyph_id = Some(last.glyph.id());
+                metrics.last_glyph_x_pos = last.position_x();
+                metrics.last_font_id = Some(last.font_id);
+            }
+        }
+
+        let mut ellipsis_glyphs = FormattedGlyphVec::new();
+
+        for codepoint in ellipsis {
+            let glyph = match layout_helper.lookup_glyph_for_codepoint(*codepoint) {
+                None => continue,
+                Some(glyph) => glyph
+            };
+
+            let scaled_glyph = glyph.glyph.scaled(*scale);
+
+            let glyph_x_pos_start = metrics.update_and_get_render_pos_x(
+                &scaled_glyph,
+                glyph.font.id(),
+                scale,
+                options
+            );
+
+            ellipsis_glyphs.push(FormattedGlyph {
+                user_index: ellipsis_user_index,
+                glyph: scaled_glyph.positioned(rusttype::point(glyph_x_pos_start, 0.0)),
+                font_id: glyph.font.id(),
+                codepoint: *codepoint,
+                synthetic_bold_offset: options.synthetic_bold_offset(scale),
+                synthetic_italic: options.italic,
+                color: None,
+                color_bitmap_font: glyph.font.color_bitmap_font.clone(),
+                is_missing: false
+            });
+        }
+
+        if metrics.x_pos <= max_width || glyphs.is_empty() {
+            finish_word_layout(&mut ellipsis_glyphs, pos_y_baseline, &metrics, glyphs);
+            *line_metrics = metrics;
+            return;
+        }
 
-    WordLayoutResult::Success(new_word_metrics)
+        ellipsis_user_index = glyphs.pop().unwrap().user_index;
+    }
 }
 
 fn layout_line_internal<T: TextLayout + ?Sized>(
@@ -440,7 +826,8 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
     words: &mut WordsIterator,
     scale: &Scale,
     options: &TextOptions,
-    pos_y_baseline: f32
+    pos_y_baseline: f32,
+    right_to_left: bool
 ) -> FormattedTextLine
 {
     let mut line_metrics = LineLayoutMetrics::new();
@@ -483,6 +870,26 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         first_word_on_line = false;
     }
 
+    if let Some(max_width) = options.ellipsis_width {
+        if line_metrics.x_pos > max_width {
+            truncate_line_with_ellipsis(
+                layout_helper,
+                &mut glyphs,
+                &mut line_metrics,
+                scale,
+                options,
+                pos_y_baseline,
+                max_width
+            );
+        }
+    }
+
+    if let Some(width) =
+        reorder_bidi_line(layout_helper, &mut glyphs, scale, options, right_to_left)
+    {
+        line_metrics.x_pos = width;
+    }
+
     if glyphs.is_empty() {
         let empty_metrics = layout_helper.empty_line_vertical_metrics(scale.y);
         line_metrics.max_ascent = empty_metrics.ascent;
@@ -490,17 +897,21 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         line_metrics.max_line_gap = empty_metrics.line_gap;
     }
 
+    let mut offset_x = 0.0;
+
     if let Some(max_width) = options.wrap_words_after_width {
-        let offset_x = match options.alignment {
+        let alignment_offset_x = match options.alignment {
             TextAlignment::Left => None,
             TextAlignment::Center => Some((max_width - line_metrics.x_pos) / 2.0),
             TextAlignment::Right => Some(max_width - line_metrics.x_pos)
         };
 
-        if let Some(offset_x) = offset_x {
+        if let Some(alignment_offset_x) = alignment_offset_x {
             for glyph in glyphs.iter_mut() {
-                glyph.add_offset_x(offset_x);
+                glyph.add_offset_x(alignment_offset_x);
             }
+
+            offset_x = alignment_offset_x;
         }
     }
 
@@ -511,7 +922,12 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         height: line_metrics.height(),
         ascent: line_metrics.max_ascent,
         descent: line_metrics.min_descent,
-        line_gap: line_metrics.max_line_gap
+        line_gap: line_metrics.max_line_gap,
+        offset_x,
+        underline: options.underline,
+        strikethrough: options.strikethrough,
+        decoration_thickness: options.decoration_thickness(scale),
+        pixel_snapping: options.pixel_snapping
     }
 }
 
@@ -524,6 +940,17 @@ fn layout_multiple_lines_internal<T: TextLayout + ?Sized>(
 {
     let scale = Scale::uniform(scale);
 
+    let right_to_left = match options.direction {
+        TextDirection::LeftToRight => false,
+        TextDirection::RightToLeft => true,
+        TextDirection::Auto => codepoints
+            .iter()
+            .map(|codepoint| BidiClass::of(codepoint.codepoint))
+            .find(|class| *class == BidiClass::Left || *class == BidiClass::Right)
+            .map(|class| class == BidiClass::Right)
+            .unwrap_or(false)
+    };
+
     let mut iterator = WordsIterator::from(Word::split_words(codepoints));
 
     let mut pos_y = 0.0;
@@ -532,8 +959,14 @@ fn layout_multiple_lines_internal<T: TextLayout + ?Sized>(
     let mut width = 0.0;
 
     while iterator.has_next() {
-        let line =
-            layout_line_internal(layout_helper, &mut iterator, &scale, &options, pos_y);
+        let line = layout_line_internal(
+            layout_helper,
+            &mut iterator,
+            &scale,
+            &options,
+            pos_y,
+            right_to_left
+        );
 
         pos_y += line.height * options.line_spacing_multiplier;
 
@@ -549,7 +982,9 @@ fn layout_multiple_lines_internal<T: TextLayout + ?Sized>(
     FormattedTextBlock {
         lines: Arc::new(lines),
         width,
-        height: pos_y
+        height: pos_y,
+        vertical_offset: 0.0,
+        pixel_snapping: options.pixel_snapping
     }
 }
 
@@ -582,6 +1017,18 @@ pub trait TextLayout
     /// cannot be found, `None` is returned.
     fn lookup_glyph_for_codepoint(&self, codepoint: char) -> Option<FontGlyph>;
 
+    /// Returns the glyph to display for codepoints which cannot be found, if
+    /// neither `□` nor `?` are available either. This is typically the
+    /// font's "notdef" glyph. If `None` is returned, such codepoints are
+    /// skipped.
+    ///
+    /// The default implementation returns `None`.
+    #[inline]
+    fn missing_glyph(&self) -> Option<FontGlyph>
+    {
+        None
+    }
+
     /// Lays out a block of text with the specified scale and options. The
     /// result may be passed to `Graphics2D::draw_text`.
     ///
@@ -641,17 +1088,164 @@ pub trait TextLayout
         layout_multiple_lines_internal(self, codepoints, scale, options)
     }
 
+    /// Lays out a block of text made up of several spans, each with its own
+    /// color. The spans are laid out as a single block of text, so words and
+    /// lines may span more than one of them. The result may be passed to
+    /// `Graphics2D::draw_text`, which draws each glyph using the color of its
+    /// span, instead of the color passed to `draw_text()`. Any underline or
+    /// strikethrough is drawn using the color passed to `draw_text()`.
+    ///
+    /// As with `layout_text()`, each span undergoes normalization before
+    /// being laid out, so the `user_index` of each `FormattedGlyph` is
+    /// undefined.
+    #[must_use]
+    fn layout_spans(
+        &self,
+        spans: &[(&str, Color)],
+        scale: f32,
+        options: TextOptions
+    ) -> FormattedTextBlock
+    {
+        let mut codepoints = Vec::new();
+        let mut span_ends = Vec::with_capacity(spans.len());
+
+        for (text, _) in spans {
+            codepoints.extend(text.nfc());
+            span_ends.push(codepoints.len());
+        }
+
+        self.layout_text_from_unindexed_codepoints(codepoints.as_slice(), scale, options)
+            .with_glyph_colors(|user_index| {
+                let span = span_ends.partition_point(|end| *end <= user_index as usize);
+                spans[span].1
+            })
+    }
+
+    /// Lays out a block of text within a box of the specified size, for
+    /// example to center a label inside a button. The text is wrapped to the
+    /// width of the box, and aligned horizontally and vertically within it.
+    /// Any wrapping width or alignment set in `options` is replaced.
+    ///
+    /// The result may be passed to `Graphics2D::draw_text`, using the top
+    /// left corner of the box as the position. The vertical position of the
+    /// text within the box is available using
+    /// [FormattedTextBlock::vertical_offset]. If the text is taller than the
+    /// box, it will extend outside the box.
+    #[must_use]
+    fn layout_text_in_box(
+        &self,
+        text: &str,
+        scale: f32,
+        box_size: Vec2,
+        horizontal_alignment: TextAlignment,
+        vertical_alignment: VerticalAlignment,
+        options: TextOptions
+    ) -> FormattedTextBlock
+    {
+        let block = self.layout_text(
+            text,
+            scale,
+            options.with_wrap_to_width(box_size.x, horizontal_alignment)
+        );
+
+        let offset_y = match vertical_alignment {
+            VerticalAlignment::Top => 0.0,
+            VerticalAlignment::Middle => (box_size.y - block.height()) / 2.0,
+            VerticalAlignment::Bottom => box_size.y - block.height()
+        };
+
+        block.with_vertical_offset(offset_y)
+    }
+
+    /// Returns the size (in pixels) that the specified text would occupy if
+    /// laid out and drawn with the specified scale and options, including any
+    /// wrapping. This is equal to the size of the block returned by
+    /// `layout_text()`, and is useful for sizing UI elements to fit their
+    /// labels.
+    #[inline]
+    #[must_use]
+    fn measure_text(&self, text: &str, scale: f32, options: TextOptions) -> Vec2
+    {
+        self.layout_text(text, scale, options).size()
+    }
+
     /// The default metrics of a line which contains no characters.
     #[must_use]
     fn empty_line_vertical_metrics(&self, scale: f32) -> LineVerticalMetrics;
 }
 
+/// The type of error encountered when loading a [Font].
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum FontLoadErrorKind
+{
+    /// The data is not a TrueType or OpenType font.
+    NotAFont,
+    /// The data is a font, but in a format which is not supported, such as
+    /// WOFF.
+    UnsupportedFormat,
+    /// The font doesn't contain glyph outlines in a supported format, so no
+    /// text could be drawn using it. TrueType (`glyf`) and CFF outlines are
+    /// supported.
+    UnsupportedOutlines,
+    /// The font is missing one or more tables which are required to lay out
+    /// and draw text, such as `cmap` or `hmtx`.
+    MissingRequiredTables,
+    /// The font data is truncated or malformed.
+    Malformed
+}
+
+/// The reason a [Font] could not be loaded. This is available as the
+/// [BacktraceError::cause] of the error returned by [Font::new], and can be
+/// retrieved using `downcast_ref::<FontLoadError>()`.
+#[derive(Clone, Debug)]
+pub struct FontLoadError
+{
+    kind: FontLoadErrorKind,
+    description: String
+}
+
+impl FontLoadError
+{
+    fn msg<S: AsRef<str>>(
+        kind: FontLoadErrorKind,
+        description: S
+    ) -> BacktraceError<ErrorMessage>
+    {
+        let description = description.as_ref().to_string();
+
+        ErrorMessage::msg_with_cause(
+            format!("Failed to load font: {}", description),
+            Self { kind, description }
+        )
+    }
+
+    /// The type of error which occurred.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> FontLoadErrorKind
+    {
+        self.kind
+    }
+}
+
+impl Display for FontLoadError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        Display::fmt(&self.description, f)
+    }
+}
+
+impl std::error::Error for FontLoadError {}
+
 /// A struct representing a font.
 #[derive(Clone)]
 pub struct Font
 {
     id: usize,
-    font: Arc<rusttype::Font<'static>>
+    data: Arc<Vec<u8>>,
+    font: Arc<rusttype::Font<'static>>,
+    color_bitmap_font: Option<Arc<ColorBitmapFont>>
 }
 
 impl Font
@@ -660,17 +1254,80 @@ impl Font
     ///
     /// The font may be in TrueType or OpenType format. Support for OpenType
     /// fonts may be limited.
+    ///
+    /// Fonts containing color bitmap glyphs (such as emoji fonts) are
+    /// supported if the `image-loading` feature is enabled. Glyphs stored as
+    /// PNG images in the `sbix` or `CBDT` tables are drawn in full color,
+    /// ignoring the red, green, and blue components of the text color. Other
+    /// color formats (such as `COLR` or `SVG` glyphs) are not supported, and
+    /// those glyphs are drawn using their monochrome outlines, if present.
+    ///
+    /// If the font cannot be loaded, the returned error describes the problem.
+    /// Its cause is a [FontLoadError], which gives the type of error (see
+    /// [FontLoadErrorKind]).
     pub fn new(bytes: &[u8]) -> Result<Font, BacktraceError<ErrorMessage>>
     {
-        let font = rusttype::Font::try_from_vec(bytes.to_vec())
-            .ok_or_else(|| ErrorMessage::msg("Failed to load font"))?;
+        validate_font_data(bytes)?;
+
+        let font = rusttype::Font::try_from_vec(bytes.to_vec()).ok_or_else(|| {
+            FontLoadError::msg(
+                FontLoadErrorKind::Malformed,
+                "the font data is malformed, or uses unsupported features"
+            )
+        })?;
+
+        let data = Arc::new(bytes.to_vec());
+        let color_bitmap_font = ColorBitmapFont::load(&data, &font).map(Arc::new);
 
         Ok(Font {
             id: FONT_ID_GENERATOR.fetch_add(1, Ordering::SeqCst),
-            font: Arc::new(font)
+            data,
+            font: Arc::new(font),
+            color_bitmap_font
         })
     }
 
+    /// Returns true if the font contains a glyph for the specified character.
+    ///
+    /// This can be used to check whether the font covers the script needed
+    /// for some text, before it is laid out. Characters which are not present
+    /// are drawn using a placeholder glyph (see [FormattedGlyph::is_missing]).
+    #[inline]
+    #[must_use]
+    pub fn contains_char(&self, codepoint: char) -> bool
+    {
+        self.font().glyph(codepoint).id().0 != 0
+    }
+
+    /// Returns every character for which the font contains a glyph, based on
+    /// the font's character map, in ascending order.
+    pub fn coverage(&self) -> impl Iterator<Item = char>
+    {
+        let mut coverage = BTreeSet::new();
+
+        let cmap = ttf_parser::Face::from_slice(&self.data, 0)
+            .ok()
+            .and_then(|face| face.tables().cmap);
+
+        if let Some(cmap) = cmap {
+            for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+                subtable.codepoints(|codepoint| {
+                    let has_glyph = subtable
+                        .glyph_index(codepoint)
+                        .is_some_and(|glyph_id| glyph_id.0 != 0);
+
+                    if let Some(codepoint) =
+                        char::from_u32(codepoint).filter(|_| has_glyph)
+                    {
+                        coverage.insert(codepoint);
+                    }
+                });
+            }
+        }
+
+        coverage.into_iter()
+    }
+
     #[inline]
     fn id(&self) -> usize
     {
@@ -697,6 +1354,13 @@ impl TextLayout for FontFamily
         None
     }
 
+    fn missing_glyph(&self) -> Option<FontGlyph>
+    {
+        Arc::deref(&self.fonts)
+            .first()
+            .and_then(|font| font.missing_glyph())
+    }
+
     fn empty_line_vertical_metrics(&self, scale: f32) -> LineVerticalMetrics
     {
         match Arc::deref(&self.fonts).first() {
@@ -733,6 +1397,14 @@ impl TextLayout for Font
         }
     }
 
+    fn missing_glyph(&self) -> Option<FontGlyph>
+    {
+        Some(FontGlyph {
+            glyph: self.font().glyph(rusttype::GlyphId(0)),
+            font: self.clone()
+        })
+    }
+
     fn empty_line_vertical_metrics(&self, scale: f32) -> LineVerticalMetrics
     {
         let metrics = self.font.v_metrics(Scale::uniform(scale));
@@ -744,33 +1416,305 @@ impl TextLayout for Font
     }
 }
 
-impl PartialEq for Font
+/// A font which contains color bitmap glyphs, stored as PNG images in the
+/// `sbix` or `CBDT` tables.
+pub(crate) struct ColorBitmapFont
 {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool
-    {
-        self.id() == other.id()
-    }
-}
+    #[cfg_attr(not(feature = "image-loading"), allow(dead_code))]
+    data: Arc<Vec<u8>>,
 
-impl Eq for Font {}
+    /// The number of pixels per em for each pixel of scale, as defined by
+    /// `rusttype::Scale`.
+    #[cfg_attr(not(feature = "image-loading"), allow(dead_code))]
+    pixels_per_em_per_scale: f32
+}
 
-impl Hash for Font
+/// A color glyph image, resized for a particular scale.
+pub(crate) struct ColorGlyphBitmap
 {
-    #[inline]
-    fn hash<H: Hasher>(&self, state: &mut H)
-    {
-        self.id().hash(state);
-    }
+    /// The size of the image, in pixels.
+    pub size: UVec2,
+
+    /// The position of the top left of the image, relative to the glyph's
+    /// origin on the baseline.
+    pub offset: IVec2,
+
+    /// The pixel data, in RGBA format without premultiplied alpha.
+    pub data: Vec<u8>
 }
 
-impl Debug for Font
+impl ColorBitmapFont
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result
+    fn load(data: &Arc<Vec<u8>>, font: &rusttype::Font) -> Option<Self>
     {
-        f.debug_struct("Font").field("id", &self.id()).finish()
+        if !cfg!(feature = "image-loading") || !has_color_bitmap_tables(data) {
+            return None;
+        }
+
+        let v_metrics = font.v_metrics_unscaled();
+        let height = v_metrics.ascent - v_metrics.descent;
+
+        if height <= 0.0 {
+            return None;
+        }
+
+        log::info!("Font contains color bitmap glyphs");
+
+        Some(ColorBitmapFont {
+            data: data.clone(),
+            pixels_per_em_per_scale: font.units_per_em() as f32 / height
+        })
     }
-}
+
+    /// Returns the image for the specified glyph, or `None` if the font
+    /// doesn't contain a supported image for that glyph.
+    #[cfg(feature = "image-loading")]
+    pub(crate) fn rasterize(
+        &self,
+        glyph_id: rusttype::GlyphId,
+        scale: f32
+    ) -> Option<ColorGlyphBitmap>
+    {
+        let face = ttf_parser::Face::from_slice(&self.data, 0).ok()?;
+
+        let pixels_per_em = scale * self.pixels_per_em_per_scale;
+        let strike_pixels_per_em = pixels_per_em.round().clamp(1.0, u16::MAX as f32);
+
+        let image = face.glyph_raster_image(
+            ttf_parser::GlyphId(glyph_id.0),
+            strike_pixels_per_em as u16
+        )?;
+
+        if image.format != ttf_parser::RasterImageFormat::PNG {
+            return None;
+        }
+
+        let decoded =
+            image::load_from_memory_with_format(image.data, image::ImageFormat::Png)
+                .map_err(|err| log::warn!("Failed to decode color glyph: {:?}", err))
+                .ok()?
+                .into_rgba8();
+
+        // The strike may have been created for a different size
+        let factor = pixels_per_em / image.pixels_per_em as f32;
+
+        let size = UVec2::new(
+            (decoded.width() as f32 * factor).round().max(1.0) as u32,
+            (decoded.height() as f32 * factor).round().max(1.0) as u32
+        );
+
+        let resized = image::imageops::resize(
+            &decoded,
+            size.x,
+            size.y,
+            image::imageops::FilterType::Triangle
+        );
+
+        // The image offset is the position of its bottom left corner, with the
+        // Y axis pointing upwards
+        let offset = IVec2::new(
+            (image.x as f32 * factor).round() as i32,
+            -((image.y as f32 * factor).round() as i32) - size.y as i32
+        );
+
+        Some(ColorGlyphBitmap {
+            size,
+            offset,
+            data: resized.into_raw()
+        })
+    }
+
+    #[cfg(not(feature = "image-loading"))]
+    pub(crate) fn rasterize(
+        &self,
+        _glyph_id: rusttype::GlyphId,
+        _scale: f32
+    ) -> Option<ColorGlyphBitmap>
+    {
+        None
+    }
+}
+
+/// The tables which must be present in every font.
+const REQUIRED_FONT_TABLES: [&str; 5] = ["cmap", "head", "hhea", "hmtx", "maxp"];
+
+/// Checks the header and table directory of the font (or the first font in a
+/// collection), to give a more specific error than the font parser if the
+/// font cannot be loaded.
+fn validate_font_data(bytes: &[u8]) -> Result<(), BacktraceError<ErrorMessage>>
+{
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(
+            bytes.get(offset..offset + 4)?.try_into().ok()?
+        ))
+    };
+
+    let read_u16 = |offset: usize| -> Option<u16> {
+        Some(u16::from_be_bytes(
+            bytes.get(offset..offset + 2)?.try_into().ok()?
+        ))
+    };
+
+    let malformed = || {
+        FontLoadError::msg(
+            FontLoadErrorKind::Malformed,
+            "the font data is truncated or malformed"
+        )
+    };
+
+    let font_offset = match bytes.get(0..4) {
+        None => {
+            return Err(FontLoadError::msg(
+                FontLoadErrorKind::NotAFont,
+                "the data is too short to be a font file"
+            ))
+        }
+        Some(b"\x00\x01\x00\x00") | Some(b"true") | Some(b"OTTO") => 0,
+        Some(b"ttcf") => read_u32(12).ok_or_else(malformed)? as usize,
+        Some(b"wOFF") | Some(b"wOF2") => {
+            return Err(FontLoadError::msg(
+                FontLoadErrorKind::UnsupportedFormat,
+                "WOFF fonts are not supported, please convert the font to TrueType or \
+                 OpenType format"
+            ))
+        }
+        Some(b"typ1") => {
+            return Err(FontLoadError::msg(
+                FontLoadErrorKind::UnsupportedFormat,
+                "PostScript Type 1 fonts are not supported"
+            ))
+        }
+        Some(_) => {
+            return Err(FontLoadError::msg(
+                FontLoadErrorKind::NotAFont,
+                "unrecognized file format, expected TrueType or OpenType font data"
+            ))
+        }
+    };
+
+    let table_count = read_u16(font_offset + 4).ok_or_else(malformed)? as usize;
+    let mut tags = Vec::with_capacity(table_count);
+
+    for i in 0..table_count {
+        let record_offset = font_offset + 12 + i * 16;
+
+        let tag = bytes
+            .get(record_offset..record_offset + 4)
+            .ok_or_else(malformed)?;
+
+        let table_offset = read_u32(record_offset + 8).ok_or_else(malformed)? as usize;
+        let table_length = read_u32(record_offset + 12).ok_or_else(malformed)? as usize;
+
+        match table_offset.checked_add(table_length) {
+            Some(table_end) if table_end <= bytes.len() => tags.push(tag),
+            _ => return Err(malformed())
+        }
+    }
+
+    let has_table = |tag: &str| tags.contains(&tag.as_bytes());
+
+    let mut missing_tables: Vec<&str> = REQUIRED_FONT_TABLES
+        .iter()
+        .copied()
+        .filter(|tag| !has_table(tag))
+        .collect();
+
+    if has_table("glyf") && !has_table("loca") {
+        missing_tables.push("loca");
+    }
+
+    if !missing_tables.is_empty() {
+        return Err(FontLoadError::msg(
+            FontLoadErrorKind::MissingRequiredTables,
+            format!(
+                "the font is missing required tables: {}",
+                missing_tables.join(", ")
+            )
+        ));
+    }
+
+    let has_outlines = has_table("glyf") || has_table("CFF ") || has_table("CFF2");
+
+    let has_color_bitmaps =
+        cfg!(feature = "image-loading") && (has_table("sbix") || has_table("CBDT"));
+
+    if !has_outlines && !has_color_bitmaps {
+        return Err(FontLoadError::msg(
+            FontLoadErrorKind::UnsupportedOutlines,
+            "the font doesn't contain glyph outlines in a supported format (TrueType or \
+             CFF)"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns true if the table directory of the font (or the first font in a
+/// collection) contains an `sbix` or `CBDT` table.
+fn has_color_bitmap_tables(bytes: &[u8]) -> bool
+{
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(
+            bytes.get(offset..offset + 4)?.try_into().ok()?
+        ))
+    };
+
+    let read_u16 = |offset: usize| -> Option<u16> {
+        Some(u16::from_be_bytes(
+            bytes.get(offset..offset + 2)?.try_into().ok()?
+        ))
+    };
+
+    let find_tables = || -> Option<bool> {
+        let font_offset = match bytes.get(0..4)? {
+            b"ttcf" => read_u32(12)? as usize,
+            _ => 0
+        };
+
+        let table_count = read_u16(font_offset + 4)? as usize;
+
+        for i in 0..table_count {
+            let record_offset = font_offset + 12 + i * 16;
+
+            match bytes.get(record_offset..record_offset + 4)? {
+                b"sbix" | b"CBDT" => return Some(true),
+                _ => {}
+            }
+        }
+
+        Some(false)
+    };
+
+    find_tables().unwrap_or(false)
+}
+
+impl PartialEq for Font
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for Font {}
+
+impl Hash for Font
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.id().hash(state);
+    }
+}
+
+impl Debug for Font
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result
+    {
+        f.debug_struct("Font").field("id", &self.id()).finish()
+    }
+}
 
 /// A collection of fonts, in decreasing order of priority. When laying out
 /// text, if a codepoint cannot be found in the first font in the list, the
@@ -807,6 +1751,56 @@ pub enum TextAlignment
     Right
 }
 
+/// The vertical alignment of a block of text within a box. This can be set
+/// when calling [TextLayout::layout_text_in_box].
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum VerticalAlignment
+{
+    /// Align the text to the top of the box.
+    Top,
+    /// Center the text vertically in the box.
+    Middle,
+    /// Align the text to the bottom of the box.
+    Bottom
+}
+
+/// The base direction of a block of text. This can be set when calling
+/// `TextOptions::with_direction`.
+///
+/// Runs of right-to-left text (such as Hebrew or Arabic) are always displayed
+/// in the correct order, and brackets within them are mirrored. The base
+/// direction determines the order of the runs within each line, and the
+/// direction of any punctuation between runs of different directions.
+///
+/// Note that only a simplified version of the Unicode Bidirectional Algorithm
+/// is implemented, without support for explicit embedding characters. Text
+/// shaping (for example, joining Arabic letters) is not supported.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TextDirection
+{
+    /// Lay out each line from left to right.
+    LeftToRight,
+    /// Lay out each line from right to left.
+    RightToLeft,
+    /// Use the direction of the first character in the text which has a
+    /// strong direction, or left-to-right if there is no such character.
+    Auto
+}
+
+/// Determines what happens when a single word is too long to fit within the
+/// wrapping width. This can be set when calling
+/// `TextOptions::with_overflow_wrap`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum OverflowWrap
+{
+    /// Place the word on its own line, allowing it to extend past the
+    /// wrapping width.
+    Normal,
+    /// Break the word between characters, so that it fits within the wrapping
+    /// width. Combining marks and emoji sequences are kept together.
+    BreakWord
+}
+
 /// A series of options for specifying how text should be laid out.
 pub struct TextOptions
 {
@@ -814,7 +1808,15 @@ pub struct TextOptions
     wrap_words_after_width: Option<f32>,
     alignment: TextAlignment,
     line_spacing_multiplier: f32,
-    trim_each_line: bool
+    trim_each_line: bool,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    pixel_snapping: bool,
+    overflow_wrap: OverflowWrap,
+    ellipsis_width: Option<f32>,
+    direction: TextDirection
 }
 
 impl TextOptions
@@ -829,12 +1831,24 @@ impl TextOptions
             wrap_words_after_width: None,
             alignment: TextAlignment::Left,
             line_spacing_multiplier: 1.0,
-            trim_each_line: true
+            trim_each_line: true,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikethrough: false,
+            pixel_snapping: false,
+            overflow_wrap: OverflowWrap::BreakWord,
+            ellipsis_width: None,
+            direction: TextDirection::LeftToRight
         }
     }
 
     /// Sets the tracking of the font. This is the amount of extra space (in
-    /// pixels) to put between each character.
+    /// pixels) to put between each character, and is taken into account when
+    /// wrapping text.
+    ///
+    /// Negative values reduce the space between each character, although a
+    /// character will never be positioned to the left of the previous one.
     ///
     /// The default is `0.0`.
     #[inline]
@@ -864,6 +1878,35 @@ impl TextOptions
         self
     }
 
+    /// Determines what happens when a single word is too long to fit within
+    /// the width set using `with_wrap_to_width()`. See [OverflowWrap] for
+    /// details.
+    ///
+    /// The default is `OverflowWrap::BreakWord`.
+    #[inline]
+    #[must_use]
+    pub fn with_overflow_wrap(mut self, overflow_wrap: OverflowWrap) -> Self
+    {
+        self.overflow_wrap = overflow_wrap;
+        self
+    }
+
+    /// Limits each line of text to the specified pixel width. Lines which are
+    /// wider than this are truncated, and end in an ellipsis (`…`) instead.
+    ///
+    /// If the font doesn't contain a `…` glyph, three full stops are used
+    /// instead.
+    ///
+    /// This is intended for single-line labels, for example in toolbars and
+    /// lists. The default is to not truncate text.
+    #[inline]
+    #[must_use]
+    pub fn with_ellipsis(mut self, max_width: f32) -> Self
+    {
+        self.ellipsis_width = Some(max_width);
+        self
+    }
+
     /// Sets the amount of space between each line of text. The gap between the
     /// baseline of each line of text is multiplied by this value.
     ///
@@ -887,6 +1930,112 @@ impl TextOptions
         self.trim_each_line = trim_each_line;
         self
     }
+
+    /// True if the text should be made bold. This is a synthetic effect, which
+    /// thickens each glyph by drawing it twice with a small horizontal offset.
+    /// The advance width of each glyph is increased to match. Where possible,
+    /// using a bold version of the font will give better results.
+    ///
+    /// The default is `false`.
+    #[inline]
+    #[must_use]
+    pub fn with_bold(mut self, bold: bool) -> Self
+    {
+        self.bold = bold;
+        self
+    }
+
+    /// True if the text should be made italic. This is a synthetic effect,
+    /// which slants each glyph by applying a horizontal shear when it is drawn.
+    /// Where possible, using an italic version of the font will give better
+    /// results.
+    ///
+    /// The default is `false`.
+    #[inline]
+    #[must_use]
+    pub fn with_italic(mut self, italic: bool) -> Self
+    {
+        self.italic = italic;
+        self
+    }
+
+    /// True if a line should be drawn underneath each line of text. The line
+    /// is positioned below the baseline based on the font's descent, and its
+    /// thickness scales with the font size.
+    ///
+    /// The default is `false`.
+    #[inline]
+    #[must_use]
+    pub fn with_underline(mut self, underline: bool) -> Self
+    {
+        self.underline = underline;
+        self
+    }
+
+    /// True if a line should be drawn through the middle of each line of
+    /// text. The line is positioned above the baseline based on the font's
+    /// ascent, and its thickness scales with the font size.
+    ///
+    /// The default is `false`.
+    #[inline]
+    #[must_use]
+    pub fn with_strikethrough(mut self, strikethrough: bool) -> Self
+    {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    /// True if the text should be snapped to whole pixels when drawn. The
+    /// position of the block is rounded to the nearest pixel on the screen,
+    /// so that it's not necessary to round the position passed to
+    /// `Graphics2D::draw_text()`. This avoids the performance cost of
+    /// re-rendering the glyphs when the subpixel position of the text changes
+    /// between frames. The block is moved as a whole, so the spacing between
+    /// each glyph is unaffected.
+    ///
+    /// Snapping has no effect if the current transform includes a rotation
+    /// or scale.
+    ///
+    /// The default is `false`.
+    #[inline]
+    #[must_use]
+    pub fn with_pixel_snapping(mut self, pixel_snapping: bool) -> Self
+    {
+        self.pixel_snapping = pixel_snapping;
+        self
+    }
+
+    /// Sets the base direction of the text. Right-to-left text is supported
+    /// regardless of this setting, however the base direction determines the
+    /// order in which runs of left-to-right and right-to-left text are placed
+    /// on each line. See [TextDirection] for details.
+    ///
+    /// This does not affect the alignment of the text, which may be set using
+    /// `with_wrap_to_width()`.
+    ///
+    /// The default is `TextDirection::LeftToRight`.
+    #[inline]
+    #[must_use]
+    pub fn with_direction(mut self, direction: TextDirection) -> Self
+    {
+        self.direction = direction;
+        self
+    }
+
+    #[inline]
+    fn decoration_thickness(&self, scale: &Scale) -> f32
+    {
+        (scale.y * DECORATION_THICKNESS_RATIO).round().max(1.0)
+    }
+
+    #[inline]
+    fn synthetic_bold_offset(&self, scale: &Scale) -> f32
+    {
+        match self.bold {
+            true => (scale.x * SYNTHETIC_BOLD_RATIO).round().max(1.0),
+            false => 0.0
+        }
+    }
 }
 
 impl Default for TextOptions
@@ -903,7 +2052,13 @@ pub struct FormattedGlyph
 {
     glyph: rusttype::PositionedGlyph<'static>,
     font_id: FontId,
-    user_index: UserGlyphIndex
+    user_index: UserGlyphIndex,
+    codepoint: char,
+    synthetic_bold_offset: f32,
+    synthetic_italic: bool,
+    color: Option<Color>,
+    color_bitmap_font: Option<Arc<ColorBitmapFont>>,
+    is_missing: bool
 }
 
 impl FormattedGlyph
@@ -933,6 +2088,27 @@ impl FormattedGlyph
         self.user_index
     }
 
+    /// The color of this glyph, if it was laid out as part of a colored span
+    /// using [TextLayout::layout_spans]. If `None`, the glyph is drawn using
+    /// the color passed to `Graphics2D::draw_text`.
+    #[inline]
+    #[must_use]
+    pub fn color(&self) -> Option<Color>
+    {
+        self.color
+    }
+
+    /// True if the font didn't contain a glyph for the input codepoint, in
+    /// which case this is a placeholder glyph instead. The placeholder is `□`
+    /// or `?` if available, or otherwise the font's "notdef" glyph (see
+    /// [TextLayout::missing_glyph]).
+    #[inline]
+    #[must_use]
+    pub fn is_missing(&self) -> bool
+    {
+        self.is_missing
+    }
+
     /// The `x` coordinate of this glyph, relative to the start of the line
     #[inline]
     #[must_use]
@@ -945,15 +2121,19 @@ impl FormattedGlyph
     /// information, this would represent the horizontal distance between
     /// the position of this character, and the position of the next
     /// character.
+    ///
+    /// This includes any extra width added by
+    /// [TextOptions::with_bold].
     #[inline]
     #[must_use]
     pub fn advance_width(&self) -> f32
     {
-        self.glyph.unpositioned().h_metrics().advance_width
+        self.glyph.unpositioned().h_metrics().advance_width + self.synthetic_bold_offset
     }
 
     /// The bounding box of this glyph in pixels. This encloses the
-    /// total renderable area of the glyph.
+    /// total renderable area of the glyph, including the effects of
+    /// [TextOptions::with_bold] and [TextOptions::with_italic].
     ///
     /// Some glyphs, such as a space, might not render anything at all -- in
     /// this case, this function will return `None`.
@@ -962,13 +2142,45 @@ impl FormattedGlyph
     pub fn pixel_bounding_box(&self) -> Option<Rect>
     {
         self.glyph.pixel_bounding_box().map(|r| {
+            let (shear_left, shear_right) = match self.synthetic_italic {
+                false => (0.0, 0.0),
+                true => {
+                    let baseline = self.glyph.position().y;
+                    (
+                        ((baseline - r.max.y as f32) * SYNTHETIC_ITALIC_SHEAR).floor(),
+                        ((baseline - r.min.y as f32) * SYNTHETIC_ITALIC_SHEAR).ceil()
+                    )
+                }
+            };
+
             Rect::from_tuples(
-                (r.min.x as f32, r.min.y as f32),
-                (r.max.x as f32, r.max.y as f32)
+                (r.min.x as f32 + shear_left, r.min.y as f32),
+                (
+                    r.max.x as f32 + self.synthetic_bold_offset + shear_right,
+                    r.max.y as f32
+                )
             )
         })
     }
 
+    #[inline]
+    pub(crate) fn synthetic_bold_offset(&self) -> f32
+    {
+        self.synthetic_bold_offset
+    }
+
+    #[inline]
+    pub(crate) fn is_synthetic_italic(&self) -> bool
+    {
+        self.synthetic_italic
+    }
+
+    #[inline]
+    pub(crate) fn color_bitmap_font(&self) -> Option<&ColorBitmapFont>
+    {
+        self.color_bitmap_font.as_deref()
+    }
+
     #[inline]
     fn reposition_y(&mut self, y_pos: f32)
     {
@@ -977,6 +2189,22 @@ impl FormattedGlyph
             .set_position(rusttype::point(existing_pos.x, y_pos));
     }
 
+    #[inline]
+    fn replace_glyph(&mut self, glyph: FontGlyph, scale: &Scale)
+    {
+        self.glyph = glyph.glyph.scaled(*scale).positioned(self.glyph.position());
+        self.font_id = glyph.font.id();
+        self.color_bitmap_font = glyph.font.color_bitmap_font.clone();
+    }
+
+    #[inline]
+    fn set_position_x(&mut self, x_pos: f32)
+    {
+        let existing_pos = self.glyph.position();
+        self.glyph
+            .set_position(rusttype::point(x_pos, existing_pos.y));
+    }
+
     #[inline]
     fn add_offset_x(&mut self, offset_x: f32)
     {
@@ -984,6 +2212,14 @@ impl FormattedGlyph
         self.glyph
             .set_position(rusttype::point(existing_pos.x + offset_x, existing_pos.y));
     }
+
+    #[inline]
+    fn add_offset_y(&mut self, offset_y: f32)
+    {
+        let existing_pos = self.glyph.position();
+        self.glyph
+            .set_position(rusttype::point(existing_pos.x, existing_pos.y + offset_y));
+    }
 }
 
 /// Represents a block of text which has been laid out.
@@ -992,11 +2228,42 @@ pub struct FormattedTextBlock
 {
     lines: Arc<FormattedTextLineVec>,
     width: f32,
-    height: f32
+    height: f32,
+    vertical_offset: f32,
+    pixel_snapping: bool
 }
 
 impl FormattedTextBlock
 {
+    /// Sets the color of each glyph, based on its `user_index`.
+    fn with_glyph_colors<F>(mut self, color_for_index: F) -> Self
+    where
+        F: Fn(UserGlyphIndex) -> Color
+    {
+        for line in Arc::make_mut(&mut self.lines).iter_mut() {
+            for glyph in Arc::make_mut(&mut line.glyphs).iter_mut() {
+                glyph.color = Some(color_for_index(glyph.user_index));
+            }
+        }
+
+        self
+    }
+
+    /// Moves each line of text downwards by the specified amount.
+    fn with_vertical_offset(mut self, offset_y: f32) -> Self
+    {
+        for line in Arc::make_mut(&mut self.lines).iter_mut() {
+            line.baseline_vertical_position += offset_y;
+
+            for glyph in Arc::make_mut(&mut line.glyphs).iter_mut() {
+                glyph.add_offset_y(offset_y);
+            }
+        }
+
+        self.vertical_offset += offset_y;
+        self
+    }
+
     /// Iterate over the lines of text in this block.
     #[inline]
     pub fn iter_lines(&self) -> Iter<'_, FormattedTextLine>
@@ -1004,6 +2271,27 @@ impl FormattedTextBlock
         self.lines.iter()
     }
 
+    /// The number of glyphs in this block which were replaced with a
+    /// placeholder, as the font didn't contain them. If this is greater than
+    /// zero, a different font (or a [FontFamily] with more fallback fonts) is
+    /// needed to display the text. See [FormattedGlyph::is_missing].
+    #[must_use]
+    pub fn missing_glyph_count(&self) -> usize
+    {
+        self.iter_lines()
+            .flat_map(|line| line.iter_glyphs())
+            .filter(|glyph| glyph.is_missing())
+            .count()
+    }
+
+    /// True if this block was laid out using
+    /// [TextOptions::with_pixel_snapping].
+    #[inline]
+    pub(crate) fn is_pixel_snapped(&self) -> bool
+    {
+        self.pixel_snapping
+    }
+
     /// The width (in pixels) of this text block.
     #[inline]
     #[must_use]
@@ -1027,23 +2315,124 @@ impl FormattedTextBlock
     {
         Vec2::new(self.width, self.height)
     }
-}
-
-/// Represents a line of text which has been laid out as part of a block.
-#[derive(Clone)]
-pub struct FormattedTextLine
-{
-    glyphs: Arc<FormattedGlyphVec>,
-    baseline_vertical_position: f32,
-    width: f32,
-    height: f32,
-    ascent: f32,
-    descent: f32,
-    line_gap: f32
-}
 
-impl FormattedTextLine
-{
+    /// The area (in pixels, relative to the top left of the block) occupied
+    /// by the lines of text in this block. Horizontally, each line spans the
+    /// advance width of its glyphs, taking into account any alignment.
+    /// Vertically, each line spans its full height.
+    ///
+    /// This is the layout box of the text, which is suitable for drawing a
+    /// background behind it. Some glyphs may extend slightly outside this
+    /// area, see [FormattedTextBlock::ink_bounds].
+    #[must_use]
+    pub fn bounds(&self) -> Rect
+    {
+        self.iter_lines()
+            .map(|line| {
+                let top = line.baseline_position();
+
+                Rect::from_tuples(
+                    (line.offset_x, top),
+                    (line.offset_x + line.width(), top + line.height())
+                )
+            })
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_else(|| {
+                Rect::from_tuples(
+                    (0.0, self.vertical_offset),
+                    (0.0, self.vertical_offset)
+                )
+            })
+    }
+
+    /// The area (in pixels, relative to the top left of the block) which is
+    /// actually painted when this block is drawn, including any underline or
+    /// strikethrough. This is the tight ink box of the text, which includes
+    /// any overhang of the glyphs outside [FormattedTextBlock::bounds].
+    ///
+    /// If the block doesn't contain anything visible (for example, if it only
+    /// contains whitespace), `None` is returned.
+    #[must_use]
+    pub fn ink_bounds(&self) -> Option<Rect>
+    {
+        self.iter_lines()
+            .flat_map(|line| {
+                line.iter_glyphs()
+                    .filter_map(|glyph| glyph.pixel_bounding_box())
+                    .chain(line.decoration_rects())
+            })
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// The distance (in pixels) from the top of the block to the top of the
+    /// first line of text. This is zero, unless the block was laid out using
+    /// [TextLayout::layout_text_in_box], in which case the text occupies the
+    /// area from `vertical_offset()` to `vertical_offset() + height()`.
+    #[inline]
+    #[must_use]
+    pub fn vertical_offset(&self) -> f32
+    {
+        self.vertical_offset
+    }
+
+    /// Iterate over the glyphs in this block, along with the area (in pixels,
+    /// relative to the top left of the block) which each glyph occupies. This
+    /// area spans the advance width of the glyph horizontally, and the full
+    /// height of its line vertically, making it suitable for hit-testing and
+    /// caret placement.
+    ///
+    /// The `user_index` of each glyph identifies the input codepoint it was
+    /// created from.
+    pub fn iter_glyph_rects(&self) -> impl Iterator<Item = (&FormattedGlyph, Rect)>
+    {
+        self.iter_lines().flat_map(|line| {
+            let top = line.baseline_position();
+            let bottom = top + line.height();
+
+            line.iter_glyphs().map(move |glyph| {
+                let left = glyph.position_x();
+                let right = left + glyph.advance_width();
+                (glyph, Rect::from_tuples((left, top), (right, bottom)))
+            })
+        })
+    }
+
+    /// Returns the `user_index` of the glyph at the specified position (in
+    /// pixels, relative to the top left of the block), or `None` if there is
+    /// no glyph at that position. See [FormattedTextBlock::iter_glyph_rects]
+    /// for details of the area occupied by each glyph.
+    #[must_use]
+    pub fn index_at_position<V: Into<Vec2>>(&self, position: V)
+        -> Option<UserGlyphIndex>
+    {
+        let position = position.into();
+
+        self.iter_glyph_rects()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(glyph, _)| glyph.user_index())
+    }
+}
+
+/// Represents a line of text which has been laid out as part of a block.
+#[derive(Clone)]
+pub struct FormattedTextLine
+{
+    glyphs: Arc<FormattedGlyphVec>,
+    baseline_vertical_position: f32,
+    width: f32,
+    height: f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    offset_x: f32,
+    underline: bool,
+    strikethrough: bool,
+    decoration_thickness: f32,
+    pixel_snapping: bool
+}
+
+impl FormattedTextLine
+{
     /// Iterate over the glyphs in this line.
     #[inline]
     pub fn iter_glyphs(&self) -> Iter<'_, FormattedGlyph>
@@ -1060,7 +2449,9 @@ impl FormattedTextLine
         FormattedTextBlock {
             lines: Arc::new(smallvec![self.clone()]),
             width: self.width,
-            height: self.height
+            height: self.height,
+            vertical_offset: 0.0,
+            pixel_snapping: self.pixel_snapping
         }
     }
 
@@ -1118,6 +2509,42 @@ impl FormattedTextLine
     {
         self.baseline_vertical_position
     }
+
+    /// Returns the areas (relative to the top left of the block) covered by
+    /// the underline and strikethrough of this line, if enabled using
+    /// [TextOptions::with_underline] and [TextOptions::with_strikethrough].
+    pub(crate) fn decoration_rects(&self) -> SmallVec<[Rect; 2]>
+    {
+        let mut result = SmallVec::new();
+
+        if self.width <= 0.0 {
+            return result;
+        }
+
+        let baseline = self.baseline_vertical_position + self.ascent;
+        let thickness = self.decoration_thickness;
+
+        let mut add_line = |top: f32| {
+            result.push(Rect::from_tuples(
+                (self.offset_x, top),
+                (self.offset_x + self.width, top + thickness)
+            ))
+        };
+
+        if self.underline {
+            add_line(baseline + (-self.descent / 2.0 - thickness / 2.0).round());
+        }
+
+        if self.strikethrough {
+            add_line(
+                baseline
+                    - (self.ascent * STRIKETHROUGH_ASCENT_RATIO + thickness / 2.0)
+                        .round()
+            );
+        }
+
+        result
+    }
 }
 
 impl<T: Copy> From<&rusttype::Rect<T>> for Rectangle<T>
@@ -1138,6 +2565,339 @@ mod test
 {
     use super::*;
 
+    fn test_font() -> Font
+    {
+        Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap()
+    }
+
+    #[test]
+    fn test_measure_text_single_line()
+    {
+        let font = test_font();
+
+        let block = font.layout_text("Hello world", 32.0, TextOptions::new());
+        let size = font.measure_text("Hello world", 32.0, TextOptions::new());
+
+        assert_eq!(block.size(), size);
+        assert_eq!(1, block.iter_lines().count());
+
+        let line = block.iter_lines().next().unwrap();
+
+        assert_eq!(line.width(), size.x);
+        assert_eq!(line.height(), size.y);
+        assert_eq!(line.ascent() - line.descent(), size.y);
+
+        assert!(size.x > 0.0);
+        assert!(
+            font.measure_text("Hello", 32.0, TextOptions::new()).x < size.x,
+            "Shorter text should be narrower"
+        );
+    }
+
+    #[test]
+    fn test_glyph_rects()
+    {
+        let font = test_font();
+
+        let block = font.layout_text("ab\ncd", 32.0, TextOptions::new());
+
+        let rects: Vec<(UserGlyphIndex, Rect)> = block
+            .iter_glyph_rects()
+            .map(|(glyph, rect)| (glyph.user_index(), rect))
+            .collect();
+
+        assert_eq!(
+            vec![0, 1, 3, 4],
+            rects.iter().map(|(index, _)| *index).collect::<Vec<_>>()
+        );
+
+        // Glyphs on the same line are next to each other, and the second line
+        // is below the first
+        assert!(rects[1].1.left() > rects[0].1.left());
+        assert_eq!(rects[0].1.top(), rects[1].1.top());
+        assert_eq!(rects[0].1.bottom(), rects[1].1.bottom());
+        assert!(rects[2].1.top() >= rects[0].1.bottom());
+        assert_eq!(0.0, rects[2].1.left());
+
+        for (index, rect) in &rects {
+            let center = (*rect.top_left() + *rect.bottom_right()) / 2.0;
+            assert_eq!(Some(*index), block.index_at_position(center));
+        }
+
+        assert_eq!(None, block.index_at_position((-1.0, 1.0)));
+        assert_eq!(None, block.index_at_position((block.width() + 1.0, 1.0)));
+        assert_eq!(None, block.index_at_position((1.0, block.height() + 1.0)));
+    }
+
+    #[test]
+    fn test_font_family()
+    {
+        let primary = test_font();
+        let secondary = test_font();
+
+        let family = FontFamily::new(vec![primary.clone(), secondary]);
+
+        assert_eq!(
+            primary.id(),
+            family.lookup_glyph_for_codepoint('a').unwrap().font.id()
+        );
+
+        assert!(family.lookup_glyph_for_codepoint('\u{E000}').is_none());
+
+        // Missing codepoints are replaced, rather than being skipped
+        let block = family.layout_text("a\u{E000}b", 32.0, TextOptions::new());
+
+        let glyphs: Vec<&FormattedGlyph> = block
+            .iter_lines()
+            .flat_map(|line| line.iter_glyphs())
+            .collect();
+
+        assert_eq!(3, glyphs.len());
+        assert!(glyphs.iter().all(|glyph| glyph.font_id() == primary.id()));
+    }
+
+    #[test]
+    fn test_font_family_empty()
+    {
+        let family = FontFamily::new(vec![]);
+
+        assert!(family.lookup_glyph_for_codepoint('a').is_none());
+        assert!(family.missing_glyph().is_none());
+
+        let block = family.layout_text("abc", 32.0, TextOptions::new());
+
+        assert_eq!(
+            0,
+            block
+                .iter_lines()
+                .flat_map(|line| line.iter_glyphs())
+                .count()
+        );
+    }
+
+    fn visual_order(block: &FormattedTextBlock) -> Vec<UserGlyphIndex>
+    {
+        let mut glyphs: Vec<&FormattedGlyph> = block
+            .iter_lines()
+            .flat_map(|line| line.iter_glyphs())
+            .collect();
+
+        glyphs.sort_by(|a, b| a.position_x().partial_cmp(&b.position_x()).unwrap());
+
+        glyphs.iter().map(|glyph| glyph.user_index()).collect()
+    }
+
+    #[test]
+    fn test_bidi_left_to_right()
+    {
+        let font = test_font();
+
+        let block = font.layout_text("ab \u{05D0}\u{05D1}", 32.0, TextOptions::new());
+        assert_eq!(vec![0, 1, 2, 4, 3], visual_order(&block));
+
+        // Numbers following right-to-left text stay in left-to-right order
+        let block = font.layout_text("\u{05D0}\u{05D1} 12", 32.0, TextOptions::new());
+        assert_eq!(vec![3, 4, 2, 1, 0], visual_order(&block));
+
+        // Text without any right-to-left characters is unaffected
+        let text = "Hello world";
+        let block = font.layout_text(text, 32.0, TextOptions::new());
+        assert_eq!((0..11).collect::<Vec<_>>(), visual_order(&block));
+    }
+
+    #[test]
+    fn test_bidi_right_to_left()
+    {
+        let font = test_font();
+        let text = "ab \u{05D0}\u{05D1}";
+
+        let options = || TextOptions::new().with_direction(TextDirection::RightToLeft);
+
+        let block = font.layout_text(text, 32.0, options());
+        assert_eq!(vec![4, 3, 2, 0, 1], visual_order(&block));
+
+        // The base direction changes the order, but not the width
+        let left_to_right = font.layout_text(text, 32.0, TextOptions::new());
+        assert!((left_to_right.width() - block.width()).abs() < 0.001);
+
+        let auto = font.layout_text(
+            "\u{05D0}\u{05D1} ab",
+            32.0,
+            TextOptions::new().with_direction(TextDirection::Auto)
+        );
+        assert_eq!(vec![3, 4, 2, 1, 0], visual_order(&auto));
+    }
+
+    #[test]
+    fn test_bidi_mirroring()
+    {
+        let font = test_font();
+
+        let block = font.layout_text(
+            "(\u{05D0})",
+            32.0,
+            TextOptions::new().with_direction(TextDirection::RightToLeft)
+        );
+
+        assert_eq!(vec![2, 1, 0], visual_order(&block));
+
+        let glyph_id = |user_index: UserGlyphIndex| {
+            block
+                .iter_lines()
+                .flat_map(|line| line.iter_glyphs())
+                .find(|glyph| glyph.user_index() == user_index)
+                .unwrap()
+                .glyph()
+                .id()
+        };
+
+        let open = font.lookup_glyph_for_codepoint('(').unwrap().glyph.id();
+        let close = font.lookup_glyph_for_codepoint(')').unwrap().glyph.id();
+
+        assert_eq!(close, glyph_id(0));
+        assert_eq!(open, glyph_id(2));
+    }
+
+    #[test]
+    fn test_tracking()
+    {
+        let font = test_font();
+        let text = "Hello";
+
+        let width = |tracking: f32| {
+            font.measure_text(text, 32.0, TextOptions::new().with_tracking(tracking))
+                .x
+        };
+
+        let normal = width(0.0);
+
+        assert!((normal + 5.0 * 4.0 - width(5.0)).abs() < 0.001);
+        assert!((normal - 2.0 * 4.0 - width(-2.0)).abs() < 0.001);
+
+        let squashed =
+            font.layout_text(text, 32.0, TextOptions::new().with_tracking(-1000.0));
+
+        let positions: Vec<f32> = squashed
+            .iter_lines()
+            .flat_map(|line| line.iter_glyphs().map(|glyph| glyph.position_x()))
+            .collect();
+
+        assert_eq!(5, positions.len());
+        assert!(positions.windows(2).all(|pair| pair[1] >= pair[0]));
+    }
+
+    #[test]
+    fn test_synthetic_bold_and_italic()
+    {
+        let font = test_font();
+        let text = "Hello";
+
+        let layout = |options: TextOptions| font.layout_text(text, 32.0, options);
+
+        let normal = layout(TextOptions::new());
+        let bold = layout(TextOptions::new().with_bold(true));
+        let italic = layout(TextOptions::new().with_italic(true));
+
+        let glyphs = |block: &FormattedTextBlock| -> Vec<FormattedGlyph> {
+            block
+                .iter_lines()
+                .flat_map(|line| line.iter_glyphs().cloned())
+                .collect()
+        };
+
+        // At this scale, bold glyphs are thickened by one pixel
+        assert!((normal.width() + 5.0 - bold.width()).abs() < 0.001);
+
+        for (normal_glyph, bold_glyph) in glyphs(&normal).iter().zip(glyphs(&bold).iter())
+        {
+            assert_eq!(
+                normal_glyph.advance_width() + 1.0,
+                bold_glyph.advance_width()
+            );
+
+            let normal_box = normal_glyph.pixel_bounding_box().unwrap();
+            let bold_box = bold_glyph.pixel_bounding_box().unwrap();
+
+            assert_eq!(normal_box.width() + 1.0, bold_box.width());
+            assert_eq!(normal_box.height(), bold_box.height());
+        }
+
+        // Italic text is slanted, without affecting the layout
+        assert_eq!(normal.size(), italic.size());
+
+        let normal_box = glyphs(&normal)[0].pixel_bounding_box().unwrap();
+        let italic_box = glyphs(&italic)[0].pixel_bounding_box().unwrap();
+
+        assert!(italic_box.right() > normal_box.right());
+        assert_eq!(normal_box.top(), italic_box.top());
+        assert_eq!(normal_box.bottom(), italic_box.bottom());
+    }
+
+    #[test]
+    fn test_line_spacing_multiplier()
+    {
+        let font = test_font();
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        let options = |multiplier: f32| {
+            TextOptions::new()
+                .with_wrap_to_width(200.0, TextAlignment::Left)
+                .with_line_spacing_multiplier(multiplier)
+        };
+
+        let normal = font.layout_text(text, 32.0, options(1.0));
+        let spaced = font.layout_text(text, 32.0, options(1.5));
+
+        assert!(normal.iter_lines().count() > 1);
+        assert_eq!(normal.iter_lines().count(), spaced.iter_lines().count());
+        assert_eq!(normal.width(), spaced.width());
+        assert!((normal.height() * 1.5 - spaced.height()).abs() < 0.001);
+
+        // Every wrapped line is moved down by the extra spacing
+        for (normal_line, spaced_line) in normal.iter_lines().zip(spaced.iter_lines()) {
+            assert!(
+                (normal_line.baseline_position() * 1.5 - spaced_line.baseline_position())
+                    .abs()
+                    < 0.001
+            );
+        }
+    }
+
+    #[test]
+    fn test_measure_text_wrapped()
+    {
+        let font = test_font();
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        let unwrapped = font.measure_text(text, 32.0, TextOptions::new());
+
+        let options =
+            || TextOptions::new().with_wrap_to_width(200.0, TextAlignment::Left);
+
+        let block = font.layout_text(text, 32.0, options());
+        let size = font.measure_text(text, 32.0, options());
+
+        assert_eq!(block.size(), size);
+        assert!(block.iter_lines().count() > 1);
+
+        let widest_line = block
+            .iter_lines()
+            .map(|line| line.width())
+            .fold(0.0, f32::max);
+
+        let total_height: f32 = block
+            .iter_lines()
+            .map(|line| line.height() + line.line_gap())
+            .sum::<f32>()
+            - block.iter_lines().last().unwrap().line_gap();
+
+        assert_eq!(widest_line, size.x);
+        assert!((total_height - size.y).abs() < 0.001);
+        assert!(size.x <= 200.0);
+        assert!(size.x < unwrapped.x);
+        assert!(size.y > unwrapped.y);
+    }
+
     #[test]
     fn test_word_split_1()
     {
@@ -1202,4 +2962,478 @@ mod test
             words
         )
     }
+
+    #[test]
+    fn test_underline_and_strikethrough()
+    {
+        let font = test_font();
+
+        let layout = |size: f32, options: TextOptions| {
+            font.layout_text("Hello", size, options)
+                .iter_lines()
+                .next()
+                .unwrap()
+                .clone()
+        };
+
+        assert!(layout(32.0, TextOptions::new())
+            .decoration_rects()
+            .is_empty());
+
+        let line = layout(32.0, TextOptions::new().with_underline(true));
+        let baseline = line.baseline_position() + line.ascent();
+        let rects = line.decoration_rects();
+
+        assert_eq!(1, rects.len());
+        assert_eq!(0.0, rects[0].left());
+        assert_eq!(line.width(), rects[0].width());
+        assert_eq!(2.0, rects[0].height());
+        assert!(rects[0].top() > baseline);
+        assert!(rects[0].bottom() < baseline - line.descent());
+
+        let line = layout(32.0, TextOptions::new().with_strikethrough(true));
+        let rects = line.decoration_rects();
+
+        assert_eq!(1, rects.len());
+        assert!(rects[0].bottom() < baseline);
+        assert!(rects[0].top() > line.baseline_position());
+
+        let line = layout(
+            64.0,
+            TextOptions::new()
+                .with_underline(true)
+                .with_strikethrough(true)
+        );
+        let rects = line.decoration_rects();
+
+        assert_eq!(2, rects.len());
+        assert_eq!(4.0, rects[0].height());
+        assert_eq!(4.0, rects[1].height());
+        assert!(rects[1].bottom() < rects[0].top());
+
+        let line = layout(
+            32.0,
+            TextOptions::new()
+                .with_underline(true)
+                .with_wrap_to_width(200.0, TextAlignment::Right)
+        );
+        let rects = line.decoration_rects();
+
+        assert!((rects[0].right() - 200.0).abs() < 0.001);
+        assert!((rects[0].width() - line.width()).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_layout_spans()
+    {
+        let font = test_font();
+
+        let plain = font.layout_text("Hello world", 32.0, TextOptions::new());
+        let spans = font.layout_spans(
+            &[
+                ("Hello ", Color::RED),
+                ("wor", Color::GREEN),
+                ("ld", Color::BLUE)
+            ],
+            32.0,
+            TextOptions::new()
+        );
+
+        assert_eq!(plain.size(), spans.size());
+
+        let colors = |block: &FormattedTextBlock| {
+            block
+                .iter_lines()
+                .flat_map(|line| line.iter_glyphs())
+                .map(|glyph| glyph.color())
+                .collect::<Vec<Option<Color>>>()
+        };
+
+        assert!(colors(&plain).iter().all(|color| color.is_none()));
+
+        let mut expected = vec![Some(Color::RED); 6];
+        expected.extend(vec![Some(Color::GREEN); 3]);
+        expected.extend(vec![Some(Color::BLUE); 2]);
+
+        assert_eq!(expected, colors(&spans));
+    }
+
+    #[test]
+    fn test_color_bitmap_tables()
+    {
+        assert!(!has_color_bitmap_tables(include_bytes!(
+            "../assets/fonts/NotoSans-Regular.ttf"
+        )));
+
+        let directory = |tags: &[&[u8; 4]]| {
+            let mut bytes = vec![0, 1, 0, 0];
+            bytes.extend_from_slice(&(tags.len() as u16).to_be_bytes());
+            bytes.extend_from_slice(&[0; 6]);
+
+            for tag in tags {
+                bytes.extend_from_slice(*tag);
+                bytes.extend_from_slice(&[0; 12]);
+            }
+
+            bytes
+        };
+
+        assert!(!has_color_bitmap_tables(&directory(&[
+            b"cmap", b"glyf", b"head"
+        ])));
+        assert!(has_color_bitmap_tables(&directory(&[
+            b"CBDT", b"CBLC", b"cmap"
+        ])));
+        assert!(has_color_bitmap_tables(&directory(&[b"cmap", b"sbix"])));
+
+        let mut collection = b"ttcf".to_vec();
+        collection.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 16]);
+        collection.extend(directory(&[b"head", b"sbix"]));
+
+        assert!(has_color_bitmap_tables(&collection));
+
+        // Truncated data
+        assert!(!has_color_bitmap_tables(&directory(&[b"sbix"])[..14]));
+        assert!(!has_color_bitmap_tables(&[]));
+    }
+
+    #[test]
+    fn test_missing_glyphs()
+    {
+        let font = test_font();
+
+        let block = font.layout_text("Hello", 32.0, TextOptions::new());
+        assert_eq!(0, block.missing_glyph_count());
+
+        let block = font.layout_text("日本語", 32.0, TextOptions::new());
+        let glyphs: Vec<&FormattedGlyph> = block
+            .iter_lines()
+            .flat_map(|line| line.iter_glyphs())
+            .collect();
+
+        // Each missing glyph is replaced by a visible placeholder
+        assert_eq!(3, block.missing_glyph_count());
+        assert_eq!(3, glyphs.len());
+        assert!(glyphs.iter().all(|glyph| glyph.is_missing()));
+        assert!(glyphs
+            .iter()
+            .all(|glyph| glyph.pixel_bounding_box().is_some()));
+        assert!(block.width() > 0.0);
+
+        let block = font.layout_text("Hello 世界", 32.0, TextOptions::new());
+        assert_eq!(2, block.missing_glyph_count());
+    }
+
+    #[test]
+    fn test_overflow_wrap()
+    {
+        let font = test_font();
+        let text = "a".repeat(50);
+
+        let block = font.layout_text(
+            &text,
+            32.0,
+            TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Left)
+        );
+
+        assert!(block.iter_lines().len() > 1);
+        assert!(block.iter_lines().all(|line| line.width() <= 100.0));
+        assert_eq!(
+            50,
+            block
+                .iter_lines()
+                .map(|line| line.iter_glyphs().len())
+                .sum::<usize>()
+        );
+
+        let block = font.layout_text(
+            &text,
+            32.0,
+            TextOptions::new()
+                .with_wrap_to_width(100.0, TextAlignment::Left)
+                .with_overflow_wrap(OverflowWrap::Normal)
+        );
+
+        assert_eq!(1, block.iter_lines().len());
+        assert!(block.width() > 100.0);
+    }
+
+    #[test]
+    fn test_overflow_wrap_keeps_grapheme_clusters()
+    {
+        let font = test_font();
+        let codepoints: Vec<char> = "a\u{20DD}".repeat(25).chars().collect();
+
+        let block = font.layout_text_from_unindexed_codepoints(
+            &codepoints,
+            32.0,
+            TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Left)
+        );
+
+        assert!(block.iter_lines().len() > 1);
+
+        for line in block.iter_lines() {
+            assert_eq!('a', line.iter_glyphs().next().unwrap().codepoint);
+        }
+    }
+
+    #[test]
+    fn test_ellipsis()
+    {
+        let font = test_font();
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        let block = font.layout_text(text, 32.0, TextOptions::new().with_ellipsis(150.0));
+
+        assert_eq!(1, block.iter_lines().len());
+        assert!(block.width() <= 150.0);
+
+        let line = block.iter_lines().next().unwrap();
+        let last_glyph = line.iter_glyphs().last().unwrap();
+
+        assert!(last_glyph.codepoint == '…' || last_glyph.codepoint == '.');
+        assert!(last_glyph.position_x() + last_glyph.advance_width() <= 150.0);
+
+        // Text which already fits is unchanged
+        let block =
+            font.layout_text("Fox", 32.0, TextOptions::new().with_ellipsis(150.0));
+
+        assert_eq!(
+            vec!['F', 'o', 'x'],
+            block
+                .iter_lines()
+                .flat_map(|line| line.iter_glyphs())
+                .map(|glyph| glyph.codepoint)
+                .collect::<Vec<char>>()
+        );
+    }
+
+    fn load_error_kind(err: &BacktraceError<ErrorMessage>) -> FontLoadErrorKind
+    {
+        err.cause()
+            .as_ref()
+            .and_then(|cause| cause.downcast_ref::<FontLoadError>())
+            .expect("Expected the cause to be a FontLoadError")
+            .kind()
+    }
+
+    #[test]
+    fn test_font_load_errors()
+    {
+        let error = |bytes: &[u8]| match Font::new(bytes) {
+            Ok(_) => panic!("Expected font loading to fail"),
+            Err(err) => (load_error_kind(&err), err.to_string())
+        };
+
+        assert_eq!(
+            (
+                FontLoadErrorKind::NotAFont,
+                "Failed to load font: unrecognized file format, expected TrueType or \
+                 OpenType font data"
+                    .to_string()
+            ),
+            error(b"this is not a font file")
+        );
+
+        assert_eq!(
+            (
+                FontLoadErrorKind::NotAFont,
+                "Failed to load font: the data is too short to be a font file"
+                    .to_string()
+            ),
+            error(&[0, 1])
+        );
+
+        assert_eq!(
+            FontLoadErrorKind::UnsupportedFormat,
+            error(b"wOFF\x00\x01\x00\x00").0
+        );
+
+        assert_eq!(
+            (
+                FontLoadErrorKind::Malformed,
+                "Failed to load font: the font data is truncated or malformed"
+                    .to_string()
+            ),
+            error(&[0, 1, 0, 0, 0xFF, 0xFF, 0xFF])
+        );
+
+        let truncated = &include_bytes!("../assets/fonts/NotoSans-Regular.ttf")[..1000];
+        assert_eq!(FontLoadErrorKind::Malformed, error(truncated).0);
+    }
+
+    #[test]
+    fn test_font_table_validation()
+    {
+        // A table directory containing the specified tables, all empty
+        let font_data = |magic: &[u8], tags: &[&str]| -> Vec<u8> {
+            let mut data = magic.to_vec();
+            data.extend_from_slice(&(tags.len() as u16).to_be_bytes());
+            data.extend_from_slice(&[0; 6]);
+
+            for tag in tags {
+                data.extend_from_slice(tag.as_bytes());
+                data.extend_from_slice(&[0; 12]);
+            }
+
+            data
+        };
+
+        let kind = |data: Vec<u8>| {
+            validate_font_data(&data).map_err(|err| load_error_kind(&err))
+        };
+
+        // CFF-based OpenType fonts are supported
+        assert_eq!(
+            Ok(()),
+            kind(font_data(
+                b"OTTO",
+                &["CFF ", "cmap", "head", "hhea", "hmtx", "maxp"]
+            ))
+        );
+
+        assert_eq!(
+            Ok(()),
+            kind(font_data(
+                b"\x00\x01\x00\x00",
+                &["cmap", "glyf", "head", "hhea", "hmtx", "loca", "maxp"]
+            ))
+        );
+
+        assert_eq!(
+            Err(FontLoadErrorKind::MissingRequiredTables),
+            kind(font_data(
+                b"OTTO",
+                &["CFF ", "cmap", "head", "hhea", "maxp"]
+            ))
+        );
+
+        assert_eq!(
+            Err(FontLoadErrorKind::MissingRequiredTables),
+            kind(font_data(
+                b"\x00\x01\x00\x00",
+                &["cmap", "glyf", "head", "hhea", "hmtx", "maxp"]
+            ))
+        );
+
+        assert_eq!(
+            Err(FontLoadErrorKind::UnsupportedOutlines),
+            kind(font_data(
+                b"OTTO",
+                &["SVG ", "cmap", "head", "hhea", "hmtx", "maxp"]
+            ))
+        );
+
+        assert_eq!(
+            Ok(()),
+            validate_font_data(include_bytes!("../assets/fonts/NotoSans-Regular.ttf"))
+                .map_err(|err| load_error_kind(&err))
+        );
+    }
+
+    #[test]
+    fn test_font_coverage()
+    {
+        let font = test_font();
+
+        assert!(font.contains_char('A'));
+        assert!(font.contains_char('é'));
+        assert!(!font.contains_char('\u{2000B}'));
+
+        let coverage: Vec<char> = font.coverage().collect();
+
+        assert!(coverage.contains(&'A'));
+        assert!(!coverage.contains(&'\u{2000B}'));
+        assert!(coverage.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(coverage.iter().all(|c| font.contains_char(*c)));
+    }
+
+    #[test]
+    fn test_layout_text_in_box()
+    {
+        let font = test_font();
+        let box_size = Vec2::new(300.0, 200.0);
+
+        let layout = |vertical_alignment| {
+            font.layout_text_in_box(
+                "Hello",
+                32.0,
+                box_size,
+                TextAlignment::Center,
+                vertical_alignment,
+                TextOptions::new()
+            )
+        };
+
+        let top = layout(VerticalAlignment::Top);
+        let middle = layout(VerticalAlignment::Middle);
+        let bottom = layout(VerticalAlignment::Bottom);
+
+        assert_eq!(0.0, top.vertical_offset());
+        assert_eq!((200.0 - middle.height()) / 2.0, middle.vertical_offset());
+        assert_eq!(200.0 - bottom.height(), bottom.vertical_offset());
+
+        for block in [&top, &middle, &bottom].iter() {
+            let line = block.iter_lines().next().unwrap();
+
+            assert_eq!(block.vertical_offset(), line.baseline_position());
+            assert!((line.offset_x - (300.0 - line.width()) / 2.0).abs() < 0.01);
+
+            let (_, rect) = block.iter_glyph_rects().next().unwrap();
+            assert_eq!(block.vertical_offset(), rect.top());
+        }
+
+        let top_glyph = top
+            .iter_lines()
+            .next()
+            .unwrap()
+            .iter_glyphs()
+            .next()
+            .unwrap();
+        let bottom_glyph = bottom
+            .iter_lines()
+            .next()
+            .unwrap()
+            .iter_glyphs()
+            .next()
+            .unwrap();
+
+        let glyph_offset_y =
+            bottom_glyph.glyph().position().y - top_glyph.glyph().position().y;
+
+        assert!((bottom.vertical_offset() - glyph_offset_y).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_text_bounds()
+    {
+        let font = test_font();
+
+        let block = font.layout_text("Hello", 32.0, TextOptions::new());
+
+        assert_eq!(
+            Rect::from_tuples((0.0, 0.0), (block.width(), block.height())),
+            block.bounds()
+        );
+
+        let ink_bounds = block.ink_bounds().unwrap();
+        let bounds = block.bounds();
+
+        assert!(ink_bounds.width() > 0.0 && ink_bounds.height() > 0.0);
+        assert!(ink_bounds.width() <= bounds.width() + 2.0);
+        assert!(ink_bounds.height() < bounds.height());
+
+        let centered = font.layout_text(
+            "Hello",
+            32.0,
+            TextOptions::new().with_wrap_to_width(200.0, TextAlignment::Center)
+        );
+
+        let offset_x = (200.0 - centered.width()) / 2.0;
+        assert!((centered.bounds().left() - offset_x).abs() < 0.01);
+        assert!(centered.ink_bounds().unwrap().left() >= offset_x - 2.0);
+
+        let whitespace = font.layout_text("   ", 32.0, TextOptions::new());
+        assert!(whitespace.ink_bounds().is_none());
+    }
 }
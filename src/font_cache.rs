@@ -109,7 +109,10 @@ pub(crate) struct GlyphCache
     this_frame: HashSet<GlyphCacheKey>,
 
     cache_entries: HashMap<GlyphCacheKey, GlyphCacheEntry>,
-    textures: Vec<GlyphCacheTexture>
+    textures: Vec<GlyphCacheTexture>,
+
+    frame_number: u64,
+    max_entries: usize
 }
 
 impl GlyphCache
@@ -139,7 +142,7 @@ impl GlyphCache
 
         let texture_size = GlyphCacheTexture::SIZE as f32;
 
-        let mut texture_region = Rectangle::new(
+        let texture_region = Rectangle::new(
             texture_entry
                 .texture_area
                 .top_left()
@@ -157,100 +160,156 @@ impl GlyphCache
         // We round the position here as the offset is between -0.5 and 0.5
         let screen_region_start = position.round().into_i32() + entry.bounding_box_offset;
 
-        let mut screen_region = Rectangle::new(
+        // Color glyphs are drawn as they are, keeping only the text's alpha
+        let color = match entry.is_color {
+            true => Color::from_rgba(1.0, 1.0, 1.0, color.a()),
+            false => color
+        };
+
+        let screen_region = Rectangle::new(
             screen_region_start,
             screen_region_start + texture_entry.texture_area.size().into_i32()
         )
         .into_f32();
 
-        if let Some(crop_window) = crop_window {
-            if let Some(screen_intersection) = screen_region.intersect(crop_window) {
-                let left_diff = (screen_intersection.top_left().x
-                    - screen_region.top_left().x)
-                    / screen_region.width();
-                let right_diff = (screen_region.bottom_right().x
-                    - screen_intersection.bottom_right().x)
-                    / screen_region.width();
-
-                let top_diff = (screen_intersection.top_left().y
-                    - screen_region.top_left().y)
-                    / screen_region.height();
-                let bottom_diff = (screen_region.bottom_right().y
-                    - screen_intersection.bottom_right().y)
-                    / screen_region.height();
-
-                texture_region = Rectangle::new(
-                    texture_region.top_left()
-                        + Vec2::new(
-                            texture_region.width() * left_diff,
-                            texture_region.height() * top_diff
-                        ),
-                    texture_region.bottom_right()
-                        - Vec2::new(
-                            texture_region.width() * right_diff,
-                            texture_region.height() * bottom_diff
-                        )
-                );
+        let baseline = position.y;
 
-                screen_region = screen_intersection;
-            } else {
-                return;
-            }
-        }
+        let shear = |point: Vec2| match glyph.is_synthetic_italic() {
+            false => point,
+            true => Vec2::new(
+                point.x + (baseline - point.y) * font::SYNTHETIC_ITALIC_SHEAR,
+                point.y
+            )
+        };
 
-        runner(Renderer2DAction {
-            texture: Some(texture_cache.texture.clone()),
-            vertices_clockwise: [
-                Renderer2DVertex {
-                    position: *screen_region.top_left(),
-                    texture_coord: *texture_region.top_left(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
-                },
-                Renderer2DVertex {
-                    position: screen_region.top_right(),
-                    texture_coord: texture_region.top_right(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
-                },
-                Renderer2DVertex {
-                    position: *screen_region.bottom_right(),
-                    texture_coord: *texture_region.bottom_right(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
-                }
-            ]
-        });
+        // Synthetic bold glyphs are drawn a second time, slightly to the right
+        let bold_offset = glyph.synthetic_bold_offset();
+
+        let offsets = [0.0, bold_offset];
+
+        let offsets = match bold_offset > 0.0 {
+            true => &offsets[..],
+            false => &offsets[..1]
+        };
 
-        runner(Renderer2DAction {
-            texture: Some(texture_cache.texture.clone()),
-            vertices_clockwise: [
-                Renderer2DVertex {
-                    position: *screen_region.bottom_right(),
-                    texture_coord: *texture_region.bottom_right(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
-                },
-                Renderer2DVertex {
-                    position: screen_region.bottom_left(),
-                    texture_coord: texture_region.bottom_left(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
-                },
-                Renderer2DVertex {
-                    position: *screen_region.top_left(),
-                    texture_coord: *texture_region.top_left(),
-                    color,
-                    texture_mix: 1.0,
-                    circle_mix: 0.0
+        for offset in offsets {
+            let screen_region = screen_region.with_offset((*offset, 0.0));
+
+            let (screen_region, texture_region) = match crop_window {
+                None => (screen_region, texture_region.clone()),
+                Some(crop_window) => {
+                    match GlyphCache::crop_regions(
+                        &screen_region,
+                        &texture_region,
+                        crop_window
+                    ) {
+                        None => continue,
+                        Some(regions) => regions
+                    }
                 }
-            ]
-        });
+            };
+
+            let top_left = shear(*screen_region.top_left());
+            let top_right = shear(screen_region.top_right());
+            let bottom_right = shear(*screen_region.bottom_right());
+            let bottom_left = shear(screen_region.bottom_left());
+
+            runner(Renderer2DAction {
+                texture: Some(texture_cache.texture.clone()),
+                texture_premultiplied_alpha: false,
+                vertices_clockwise: [
+                    Renderer2DVertex {
+                        position: top_left,
+                        texture_coord: *texture_region.top_left(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    },
+                    Renderer2DVertex {
+                        position: top_right,
+                        texture_coord: texture_region.top_right(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    },
+                    Renderer2DVertex {
+                        position: bottom_right,
+                        texture_coord: *texture_region.bottom_right(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    }
+                ]
+            });
+
+            runner(Renderer2DAction {
+                texture: Some(texture_cache.texture.clone()),
+                texture_premultiplied_alpha: false,
+                vertices_clockwise: [
+                    Renderer2DVertex {
+                        position: bottom_right,
+                        texture_coord: *texture_region.bottom_right(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    },
+                    Renderer2DVertex {
+                        position: bottom_left,
+                        texture_coord: texture_region.bottom_left(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    },
+                    Renderer2DVertex {
+                        position: top_left,
+                        texture_coord: *texture_region.top_left(),
+                        color,
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    }
+                ]
+            });
+        }
+    }
+
+    /// Crops the screen region of a glyph to the specified window, adjusting
+    /// the texture region to match. Returns `None` if the glyph is entirely
+    /// outside the window.
+    #[inline]
+    fn crop_regions(
+        screen_region: &Rect,
+        texture_region: &Rect,
+        crop_window: &Rect
+    ) -> Option<(Rect, Rect)>
+    {
+        let screen_intersection = screen_region.intersect(crop_window)?;
+
+        let left_diff = (screen_intersection.top_left().x - screen_region.top_left().x)
+            / screen_region.width();
+        let right_diff = (screen_region.bottom_right().x
+            - screen_intersection.bottom_right().x)
+            / screen_region.width();
+
+        let top_diff = (screen_intersection.top_left().y - screen_region.top_left().y)
+            / screen_region.height();
+        let bottom_diff = (screen_region.bottom_right().y
+            - screen_intersection.bottom_right().y)
+            / screen_region.height();
+
+        let texture_region = Rectangle::new(
+            texture_region.top_left()
+                + Vec2::new(
+                    texture_region.width() * left_diff,
+                    texture_region.height() * top_diff
+                ),
+            texture_region.bottom_right()
+                - Vec2::new(
+                    texture_region.width() * right_diff,
+                    texture_region.height() * bottom_diff
+                )
+        );
+
+        Some((screen_intersection, texture_region))
     }
 
     pub(crate) fn add_to_cache(
@@ -271,11 +330,35 @@ impl GlyphCache
         let cache_entries = &mut self.cache_entries;
 
         match cache_entries.entry(key.clone()) {
-            Entry::Occupied(_) => {
-                // Already in the cache, nothing to do
+            Entry::Occupied(mut entry) => {
+                // Already in the cache, just mark it as recently used
+                entry.get_mut().last_used_frame = self.frame_number;
             }
 
             Entry::Vacant(entry) => {
+                let color_bitmap = formatted_glyph
+                    .color_bitmap_font()
+                    .and_then(|font| font.rasterize(key.glyph_id, key.scale.to_pixels()));
+
+                if let Some(color_bitmap) = color_bitmap {
+                    if GlyphCache::is_too_big(&color_bitmap.size) {
+                        return;
+                    }
+
+                    entry.insert(GlyphCacheEntry {
+                        glyph_bitmap: Rc::new(BitmapRGBA {
+                            data: color_bitmap.data,
+                            size: color_bitmap.size
+                        }),
+                        bounding_box_offset: color_bitmap.offset,
+                        texture_id: None,
+                        last_used_frame: self.frame_number,
+                        is_color: true
+                    });
+
+                    return;
+                }
+
                 let glyph = formatted_glyph
                     .glyph()
                     .unpositioned()
@@ -295,16 +378,7 @@ impl GlyphCache
                 let bounding_box_size =
                     UVec2::new(bounding_box.width() as u32, bounding_box.height() as u32);
 
-                if bounding_box_size.x > GlyphCacheTexture::SIZE
-                    || bounding_box_size.y > GlyphCacheTexture::SIZE
-                {
-                    log::error!(
-                        "Glyph too big to render ({}x{}). Limit is {} px.",
-                        bounding_box_size.x,
-                        bounding_box_size.y,
-                        GlyphCacheTexture::SIZE
-                    );
-
+                if GlyphCache::is_too_big(&bounding_box_size) {
                     return;
                 }
 
@@ -318,16 +392,87 @@ impl GlyphCache
                         bounding_box.min.x,
                         bounding_box.min.y
                     ),
-                    texture_id: None
+                    texture_id: None,
+                    last_used_frame: self.frame_number,
+                    is_color: false
                 });
             }
         }
     }
 
+    fn is_too_big(bitmap_size: &UVec2) -> bool
+    {
+        if bitmap_size.x > GlyphCacheTexture::SIZE
+            || bitmap_size.y > GlyphCacheTexture::SIZE
+        {
+            log::error!(
+                "Glyph too big to render ({}x{}). Limit is {} px.",
+                bitmap_size.x,
+                bitmap_size.y,
+                GlyphCacheTexture::SIZE
+            );
+
+            return true;
+        }
+
+        false
+    }
+
     pub(crate) fn on_new_frame_start(&mut self)
     {
         self.last_frame.clear();
         std::mem::swap(&mut self.last_frame, &mut self.this_frame);
+        self.frame_number += 1;
+    }
+
+    /// Removes all glyphs from the cache, and deletes the textures containing
+    /// them. Glyphs will be rendered and uploaded again when next drawn.
+    pub(crate) fn clear(&mut self)
+    {
+        self.last_frame.clear();
+        self.this_frame.clear();
+        self.cache_entries.clear();
+        self.textures.clear();
+    }
+
+    /// Sets the maximum number of glyphs to keep in the cache. Glyphs used in
+    /// the current frame are never evicted, so the cache may temporarily
+    /// exceed this size.
+    pub(crate) fn set_max_entries(&mut self, max_entries: usize)
+    {
+        self.max_entries = max_entries;
+    }
+
+    /// Removes the least recently used glyphs from the cache, until it is
+    /// within the size limit. Returns true if any glyphs were evicted.
+    fn evict_least_recently_used(&mut self) -> bool
+    {
+        let excess = self.cache_entries.len().saturating_sub(self.max_entries);
+
+        if excess == 0 {
+            return false;
+        }
+
+        let this_frame = &self.this_frame;
+
+        let mut candidates: Vec<(u64, GlyphCacheKey)> = self
+            .cache_entries
+            .iter()
+            .filter(|(key, _)| !this_frame.contains(key))
+            .map(|(key, entry)| (entry.last_used_frame, key.clone()))
+            .collect();
+
+        candidates.sort_unstable_by_key(|(last_used_frame, _)| *last_used_frame);
+
+        let mut evicted = false;
+
+        for (_, key) in candidates.into_iter().take(excess) {
+            self.last_frame.remove(&key);
+            self.cache_entries.remove(&key);
+            evicted = true;
+        }
+
+        evicted
     }
 
     pub(crate) fn prepare_for_draw(
@@ -335,7 +480,9 @@ impl GlyphCache
         context: &GLContextManager
     ) -> Result<(), BacktraceError<ErrorMessage>>
     {
-        if self.try_insert_pending().is_err() {
+        // Evicted glyphs still occupy space in the textures, which can only be
+        // reclaimed by rearranging everything
+        if self.evict_least_recently_used() || self.try_insert_pending().is_err() {
             // Not enough space. Rearrange everything!
 
             self.textures.iter_mut().for_each(|texture| texture.clear());
@@ -404,7 +551,9 @@ impl GlyphCache
             last_frame: HashSet::new(),
             this_frame: HashSet::new(),
             cache_entries: HashMap::new(),
-            textures: Vec::new()
+            textures: Vec::new(),
+            frame_number: 0,
+            max_entries: usize::MAX
         }
     }
 
@@ -588,7 +737,11 @@ struct GlyphCacheEntry
 {
     glyph_bitmap: Rc<BitmapRGBA>,
     bounding_box_offset: IVec2,
-    texture_id: Option<usize>
+    texture_id: Option<usize>,
+    last_used_frame: u64,
+
+    /// True if the bitmap contains a color image, rather than a white glyph
+    is_color: bool
 }
 
 struct GlyphTextureCacheEntry
@@ -707,3 +860,84 @@ impl GlyphCacheTexture
         }
     }
 }
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    fn key(glyph_id: u16) -> GlyphCacheKey
+    {
+        GlyphCacheKey {
+            font_id: 0,
+            subpixel_offset: (
+                QuantizedDimension::from_pixels(0.0),
+                QuantizedDimension::from_pixels(0.0)
+            ),
+            scale: QuantizedDimension::from_pixels(16.0),
+            glyph_id: rusttype::GlyphId(glyph_id)
+        }
+    }
+
+    fn insert(cache: &mut GlyphCache, glyph_id: u16, last_used_frame: u64)
+    {
+        cache.cache_entries.insert(
+            key(glyph_id),
+            GlyphCacheEntry {
+                glyph_bitmap: Rc::new(BitmapRGBA::new(UVec2::new(1, 1))),
+                bounding_box_offset: IVec2::ZERO,
+                texture_id: None,
+                last_used_frame,
+                is_color: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_evict_least_recently_used()
+    {
+        let mut cache = GlyphCache::new();
+
+        for glyph_id in 0..4 {
+            insert(&mut cache, glyph_id, glyph_id as u64);
+        }
+
+        // No limit by default
+        assert!(!cache.evict_least_recently_used());
+        assert_eq!(4, cache.cache_entries.len());
+
+        cache.set_max_entries(2);
+
+        // The oldest glyph is still in use, so it must not be evicted
+        cache.this_frame.insert(key(0));
+
+        assert!(cache.evict_least_recently_used());
+        assert_eq!(2, cache.cache_entries.len());
+        assert!(cache.cache_entries.contains_key(&key(0)));
+        assert!(cache.cache_entries.contains_key(&key(3)));
+
+        assert!(!cache.evict_least_recently_used());
+    }
+
+    #[test]
+    fn test_evict_keeps_current_frame()
+    {
+        let mut cache = GlyphCache::new();
+        cache.set_max_entries(1);
+
+        for glyph_id in 0..3 {
+            insert(&mut cache, glyph_id, 0);
+            cache.this_frame.insert(key(glyph_id));
+        }
+
+        // Everything is in use, so the cache temporarily exceeds the limit
+        assert!(!cache.evict_least_recently_used());
+        assert_eq!(3, cache.cache_entries.len());
+
+        cache.on_new_frame_start();
+        cache.on_new_frame_start();
+
+        assert!(cache.evict_least_recently_used());
+        assert_eq!(1, cache.cache_entries.len());
+    }
+}
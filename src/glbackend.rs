@@ -34,6 +34,7 @@ pub mod types
     pub type GLTypeProgram = glow::Program;
     pub type GLTypeBuffer = glow::Buffer;
     pub type GLTypeTexture = glow::Texture;
+    pub type GLTypeFramebuffer = glow::Framebuffer;
     pub type GLTypeUniformLocation = glow::UniformLocation;
 }
 
@@ -52,12 +53,23 @@ pub mod constants
 
     pub const GL_SCISSOR_TEST: GLenum = glow::SCISSOR_TEST;
 
+    pub const GL_DEPTH_TEST: GLenum = glow::DEPTH_TEST;
+    pub const GL_LEQUAL: GLenum = glow::LEQUAL;
+
+    pub const GL_FRAMEBUFFER: GLenum = glow::FRAMEBUFFER;
+    pub const GL_FRAMEBUFFER_SRGB: GLenum = glow::FRAMEBUFFER_SRGB;
+    pub const GL_FRAMEBUFFER_COMPLETE: GLenum = glow::FRAMEBUFFER_COMPLETE;
+    pub const GL_COLOR_ATTACHMENT0: GLenum = glow::COLOR_ATTACHMENT0;
+
+    pub const GL_ZERO: GLenum = glow::ZERO;
     pub const GL_ONE: GLenum = glow::ONE;
+    pub const GL_DST_COLOR: GLenum = glow::DST_COLOR;
     pub const GL_SRC_ALPHA: GLenum = glow::SRC_ALPHA;
     pub const GL_ONE_MINUS_SRC_ALPHA: GLenum = glow::ONE_MINUS_SRC_ALPHA;
 
     pub const GL_NEAREST: GLenum = glow::NEAREST;
     pub const GL_LINEAR: GLenum = glow::LINEAR;
+    pub const GL_LINEAR_MIPMAP_LINEAR: GLenum = glow::LINEAR_MIPMAP_LINEAR;
 
     pub const GL_ARRAY_BUFFER: GLenum = glow::ARRAY_BUFFER;
     pub const GL_ELEMENT_ARRAY_BUFFER: GLenum = glow::ELEMENT_ARRAY_BUFFER;
@@ -70,6 +82,8 @@ pub mod constants
     pub const GL_R8: GLenum = glow::R8;
     pub const GL_RGB8: GLenum = glow::RGB8;
     pub const GL_RGBA8: GLenum = glow::RGBA8;
+    pub const GL_SRGB8: GLenum = glow::SRGB8;
+    pub const GL_SRGB8_ALPHA8: GLenum = glow::SRGB8_ALPHA8;
 
     pub const GL_RED: GLenum = glow::RED;
     pub const GL_RGB: GLenum = glow::RGB;
@@ -80,10 +94,13 @@ pub mod constants
     pub const GL_TEXTURE_MIN_FILTER: GLenum = glow::TEXTURE_MIN_FILTER;
     pub const GL_TEXTURE_MAG_FILTER: GLenum = glow::TEXTURE_MAG_FILTER;
     pub const GL_CLAMP_TO_EDGE: GLenum = glow::CLAMP_TO_EDGE;
+    pub const GL_REPEAT: GLenum = glow::REPEAT;
+    pub const GL_MIRRORED_REPEAT: GLenum = glow::MIRRORED_REPEAT;
 
     pub const GL_TRIANGLES: GLenum = glow::TRIANGLES;
 
     pub const GL_COLOR_BUFFER_BIT: GLenum = glow::COLOR_BUFFER_BIT;
+    pub const GL_DEPTH_BUFFER_BIT: GLenum = glow::DEPTH_BUFFER_BIT;
 
     pub const GL_NO_ERROR: GLenum = glow::NO_ERROR;
     pub const GL_INVALID_ENUM: GLenum = glow::INVALID_ENUM;
@@ -108,6 +125,9 @@ pub mod constants
     pub const GL_DEBUG_OUTPUT_SYNCHRONOUS: GLenum = glow::DEBUG_OUTPUT_SYNCHRONOUS;
 
     pub const GL_UNPACK_ALIGNMENT: GLenum = glow::UNPACK_ALIGNMENT;
+    pub const GL_PACK_ALIGNMENT: GLenum = glow::PACK_ALIGNMENT;
+
+    pub const GL_MAX_TEXTURE_SIZE: GLenum = glow::MAX_TEXTURE_SIZE;
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -154,11 +174,26 @@ pub trait GLBackend
     unsafe fn gl_delete_shader(&self, handle: GLTypeShader);
     unsafe fn gl_delete_buffer(&self, handle: GLTypeBuffer);
     unsafe fn gl_delete_texture(&self, handle: GLTypeTexture);
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer);
     unsafe fn gl_active_texture(&self, unit: GLenum);
     unsafe fn gl_bind_texture(&self, target: GLenum, handle: GLTypeTexture);
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    );
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        texture_target: GLenum,
+        texture: GLTypeTexture,
+        level: GLint
+    );
     unsafe fn gl_enable(&self, cap: GLenum);
     unsafe fn gl_disable(&self, cap: GLenum);
     unsafe fn gl_blend_func(&self, sfactor: GLenum, dfactor: GLenum);
+    unsafe fn gl_depth_func(&self, func: GLenum);
     unsafe fn gl_blend_func_separate(
         &self,
         sfactor: GLenum,
@@ -171,11 +206,27 @@ pub trait GLBackend
     unsafe fn gl_disable_vertex_attrib_array(&self, handle: GLuint);
     unsafe fn gl_uniform_1f(&self, handle: &GLTypeUniformLocation, value: f32);
     unsafe fn gl_uniform_1i(&self, handle: &GLTypeUniformLocation, value: GLint);
+    unsafe fn gl_uniform_2f(&self, handle: &GLTypeUniformLocation, x: f32, y: f32);
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    );
+    unsafe fn gl_bind_attrib_location(
+        &self,
+        program: GLTypeProgram,
+        index: GLuint,
+        name: &str
+    );
     unsafe fn gl_attach_shader(&self, program: GLTypeProgram, shader: GLTypeShader);
     unsafe fn gl_link_program(&self, program: GLTypeProgram);
     unsafe fn gl_shader_source(&self, handle: GLTypeShader, source: &str);
     unsafe fn gl_compile_shader(&self, handle: GLTypeShader);
     unsafe fn gl_tex_parameter_i(&self, target: GLenum, parameter: GLenum, value: GLint);
+    unsafe fn gl_generate_mipmap(&self, target: GLenum);
     unsafe fn gl_bind_buffer(&self, target: GLenum, handle: GLTypeBuffer);
     unsafe fn gl_buffer_data(&self, target: GLenum, data: &[u8], usage: GLenum);
     unsafe fn gl_draw_arrays(&self, mode: GLenum, first: GLint, count: GLsizei);
@@ -183,6 +234,7 @@ pub trait GLBackend
     unsafe fn gl_clear(&self, mask: GLenum);
     unsafe fn gl_enable_debug_message_callback(&self);
     unsafe fn gl_get_string(&self, parameter: GLenum) -> String;
+    unsafe fn gl_get_integer(&self, parameter: GLenum) -> GLint;
     unsafe fn gl_viewport(&self, x: i32, y: i32, width: i32, height: i32);
     unsafe fn gl_scissor(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei);
     unsafe fn gl_pixel_store_i(&self, param: GLenum, value: GLint);
@@ -240,6 +292,13 @@ pub trait GLBackend
         &self
     ) -> Result<GLTypeTexture, BacktraceError<ErrorMessage>>;
 
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>;
+
+    #[must_use]
+    unsafe fn gl_check_framebuffer_status(&self, target: GLenum) -> GLenum;
+
     #[must_use]
     unsafe fn gl_get_error(&self) -> GLenum;
 
@@ -361,6 +420,11 @@ impl GLBackend for GLBackendGlow
         self.context.delete_texture(handle)
     }
 
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer)
+    {
+        self.context.delete_framebuffer(handle)
+    }
+
     unsafe fn gl_active_texture(&self, unit: GLenum)
     {
         self.context.active_texture(unit)
@@ -371,6 +435,33 @@ impl GLBackend for GLBackendGlow
         self.context.bind_texture(target, Some(handle))
     }
 
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    )
+    {
+        self.context.bind_framebuffer(target, handle)
+    }
+
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        texture_target: GLenum,
+        texture: GLTypeTexture,
+        level: GLint
+    )
+    {
+        self.context.framebuffer_texture_2d(
+            target,
+            attachment,
+            texture_target,
+            Some(texture),
+            level
+        )
+    }
+
     unsafe fn gl_enable(&self, cap: GLenum)
     {
         self.context.enable(cap)
@@ -386,6 +477,11 @@ impl GLBackend for GLBackendGlow
         self.context.blend_func(sfactor, dfactor)
     }
 
+    unsafe fn gl_depth_func(&self, func: GLenum)
+    {
+        self.context.depth_func(func)
+    }
+
     unsafe fn gl_blend_func_separate(
         &self,
         sfactor: GLenum,
@@ -423,6 +519,33 @@ impl GLBackend for GLBackendGlow
         self.context.uniform_1_i32(Some(handle), value)
     }
 
+    unsafe fn gl_uniform_2f(&self, handle: &GLTypeUniformLocation, x: f32, y: f32)
+    {
+        self.context.uniform_2_f32(Some(handle), x, y)
+    }
+
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    )
+    {
+        self.context.uniform_4_f32(Some(handle), x, y, z, w)
+    }
+
+    unsafe fn gl_bind_attrib_location(
+        &self,
+        program: GLTypeProgram,
+        index: GLuint,
+        name: &str
+    )
+    {
+        self.context.bind_attrib_location(program, index, name)
+    }
+
     unsafe fn gl_attach_shader(&self, program: GLTypeProgram, shader: GLTypeShader)
     {
         self.context.attach_shader(program, shader)
@@ -448,6 +571,11 @@ impl GLBackend for GLBackendGlow
         self.context.tex_parameter_i32(target, parameter, value)
     }
 
+    unsafe fn gl_generate_mipmap(&self, target: u32)
+    {
+        self.context.generate_mipmap(target)
+    }
+
     unsafe fn gl_bind_buffer(&self, target: u32, handle: GLTypeBuffer)
     {
         self.context.bind_buffer(target, Some(handle))
@@ -508,6 +636,11 @@ impl GLBackend for GLBackendGlow
         self.context.get_parameter_string(parameter)
     }
 
+    unsafe fn gl_get_integer(&self, parameter: u32) -> i32
+    {
+        self.context.get_parameter_i32(parameter)
+    }
+
     unsafe fn gl_viewport(&self, x: i32, y: i32, width: i32, height: i32)
     {
         self.context.viewport(x, y, width, height)
@@ -631,6 +764,22 @@ impl GLBackend for GLBackendGlow
         Ok(handle)
     }
 
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        let handle = self.context.create_framebuffer().map_err(|err| {
+            ErrorMessage::msg(format!("Failed to create framebuffer: {err}"))
+        })?;
+
+        Ok(handle)
+    }
+
+    unsafe fn gl_check_framebuffer_status(&self, target: GLenum) -> GLenum
+    {
+        self.context.check_framebuffer_status(target)
+    }
+
     unsafe fn gl_get_error(&self) -> GLenum
     {
         self.context.get_error()
@@ -29,6 +29,7 @@ use crate::error::{BacktraceError, Context, ErrorMessage};
 use crate::glbackend::constants::*;
 use crate::glbackend::types::{
     GLTypeBuffer,
+    GLTypeFramebuffer,
     GLTypeProgram,
     GLTypeShader,
     GLTypeTexture,
@@ -79,7 +80,8 @@ enum GLHandleType
     Program,
     Shader,
     Buffer,
-    Texture
+    Texture,
+    Framebuffer
 }
 
 trait GLHandleId: Debug + Hash + PartialEq + Eq
@@ -112,6 +114,12 @@ struct GLHandleTypeTexture
     handle: GLTypeTexture
 }
 
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct GLHandleTypeFramebuffer
+{
+    handle: GLTypeFramebuffer
+}
+
 struct GLHandle<HandleType: GLHandleId>
 {
     context: Weak<RefCell<GLContextManagerState>>,
@@ -177,6 +185,7 @@ impl<HandleType: GLHandleId> GLHandle<HandleType>
             GLHandleType::Shader => gl_clear_and_log_old_error(context),
             GLHandleType::Buffer => {}
             GLHandleType::Texture => {}
+            GLHandleType::Framebuffer => gl_clear_and_log_old_error(context)
         }
 
         let handle = handle_creator().context("Handle creation failed")?;
@@ -186,6 +195,7 @@ impl<HandleType: GLHandleId> GLHandle<HandleType>
             GLHandleType::Shader => gl_check_error_always(context)?,
             GLHandleType::Buffer => {}
             GLHandleType::Texture => {}
+            GLHandleType::Framebuffer => gl_check_error_always(context)?
         }
 
         Ok(GLHandle {
@@ -257,6 +267,18 @@ impl GLHandleId for GLHandleTypeTexture
     }
 }
 
+impl GLHandleId for GLHandleTypeFramebuffer
+{
+    type HandleRawType = GLTypeFramebuffer;
+
+    fn delete(&self, context: &GLContextManager)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_delete_framebuffer(self.handle)
+        });
+    }
+}
+
 #[derive(Debug)]
 pub struct GLProgram
 {
@@ -335,6 +357,25 @@ impl GLProgram
         program.attach_shader(context, vertex_shader)?;
         program.attach_shader(context, fragment_shader)?;
 
+        let attribute_names: Vec<&'static str> =
+            attribute_names.into_iter().copied().collect();
+
+        // Every program is given the same attribute locations, so that vertex
+        // buffers can be shared between programs.
+        context.with_gl_backend::<Result<(), BacktraceError<ErrorMessage>>, _>(
+            |backend| unsafe {
+                for (index, attribute_name) in attribute_names.iter().enumerate() {
+                    backend.gl_bind_attrib_location(
+                        program.get_handle(),
+                        index.try_into()?,
+                        attribute_name
+                    );
+                }
+
+                Ok(())
+            }
+        )?;
+
         context.with_gl_backend(|backend| unsafe {
             backend.gl_link_program(program.get_handle());
         });
@@ -354,10 +395,12 @@ impl GLProgram
 
         gl_check_error_always(context)?;
 
-        for attribute_name in attribute_names.into_iter() {
+        for (index, attribute_name) in attribute_names.into_iter().enumerate() {
             program.attribute_handles.insert(
-                attribute_name.as_ref(),
-                program.get_attribute_handle(attribute_name.as_ref())?
+                attribute_name,
+                GLAttributeHandle {
+                    handle: index.try_into()?
+                }
             );
         }
 
@@ -543,6 +586,20 @@ impl GLUniformHandle
             backend.gl_uniform_1i(&self.handle, value)
         })
     }
+
+    pub fn set_value_vec2(&self, context: &GLContextManager, x: f32, y: f32)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_uniform_2f(&self.handle, x, y)
+        })
+    }
+
+    pub fn set_value_vec4(&self, context: &GLContextManager, value: [f32; 4])
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_uniform_4f(&self.handle, value[0], value[1], value[2], value[3])
+        })
+    }
 }
 
 pub enum GLBufferTarget
@@ -630,11 +687,20 @@ impl GLBuffer
     }
 }
 
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum GLTextureWrap
+{
+    ClampToEdge,
+    Repeat,
+    MirroredRepeat
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum GLTextureSmoothing
 {
     NearestNeighbour,
-    Linear
+    Linear,
+    LinearMipmap
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -653,19 +719,21 @@ impl From<ImageDataType> for GLTextureImageFormatU8
     {
         match value {
             ImageDataType::RGB => Self::RGB,
-            ImageDataType::RGBA => Self::RGBA
+            ImageDataType::RGBA | ImageDataType::RGBAPremultiplied => Self::RGBA
         }
     }
 }
 
 impl GLTextureImageFormatU8
 {
-    fn get_internal_format(&self) -> GLenum
+    fn get_internal_format(&self, srgb: bool) -> GLenum
     {
-        match self {
-            GLTextureImageFormatU8::Red => GL_R8,
-            GLTextureImageFormatU8::RGB => GL_RGB8,
-            GLTextureImageFormatU8::RGBA => GL_RGBA8
+        match (self, srgb) {
+            (GLTextureImageFormatU8::Red, _) => GL_R8,
+            (GLTextureImageFormatU8::RGB, false) => GL_RGB8,
+            (GLTextureImageFormatU8::RGB, true) => GL_SRGB8,
+            (GLTextureImageFormatU8::RGBA, false) => GL_RGBA8,
+            (GLTextureImageFormatU8::RGBA, true) => GL_SRGB8_ALPHA8
         }
     }
 
@@ -678,7 +746,7 @@ impl GLTextureImageFormatU8
         }
     }
 
-    fn get_bytes_per_pixel(&self) -> usize
+    pub fn get_bytes_per_pixel(&self) -> usize
     {
         match self {
             GLTextureImageFormatU8::Red => 1,
@@ -733,24 +801,22 @@ impl GLTexture
             return Ok(());
         }
 
-        let smoothing_constant = match smoothing {
-            GLTextureSmoothing::NearestNeighbour => GL_NEAREST,
-            GLTextureSmoothing::Linear => GL_LINEAR
-        } as GLint;
+        // Mipmaps can't be generated for an empty texture
+        let mipmaps =
+            smoothing == GLTextureSmoothing::LinearMipmap && size.x > 0 && size.y > 0;
 
-        context.bind_texture(self);
+        let (min_filter, mag_filter) = match smoothing {
+            GLTextureSmoothing::NearestNeighbour => (GL_NEAREST, GL_NEAREST),
+            GLTextureSmoothing::Linear => (GL_LINEAR, GL_LINEAR),
+            GLTextureSmoothing::LinearMipmap if mipmaps => {
+                (GL_LINEAR_MIPMAP_LINEAR, GL_LINEAR)
+            }
+            GLTextureSmoothing::LinearMipmap => (GL_LINEAR, GL_LINEAR)
+        };
 
-        let width_stride_bytes = size.x as usize * format.get_bytes_per_pixel();
+        context.bind_texture(self);
 
-        let unpack_alignment = if width_stride_bytes % 8 == 0 {
-            8
-        } else if width_stride_bytes % 4 == 0 {
-            4
-        } else if width_stride_bytes % 2 == 0 {
-            2
-        } else {
-            1
-        };
+        let unpack_alignment = Self::unpack_alignment(&format, size);
 
         context.with_gl_backend::<Result<(), BacktraceError<ErrorMessage>>, _>(
             |backend| unsafe {
@@ -768,19 +834,19 @@ impl GLTexture
                 backend.gl_tex_parameter_i(
                     GL_TEXTURE_2D,
                     GL_TEXTURE_MIN_FILTER,
-                    smoothing_constant
+                    min_filter as GLint
                 );
                 backend.gl_tex_parameter_i(
                     GL_TEXTURE_2D,
                     GL_TEXTURE_MAG_FILTER,
-                    smoothing_constant
+                    mag_filter as GLint
                 );
 
                 backend.gl_tex_image_2d(
                     GL_TEXTURE_2D,
                     0,
                     format
-                        .get_internal_format()
+                        .get_internal_format(context.is_srgb_framebuffer_enabled())
                         .try_into()
                         .context("Failed to cast internal format")?,
                     size.x.try_into()?,
@@ -791,10 +857,179 @@ impl GLTexture
                     Some(data)
                 );
 
+                if mipmaps {
+                    backend.gl_generate_mipmap(GL_TEXTURE_2D);
+                }
+
+                Ok(())
+            }
+        )
+    }
+
+    pub fn set_wrap(&self, context: &GLContextManager, wrap: GLTextureWrap)
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring texture set_wrap: invalid GL context");
+            return;
+        }
+
+        let wrap_constant = match wrap {
+            GLTextureWrap::ClampToEdge => GL_CLAMP_TO_EDGE,
+            GLTextureWrap::Repeat => GL_REPEAT,
+            GLTextureWrap::MirroredRepeat => GL_MIRRORED_REPEAT
+        } as GLint;
+
+        context.bind_texture(self);
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_tex_parameter_i(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, wrap_constant);
+            backend.gl_tex_parameter_i(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, wrap_constant);
+        });
+    }
+
+    pub fn generate_mipmaps(&self, context: &GLContextManager)
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring texture generate_mipmaps: invalid GL context");
+            return;
+        }
+
+        context.bind_texture(self);
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_generate_mipmap(GL_TEXTURE_2D);
+        });
+    }
+
+    pub fn is_from_context(&self, context: &GLContextManager) -> bool
+    {
+        self.handle.context.ptr_eq(&Rc::downgrade(&context.state))
+    }
+
+    pub fn set_image_sub_data(
+        &self,
+        context: &GLContextManager,
+        format: GLTextureImageFormatU8,
+        offset: &UVec2,
+        size: &UVec2,
+        data: &[u8]
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring texture set_image_sub_data: invalid GL context");
+            return Ok(());
+        }
+
+        context.bind_texture(self);
+
+        let unpack_alignment = Self::unpack_alignment(&format, size);
+
+        context.with_gl_backend::<Result<(), BacktraceError<ErrorMessage>>, _>(
+            |backend| unsafe {
+                backend.gl_pixel_store_i(GL_UNPACK_ALIGNMENT, unpack_alignment);
+
+                backend.gl_tex_sub_image_2d(
+                    GL_TEXTURE_2D,
+                    0,
+                    offset.x.try_into()?,
+                    offset.y.try_into()?,
+                    size.x.try_into()?,
+                    size.y.try_into()?,
+                    format.get_format(),
+                    GL_UNSIGNED_BYTE,
+                    data
+                );
+
                 Ok(())
             }
         )
     }
+
+    fn unpack_alignment(format: &GLTextureImageFormatU8, size: &UVec2) -> GLint
+    {
+        let width_stride_bytes = size.x as usize * format.get_bytes_per_pixel();
+
+        if width_stride_bytes % 8 == 0 {
+            8
+        } else if width_stride_bytes % 4 == 0 {
+            4
+        } else if width_stride_bytes % 2 == 0 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GLFramebuffer
+{
+    handle: Rc<GLHandle<GLHandleTypeFramebuffer>>,
+    texture: GLTexture
+}
+
+impl GLHandleOwner<GLHandleTypeFramebuffer> for GLFramebuffer
+{
+    fn get_handle(&self) -> <GLHandleTypeFramebuffer as GLHandleId>::HandleRawType
+    {
+        self.handle.handle.handle
+    }
+}
+
+impl GLFramebuffer
+{
+    fn new(
+        context: &GLContextManager,
+        texture: GLTexture
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let handle = GLHandle::wrap(context, GLHandleType::Framebuffer, || {
+            context.with_gl_backend(|backend| unsafe {
+                Ok(GLHandleTypeFramebuffer {
+                    handle: backend.gl_gen_framebuffer()?
+                })
+            })
+        })?;
+
+        let framebuffer = GLFramebuffer {
+            handle: Rc::new(handle),
+            texture
+        };
+
+        let previous = RefCell::borrow(&context.state).active_framebuffer.clone();
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_framebuffer(GL_FRAMEBUFFER, Some(framebuffer.get_handle()));
+            backend.gl_framebuffer_texture_2d(
+                GL_FRAMEBUFFER,
+                GL_COLOR_ATTACHMENT0,
+                GL_TEXTURE_2D,
+                framebuffer.texture.get_handle(),
+                0
+            );
+        });
+
+        let status = context.with_gl_backend(|backend| unsafe {
+            backend.gl_check_framebuffer_status(GL_FRAMEBUFFER)
+        });
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_framebuffer(
+                GL_FRAMEBUFFER,
+                previous.as_ref().map(|previous| previous.get_handle())
+            );
+        });
+
+        gl_check_error_always(context)?;
+
+        if status != GL_FRAMEBUFFER_COMPLETE {
+            return Err(ErrorMessage::msg(format!(
+                "Framebuffer is incomplete (status {status:#x})"
+            )));
+        }
+
+        Ok(framebuffer)
+    }
 }
 
 #[must_use]
@@ -831,8 +1066,11 @@ struct GLContextManagerState
     active_texture: Option<GLTexture>,
     active_program: Option<Rc<GLProgram>>,
     active_blend_mode: Option<GLBlendEnabled>,
+    active_framebuffer: Option<GLFramebuffer>,
     viewport_size: Option<UVec2>,
     scissor_enabled: bool,
+    depth_test_enabled: bool,
+    srgb_framebuffer_enabled: bool,
     gl_backend: Rc<dyn GLBackend + 'static>,
     gl_version: GLVersion,
     weak_ref_to_self: Weak<RefCell<GLContextManagerState>>
@@ -865,8 +1103,11 @@ impl GLContextManager
                 active_texture: None,
                 active_program: None,
                 active_blend_mode: None,
+                active_framebuffer: None,
                 viewport_size: None,
                 scissor_enabled: false,
+                depth_test_enabled: false,
+                srgb_framebuffer_enabled: false,
                 gl_backend,
                 gl_version,
                 weak_ref_to_self: Weak::new()
@@ -931,6 +1172,42 @@ impl GLContextManager
         GLTexture::new(self)
     }
 
+    pub fn new_framebuffer(
+        &self,
+        texture: GLTexture
+    ) -> Result<GLFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        self.ensure_valid()?;
+        GLFramebuffer::new(self, texture)
+    }
+
+    /// Directs subsequent drawing to the specified framebuffer, or to the
+    /// default framebuffer if `None`. Returns the previously bound
+    /// framebuffer.
+    pub fn bind_framebuffer(
+        &self,
+        framebuffer: Option<&GLFramebuffer>
+    ) -> Option<GLFramebuffer>
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring bind_framebuffer: invalid GL context");
+            return None;
+        }
+
+        let previous = RefCell::borrow_mut(&self.state).active_framebuffer.take();
+
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_framebuffer(
+                GL_FRAMEBUFFER,
+                framebuffer.map(|framebuffer| framebuffer.get_handle())
+            );
+        });
+
+        RefCell::borrow_mut(&self.state).active_framebuffer = framebuffer.cloned();
+
+        previous
+    }
+
     pub fn set_viewport_size(&self, size: UVec2)
     {
         if !self.is_valid() {
@@ -1038,6 +1315,40 @@ impl GLContextManager
                         GL_ONE,
                         GL_ONE_MINUS_SRC_ALPHA
                     );
+                }),
+
+                GLBlendMode::Additive => self.with_gl_backend(|backend| unsafe {
+                    backend.gl_enable(GL_BLEND);
+                    backend.gl_blend_func_separate(GL_SRC_ALPHA, GL_ONE, GL_ONE, GL_ONE);
+                }),
+
+                GLBlendMode::PremultipliedOneMinusSrcAlpha => {
+                    self.with_gl_backend(|backend| unsafe {
+                        backend.gl_enable(GL_BLEND);
+                        backend.gl_blend_func_separate(
+                            GL_ONE,
+                            GL_ONE_MINUS_SRC_ALPHA,
+                            GL_ONE,
+                            GL_ONE_MINUS_SRC_ALPHA
+                        );
+                    })
+                }
+
+                GLBlendMode::PremultipliedAdditive => {
+                    self.with_gl_backend(|backend| unsafe {
+                        backend.gl_enable(GL_BLEND);
+                        backend.gl_blend_func_separate(GL_ONE, GL_ONE, GL_ONE, GL_ONE);
+                    })
+                }
+
+                GLBlendMode::Multiply => self.with_gl_backend(|backend| unsafe {
+                    backend.gl_enable(GL_BLEND);
+                    backend.gl_blend_func_separate(
+                        GL_DST_COLOR,
+                        GL_ZERO,
+                        GL_ZERO,
+                        GL_ONE
+                    );
                 })
             },
 
@@ -1060,14 +1371,69 @@ impl GLContextManager
         }
     }
 
+    pub fn set_enable_depth_test(&self, enabled: bool)
+    {
+        if enabled != self.state.borrow().depth_test_enabled {
+            self.with_gl_backend(|backend| unsafe {
+                match enabled {
+                    true => {
+                        // Primitives at the same depth are drawn in call order
+                        backend.gl_depth_func(GL_LEQUAL);
+                        backend.gl_enable(GL_DEPTH_TEST)
+                    }
+                    false => backend.gl_disable(GL_DEPTH_TEST)
+                }
+            });
+            self.state.borrow_mut().depth_test_enabled = enabled;
+        }
+    }
+
+    pub fn set_srgb_framebuffer_enabled(
+        &self,
+        enabled: bool
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        if enabled && self.version() == GLVersion::WebGL2_0 {
+            return Err(ErrorMessage::msg(
+                "sRGB framebuffers are not supported in WebGL"
+            ));
+        }
+
+        if enabled != self.state.borrow().srgb_framebuffer_enabled {
+            self.with_gl_backend(|backend| unsafe {
+                match enabled {
+                    true => backend.gl_enable(GL_FRAMEBUFFER_SRGB),
+                    false => backend.gl_disable(GL_FRAMEBUFFER_SRGB)
+                }
+            });
+            self.state.borrow_mut().srgb_framebuffer_enabled = enabled;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_srgb_framebuffer_enabled(&self) -> bool
+    {
+        self.state.borrow().srgb_framebuffer_enabled
+    }
+
     pub fn set_clip(&self, x: i32, y: i32, width: i32, height: i32)
     {
         let vp_height = match self.state.borrow().viewport_size {
             None => panic!("Call to set_clip before viewport size set"),
             Some(viewport_size) => viewport_size.y as i32
         };
+
+        // Offscreen framebuffers are drawn upside down (see Renderer2D), so
+        // their rows are already in the same order as the clip rectangle.
+        let y = match self.state.borrow().active_framebuffer {
+            None => vp_height - y - height,
+            Some(_) => y
+        };
+
         self.with_gl_backend(|backend| unsafe {
-            backend.gl_scissor(x, vp_height - y - height, width, height);
+            backend.gl_scissor(x, y, width, height);
         });
     }
 
@@ -1092,9 +1458,14 @@ impl GLContextManager
             return;
         }
 
+        let color = match self.is_srgb_framebuffer_enabled() {
+            true => color.to_linear(),
+            false => color
+        };
+
         self.with_gl_backend(|backend| unsafe {
             backend.gl_clear_color(color.r(), color.g(), color.b(), color.a());
-            backend.gl_clear(GL_COLOR_BUFFER_BIT);
+            backend.gl_clear(GL_COLOR_BUFFER_BIT | GL_DEPTH_BUFFER_BIT);
         });
     }
 
@@ -1125,6 +1496,15 @@ impl GLContextManager
         self.state.borrow().gl_version
     }
 
+    /// The maximum width and height of a texture, in pixels.
+    pub fn max_texture_size(&self) -> u32
+    {
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_get_integer(GL_MAX_TEXTURE_SIZE)
+        })
+        .max(0) as u32
+    }
+
     pub fn capture(&mut self, format: ImageDataType) -> RawBitmapData
     {
         let viewport_size = match self.state.borrow().viewport_size {
@@ -1132,8 +1512,46 @@ impl GLContextManager
             Some(value) => value
         };
 
-        let width: usize = viewport_size.x.try_into().unwrap();
-        let height: usize = viewport_size.y.try_into().unwrap();
+        // The default framebuffer is stored bottom row first
+        let flip_vertically = self.state.borrow().active_framebuffer.is_none();
+
+        self.read_pixels(viewport_size, format, flip_vertically)
+    }
+
+    /// Reads back the contents of the specified texture, by temporarily
+    /// attaching it to a framebuffer.
+    pub fn read_texture(
+        &self,
+        texture: &GLTexture,
+        size: UVec2,
+        format: ImageDataType
+    ) -> Result<RawBitmapData, BacktraceError<ErrorMessage>>
+    {
+        if !texture.is_from_context(self) {
+            return Err(ErrorMessage::msg(
+                "The image was not created in the current graphics context"
+            ));
+        }
+
+        let framebuffer = self.new_framebuffer(texture.clone())?;
+        let previous = self.bind_framebuffer(Some(&framebuffer));
+
+        let result = self.read_pixels(size, format, false);
+
+        self.bind_framebuffer(previous.as_ref());
+
+        Ok(result)
+    }
+
+    fn read_pixels(
+        &self,
+        size: UVec2,
+        format: ImageDataType,
+        flip_vertically: bool
+    ) -> RawBitmapData
+    {
+        let width: usize = size.x.try_into().unwrap();
+        let height: usize = size.y.try_into().unwrap();
 
         let gl_format = GLTextureImageFormatU8::from(format);
 
@@ -1145,6 +1563,7 @@ impl GLContextManager
         let mut buf: Vec<u8> = Vec::with_capacity(bytes);
 
         self.with_gl_backend(|backend| unsafe {
+            backend.gl_pixel_store_i(GL_PACK_ALIGNMENT, 1);
             backend.gl_read_pixels(
                 0,
                 0,
@@ -1164,7 +1583,9 @@ impl GLContextManager
 
         let buf_ptr = buf.as_mut_ptr();
 
-        for row in 0..(height / 2) {
+        let rows_to_flip = if flip_vertically { height / 2 } else { 0 };
+
+        for row in 0..rows_to_flip {
             let bottom_row = height - row - 1;
 
             let top_start = row * row_bytes;
@@ -1179,20 +1600,50 @@ impl GLContextManager
             }
         }
 
-        RawBitmapData::new(buf, viewport_size, format)
+        RawBitmapData::new(buf, size, format)
     }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum GLBlendMode
 {
-    OneMinusSrcAlpha
+    OneMinusSrcAlpha,
+    Additive,
+    Multiply,
+    PremultipliedOneMinusSrcAlpha,
+    PremultipliedAdditive
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum GLBlendEnabled
 {
     Enabled(GLBlendMode),
-    #[allow(dead_code)]
     Disabled
 }
+
+impl GLBlendEnabled
+{
+    /// Returns the equivalent blend mode for source colors which have already
+    /// been multiplied by their alpha value, or `None` if this blend mode
+    /// doesn't take the source alpha value into account.
+    pub fn for_premultiplied_alpha(&self) -> Option<GLBlendEnabled>
+    {
+        match self {
+            GLBlendEnabled::Enabled(GLBlendMode::OneMinusSrcAlpha)
+            | GLBlendEnabled::Enabled(GLBlendMode::PremultipliedOneMinusSrcAlpha) => {
+                Some(GLBlendEnabled::Enabled(
+                    GLBlendMode::PremultipliedOneMinusSrcAlpha
+                ))
+            }
+
+            GLBlendEnabled::Enabled(GLBlendMode::Additive)
+            | GLBlendEnabled::Enabled(GLBlendMode::PremultipliedAdditive) => {
+                Some(GLBlendEnabled::Enabled(GLBlendMode::PremultipliedAdditive))
+            }
+
+            GLBlendEnabled::Enabled(GLBlendMode::Multiply) | GLBlendEnabled::Disabled => {
+                None
+            }
+        }
+    }
+}
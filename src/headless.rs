@@ -0,0 +1,264 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use glutin::dpi::PhysicalSize;
+use glutin::event_loop::EventLoop;
+use glutin::{Api, ContextBuilder, GlRequest, PossiblyCurrent};
+
+use crate::dimen::UVec2;
+use crate::error::BacktraceError;
+use crate::image::{ImageDataType, RawBitmapData};
+use crate::{GLRenderer, GLRendererCreationError, Graphics2D};
+
+thread_local! {
+    // Headless contexts must still be created using an event loop, so a
+    // single event loop is shared by all renderers on each thread.
+    static EVENT_LOOP: EventLoop<()> = create_event_loop();
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn create_event_loop() -> EventLoop<()>
+{
+    use glutin::platform::unix::EventLoopExtUnix;
+    EventLoop::new_any_thread()
+}
+
+#[cfg(target_os = "windows")]
+fn create_event_loop() -> EventLoop<()>
+{
+    use glutin::platform::windows::EventLoopExtWindows;
+    EventLoop::new_any_thread()
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "windows"
+)))]
+fn create_event_loop() -> EventLoop<()>
+{
+    EventLoop::new()
+}
+
+/// A renderer which draws into an offscreen OpenGL context, without creating
+/// a window. This allows the output of [Graphics2D] to be captured in tests,
+/// including in CI environments without a GPU, where a software OpenGL
+/// implementation (such as Mesa's `llvmpipe`) is used instead.
+///
+/// This requires the `headless` feature to be enabled. On Linux, an X server
+/// (such as `xvfb`) or EGL support is required. On macOS, the renderer must be
+/// created on the main thread.
+///
+/// ```rust,no_run
+/// use speedy2d::color::Color;
+/// use speedy2d::headless::HeadlessRenderer;
+/// use speedy2d::image::ImageDataType;
+///
+/// let mut renderer = HeadlessRenderer::new((64, 64)).unwrap();
+///
+/// let pixels = renderer.render_to_bitmap(ImageDataType::RGBA, |graphics| {
+///     graphics.clear_screen(Color::WHITE);
+///     graphics.draw_circle((32.0, 32.0), 16.0, Color::BLUE);
+/// });
+///
+/// assert_eq!(&[0, 0, 255, 255], &pixels.data()[(32 * 64 + 32) * 4..][..4]);
+/// ```
+pub struct HeadlessRenderer
+{
+    // The renderer must be dropped before the context
+    renderer: GLRenderer,
+    context: Option<glutin::Context<PossiblyCurrent>>,
+    size: UVec2
+}
+
+impl HeadlessRenderer
+{
+    /// Creates a new offscreen OpenGL context of the specified size in
+    /// pixels, and a renderer which draws into it.
+    pub fn new<V: Into<UVec2>>(
+        size: V
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    {
+        let size = size.into();
+
+        let context = EVENT_LOOP.with(|event_loop| {
+            ContextBuilder::new()
+                .with_multisampling(0)
+                .with_depth_buffer(24)
+                .with_vsync(false)
+                .with_gl(GlRequest::Specific(Api::OpenGl, (2, 0)))
+                .build_headless(event_loop, PhysicalSize::new(size.x, size.y))
+        });
+
+        let context = context.map_err(|err| {
+            GLRendererCreationError::msg_with_cause(
+                "Failed to create headless GL context",
+                err
+            )
+        })?;
+
+        let context = unsafe { context.make_current() }.map_err(|(_, err)| {
+            GLRendererCreationError::msg_with_cause(
+                "Failed to make headless GL context current",
+                err
+            )
+        })?;
+
+        let renderer = unsafe {
+            GLRenderer::new_for_gl_context(size, |name| {
+                context.get_proc_address(name) as *const _
+            })?
+        };
+
+        Ok(HeadlessRenderer {
+            renderer,
+            context: Some(context),
+            size
+        })
+    }
+
+    /// Returns the size of the offscreen context in pixels.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> UVec2
+    {
+        self.size
+    }
+
+    /// Draws a frame into the offscreen context, in the same way as
+    /// [GLRenderer::draw_frame]. The contents of the frame can be read back
+    /// using [Graphics2D::capture] inside the callback.
+    pub fn draw_frame<F: FnOnce(&mut Graphics2D) -> R, R>(&mut self, callback: F) -> R
+    {
+        self.make_context_current();
+        self.renderer.draw_frame(callback)
+    }
+
+    /// Draws a frame into the offscreen context, and returns the pixels which
+    /// were drawn, in the specified format. Pixels which aren't drawn by the
+    /// callback are undefined, so the callback should start by calling
+    /// [Graphics2D::clear_screen].
+    pub fn render_to_bitmap<F: FnOnce(&mut Graphics2D)>(
+        &mut self,
+        format: ImageDataType,
+        callback: F
+    ) -> RawBitmapData
+    {
+        self.draw_frame(|graphics| {
+            callback(graphics);
+            graphics.capture(format)
+        })
+    }
+
+    /// Returns the underlying [GLRenderer], for example to create images
+    /// outside of a frame.
+    #[inline]
+    pub fn renderer(&mut self) -> &mut GLRenderer
+    {
+        self.make_context_current();
+        &mut self.renderer
+    }
+
+    /// Another context may have been made current on this thread since this
+    /// renderer was created, for example by another `HeadlessRenderer`.
+    fn make_context_current(&mut self)
+    {
+        let context = match self.context.take() {
+            None => return,
+            Some(context) if context.is_current() => context,
+            Some(context) => match unsafe { context.make_current() } {
+                Ok(context) => context,
+                Err((context, err)) => {
+                    log::warn!("Failed to make headless GL context current: {:?}", err);
+                    context
+                }
+            }
+        };
+
+        self.context = Some(context);
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+    use crate::color::Color;
+    use crate::font::{Font, TextLayout, TextOptions};
+    use crate::shape::Rectangle;
+
+    fn pixel_at(pixels: &RawBitmapData, x: u32, y: u32) -> [u8; 4]
+    {
+        let index = ((y * pixels.size().x + x) * 4) as usize;
+        let mut result = [0; 4];
+        result.copy_from_slice(&pixels.data()[index..index + 4]);
+        result
+    }
+
+    #[test]
+    fn test_render_shape_and_text()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+        let text = font.layout_text("I", 48.0, TextOptions::new());
+
+        let mut renderer = HeadlessRenderer::new((128, 64)).unwrap();
+
+        assert_eq!(UVec2::new(128, 64), renderer.size());
+
+        let pixels = renderer.render_to_bitmap(ImageDataType::RGBA, |graphics| {
+            graphics.clear_screen(Color::WHITE);
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((0.0, 0.0), (64.0, 64.0)),
+                Color::RED
+            );
+            graphics.draw_text((72.0, 0.0), Color::BLACK, &text);
+        });
+
+        assert_eq!(UVec2::new(128, 64), pixels.size());
+
+        for (x, y) in [(0, 0), (32, 32), (63, 63)].iter() {
+            assert_eq!([255, 0, 0, 255], pixel_at(&pixels, *x, *y));
+        }
+
+        let mut text_pixels = 0;
+
+        for y in 0..64 {
+            for x in 64..128 {
+                let [r, g, b, a] = pixel_at(&pixels, x, y);
+
+                // The text is antialiased, so only shades of grey are expected
+                assert!(r == g && g == b && a == 255, "({}, {})", x, y);
+
+                if r == 0 {
+                    text_pixels += 1;
+                }
+            }
+        }
+
+        assert!(text_pixels > 0);
+        assert_eq!([255, 255, 255, 255], pixel_at(&pixels, 127, 63));
+    }
+}
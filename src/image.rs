@@ -14,8 +14,11 @@
  *  limitations under the License.
  */
 
-use crate::dimen::UVec2;
-use crate::glwrapper::GLTexture;
+use crate::color::Color;
+use crate::dimen::{UVec2, Vec2};
+use crate::glwrapper::{GLFramebuffer, GLTexture};
+use crate::shape::Rectangle;
+use crate::texture_packer::TexturePacker;
 
 /// The data type of the pixels making up the raw image data.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -27,7 +30,18 @@ pub enum ImageDataType
 
     /// Each pixel in the image is represented by four `u8` values: red, green,
     /// blue, and alpha.
-    RGBA
+    RGBA,
+
+    /// Each pixel in the image is represented by four `u8` values: red, green,
+    /// blue, and alpha, where the red, green, and blue values have already
+    /// been multiplied by the alpha value.
+    ///
+    /// Images created from premultiplied data are drawn using premultiplied
+    /// alpha blending, which avoids the dark fringes that can otherwise appear
+    /// around the translucent edges of smoothed or scaled images. See
+    /// [BlendMode](crate::BlendMode) for details of how each blend mode
+    /// treats these images.
+    RGBAPremultiplied
 }
 
 /// Represents a handle for a loaded image.
@@ -38,16 +52,221 @@ pub enum ImageDataType
 pub struct ImageHandle
 {
     pub(crate) size: UVec2,
-    pub(crate) texture: GLTexture
+    pub(crate) texture: GLTexture,
+    pub(crate) data_type: ImageDataType,
+    pub(crate) smoothing_mode: ImageSmoothingMode
 }
 
 impl ImageHandle
 {
-    /// Returns the size of the image in pixels.
+    /// Returns the size of the image in pixels. This is recorded when the
+    /// image is created, so it can be used (for example, by layout code)
+    /// outside of a call to
+    /// [GLRenderer::draw_frame](crate::GLRenderer::draw_frame).
     pub fn size(&self) -> &UVec2
     {
         &self.size
     }
+
+    /// Returns the format of the pixel data stored in this image. Any data
+    /// passed to
+    /// [Graphics2D::update_image_region](crate::Graphics2D::update_image_region)
+    /// must be in this format.
+    ///
+    /// Images loaded from a file always use [ImageDataType::RGBA].
+    pub fn data_type(&self) -> ImageDataType
+    {
+        self.data_type
+    }
+
+    /// Returns `true` if this image was created from data in which the color
+    /// values have already been multiplied by the alpha value. See
+    /// [ImageDataType::RGBAPremultiplied].
+    pub fn is_premultiplied_alpha(&self) -> bool
+    {
+        self.data_type == ImageDataType::RGBAPremultiplied
+    }
+}
+
+/// An offscreen image which can be drawn into using
+/// [Graphics2D::draw_to_target](crate::Graphics2D::draw_to_target), and then
+/// drawn to the screen like any other image using [RenderTarget::image].
+///
+/// Note: this handle can only be used in the graphics context in which it was
+/// created.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct RenderTarget
+{
+    pub(crate) image: ImageHandle,
+    pub(crate) framebuffer: GLFramebuffer
+}
+
+impl RenderTarget
+{
+    /// Returns the size of the render target in pixels.
+    pub fn size(&self) -> &UVec2
+    {
+        self.image.size()
+    }
+
+    /// Returns an [ImageHandle] containing whatever has been drawn into this
+    /// render target. This can be passed to any of the image drawing
+    /// functions, such as
+    /// [Graphics2D::draw_rectangle_image](crate::Graphics2D::draw_rectangle_image).
+    pub fn image(&self) -> &ImageHandle
+    {
+        &self.image
+    }
+}
+
+/// A single large image into which many smaller images can be packed, using
+/// [Graphics2D::add_image_to_atlas](crate::Graphics2D::add_image_to_atlas).
+///
+/// Consecutive drawing operations which use the same texture can be combined
+/// into a single GPU draw call. Drawing many small images from the same
+/// atlas, using [ImageAtlas::image] and the region returned when each image
+/// was added, is therefore much faster than drawing many separate images.
+///
+/// Images are not packed into an atlas automatically, as each [ImageHandle]
+/// owns its texture: functions such as
+/// [Graphics2D::update_image_region](crate::Graphics2D::update_image_region)
+/// and [Graphics2D::set_image_wrap_mode](crate::Graphics2D::set_image_wrap_mode)
+/// would otherwise affect other images, and [TextureWrapMode::Repeat] cannot
+/// be applied to part of a texture. Instead, images which are drawn together
+/// and never modified can be packed into an atlas explicitly.
+///
+/// Note: this handle can only be used in the graphics context in which it was
+/// created.
+#[derive(Debug)]
+pub struct ImageAtlas
+{
+    pub(crate) image: ImageHandle,
+    pub(crate) packer: TexturePacker
+}
+
+impl ImageAtlas
+{
+    /// Returns the size of the atlas in pixels.
+    pub fn size(&self) -> &UVec2
+    {
+        self.image.size()
+    }
+
+    /// Returns an [ImageHandle] containing every image which has been added to
+    /// this atlas. To draw one of these images, pass this handle and the
+    /// region of the image to the `draw_rectangle_image_subset_tinted()`
+    /// function of [Graphics2D](crate::Graphics2D).
+    pub fn image(&self) -> &ImageHandle
+    {
+        &self.image
+    }
+}
+
+/// A list of sprites to be drawn in a single operation, using
+/// [Graphics2D::draw_sprite_batch](crate::Graphics2D::draw_sprite_batch).
+/// This is useful for particle systems and tilemaps, where thousands of
+/// images may be drawn every frame.
+///
+/// Sprites are drawn in the order they were added. Consecutive sprites which
+/// use the same image are combined into a single GPU draw call, so for the
+/// best performance, add sprites which share an image together, or pack the
+/// images into an [ImageAtlas].
+///
+/// The batch can be cleared and reused on the next frame, to avoid
+/// reallocating its storage.
+#[derive(Debug, Clone, Default)]
+pub struct SpriteBatch
+{
+    pub(crate) sprites: Vec<Sprite>
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Sprite
+{
+    pub(crate) image: ImageHandle,
+    pub(crate) corners_clockwise: [Vec2; 4],
+    pub(crate) image_coords_normalized: Rectangle,
+    pub(crate) tint: Color
+}
+
+impl SpriteBatch
+{
+    /// Creates a new, empty `SpriteBatch`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Adds a sprite to the batch.
+    ///
+    /// The sprite is drawn into `dest_rect`, in pixels, and is rotated
+    /// clockwise by `rotation` radians around the center of `dest_rect`.
+    ///
+    /// The coordinates in `image_coords_normalized` should be in the range
+    /// `0.0` to `1.0`, and define the portion of the source image which
+    /// should be drawn. To draw the whole image, use
+    /// `Rectangle::from_tuples((0.0, 0.0), (1.0, 1.0))`.
+    ///
+    /// Each pixel of the image is multiplied by the `tint` color. To draw the
+    /// image unchanged, use [Color::WHITE].
+    pub fn add(
+        &mut self,
+        image: &ImageHandle,
+        dest_rect: impl AsRef<Rectangle>,
+        image_coords_normalized: impl AsRef<Rectangle>,
+        tint: Color,
+        rotation: f32
+    )
+    {
+        let dest_rect = dest_rect.as_ref();
+
+        let mut corners_clockwise = [
+            *dest_rect.top_left(),
+            dest_rect.top_right(),
+            *dest_rect.bottom_right(),
+            dest_rect.bottom_left()
+        ];
+
+        if rotation != 0.0 {
+            let center = (*dest_rect.top_left() + *dest_rect.bottom_right()) / 2.0;
+
+            for corner in corners_clockwise.iter_mut() {
+                *corner = center + (*corner - center).rotate(rotation);
+            }
+        }
+
+        self.sprites.push(Sprite {
+            image: image.clone(),
+            corners_clockwise,
+            image_coords_normalized: image_coords_normalized.as_ref().clone(),
+            tint
+        });
+    }
+
+    /// Removes all sprites from the batch, keeping the allocated storage.
+    #[inline]
+    pub fn clear(&mut self)
+    {
+        self.sprites.clear();
+    }
+
+    /// Returns the number of sprites in the batch.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize
+    {
+        self.sprites.len()
+    }
+
+    /// Returns true if the batch contains no sprites.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool
+    {
+        self.sprites.is_empty()
+    }
 }
 
 /// `ImageSmoothingMode` defines how images are rendered when the pixels of the
@@ -66,7 +285,44 @@ pub enum ImageSmoothingMode
     /// nearest pixels in the source image. This produces a smoother result
     /// than `NearestNeighbor`, but in cases where the image is intended to
     /// be pixel-aligned it may cause unnecessary blurriness.
-    Linear
+    Linear,
+
+    /// As with `Linear`, but additional downscaled copies (mipmaps) of the
+    /// image are generated, and blended between when the image is drawn
+    /// smaller than its original size. This avoids the aliasing and
+    /// shimmering which otherwise occurs when drawing zoomed-out sprite maps
+    /// or minimaps.
+    ///
+    /// The mipmaps increase the GPU memory used by the image by roughly a
+    /// third. They are regenerated after every call to
+    /// [Graphics2D::update_image_region](crate::Graphics2D::update_image_region),
+    /// and after drawing into a render target created using this mode, which
+    /// has an additional performance cost.
+    LinearMipmap
+}
+
+/// `TextureWrapMode` defines how an image is drawn when the image coordinates
+/// fall outside the range `0.0` to `1.0`, for example when passing coordinates
+/// of `(0.0, 0.0)` to `(10.0, 10.0)` to the
+/// `draw_rectangle_image_subset_tinted()` function of
+/// [Graphics2D](crate::Graphics2D).
+///
+/// The wrap mode can be changed using
+/// [Graphics2D::set_image_wrap_mode](crate::Graphics2D::set_image_wrap_mode).
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum TextureWrapMode
+{
+    /// Coordinates outside the image use the color of the nearest pixel on
+    /// the edge of the image. This is the default.
+    ClampToEdge,
+
+    /// The image is repeated, so that a single draw call can tile it across a
+    /// large area, such as a background.
+    Repeat,
+
+    /// The image is repeated, with every second repetition mirrored, so that
+    /// the edges of adjacent tiles line up seamlessly.
+    MirrorRepeat
 }
 
 /// Supported image formats.
@@ -151,4 +407,605 @@ impl RawBitmapData
     {
         self.data
     }
+
+    /// Reduces the size of the data by `factor` in each dimension, by
+    /// averaging each `factor` x `factor` block of pixels. For data in the
+    /// `RGBA` format, the color components are weighted by alpha, so that
+    /// fully transparent pixels don't darken the result.
+    pub(crate) fn downsampled(&self, factor: u32) -> RawBitmapData
+    {
+        if factor <= 1 {
+            return self.clone();
+        }
+
+        let bytes_per_pixel = match self.format {
+            ImageDataType::RGB => 3,
+            ImageDataType::RGBA | ImageDataType::RGBAPremultiplied => 4
+        };
+
+        let weight_by_alpha = self.format == ImageDataType::RGBA;
+
+        let factor_usize = factor as usize;
+        let stride = self.size.x as usize * bytes_per_pixel;
+        let size = UVec2::new(self.size.x / factor, self.size.y / factor);
+
+        let mut data =
+            Vec::with_capacity(size.x as usize * size.y as usize * bytes_per_pixel);
+
+        for y in 0..size.y as usize {
+            for x in 0..size.x as usize {
+                let mut sums = [0_u32; 4];
+                let mut color_weight = 0;
+
+                for sample_y in (y * factor_usize)..((y + 1) * factor_usize) {
+                    for sample_x in (x * factor_usize)..((x + 1) * factor_usize) {
+                        let offset = sample_y * stride + sample_x * bytes_per_pixel;
+                        let pixel = &self.data[offset..offset + bytes_per_pixel];
+
+                        let weight = match weight_by_alpha {
+                            true => pixel[3] as u32,
+                            false => 1
+                        };
+
+                        for (i, sum) in sums.iter_mut().take(bytes_per_pixel).enumerate()
+                        {
+                            *sum += match i {
+                                3 => pixel[i] as u32,
+                                _ => pixel[i] as u32 * weight
+                            };
+                        }
+
+                        color_weight += weight;
+                    }
+                }
+
+                for (i, sum) in sums.iter().take(bytes_per_pixel).enumerate() {
+                    let divisor = match i {
+                        3 => factor * factor,
+                        _ => color_weight
+                    };
+
+                    data.push(match divisor {
+                        0 => 0,
+                        _ => ((sum + divisor / 2) / divisor) as u8
+                    });
+                }
+            }
+        }
+
+        RawBitmapData::new(data, size, self.format)
+    }
+}
+
+/// Options for
+/// [Graphics2D::capture_offscreen](crate::Graphics2D::capture_offscreen).
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct CaptureOptions
+{
+    format: ImageDataType,
+    supersampling: u32
+}
+
+impl CaptureOptions
+{
+    /// Instantiates a new `CaptureOptions`, which captures pixels in the
+    /// specified format, without supersampling.
+    #[inline]
+    #[must_use]
+    pub fn new(format: ImageDataType) -> Self
+    {
+        CaptureOptions {
+            format,
+            supersampling: 1
+        }
+    }
+
+    /// Renders at `factor` times the resolution in each dimension, and then
+    /// downscales the result on the CPU by averaging each `factor` x `factor`
+    /// block of pixels. This smooths the edges of shapes, which are otherwise
+    /// aliased when multisampling isn't available, and makes the result more
+    /// consistent between GPUs. This is useful for visual regression tests.
+    ///
+    /// The default is `1`, which disables supersampling. A factor of zero is
+    /// treated as `1`.
+    #[inline]
+    #[must_use]
+    pub fn with_supersampling(mut self, factor: u32) -> Self
+    {
+        self.supersampling = factor.max(1);
+        self
+    }
+
+    /// The format of the captured pixels.
+    #[inline]
+    #[must_use]
+    pub fn format(&self) -> ImageDataType
+    {
+        self.format
+    }
+
+    /// The supersampling factor. See [CaptureOptions::with_supersampling].
+    #[inline]
+    #[must_use]
+    pub fn supersampling(&self) -> u32
+    {
+        self.supersampling
+    }
+}
+
+/// Options for
+/// [Graphics2D::draw_image_with_options](crate::Graphics2D::draw_image_with_options),
+/// which combine the features of the other image drawing functions.
+///
+/// ```rust,no_run
+/// # use speedy2d::color::Color;
+/// # use speedy2d::image::{DrawImageOptions, ImageHandle};
+/// # use speedy2d::Graphics2D;
+/// # fn draw(graphics: &mut Graphics2D, image: &ImageHandle) {
+/// // Draw the image at double size, facing left, centered on (100, 100)
+/// graphics.draw_image_with_options(
+///     image,
+///     &DrawImageOptions::new((100.0, 100.0))
+///         .with_size(image.size().into_f32() * 2.0)
+///         .with_anchor((0.5, 0.5))
+///         .with_flip_x(true)
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct DrawImageOptions
+{
+    position: Vec2,
+    size: Option<Vec2>,
+    source_rect: Option<Rectangle>,
+    tint: Color,
+    rotation_radians: f32,
+    flip_x: bool,
+    flip_y: bool,
+    anchor: Vec2
+}
+
+impl DrawImageOptions
+{
+    /// Instantiates a new `DrawImageOptions`, which draws the whole image at
+    /// its original size, with its top left corner at the specified position.
+    #[inline]
+    #[must_use]
+    pub fn new(position: impl Into<Vec2>) -> Self
+    {
+        DrawImageOptions {
+            position: position.into(),
+            size: None,
+            source_rect: None,
+            tint: Color::WHITE,
+            rotation_radians: 0.0,
+            flip_x: false,
+            flip_y: false,
+            anchor: Vec2::ZERO
+        }
+    }
+
+    /// Scales the image to the specified size in pixels. By default, the image
+    /// is drawn at the size of the source rectangle.
+    #[inline]
+    #[must_use]
+    pub fn with_size(mut self, size: impl Into<Vec2>) -> Self
+    {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Draws only part of the image, specified in pixels from the top left of
+    /// the image. This is useful for drawing a single sprite from a sprite
+    /// sheet. By default, the whole image is drawn.
+    #[inline]
+    #[must_use]
+    pub fn with_source_rect(mut self, source_rect: impl AsRef<Rectangle>) -> Self
+    {
+        self.source_rect = Some(source_rect.as_ref().clone());
+        self
+    }
+
+    /// Tints the image, by multiplying each color component in the image pixel
+    /// by the corresponding color component in `tint`. The default is
+    /// [Color::WHITE], which leaves the image unchanged.
+    #[inline]
+    #[must_use]
+    pub fn with_tint(mut self, tint: Color) -> Self
+    {
+        self.tint = tint;
+        self
+    }
+
+    /// Rotates the image around the anchor point (see
+    /// [DrawImageOptions::with_anchor]). As the Y axis points downwards,
+    /// positive angles result in a clockwise rotation on the screen.
+    #[inline]
+    #[must_use]
+    pub fn with_rotation(mut self, rotation_radians: f32) -> Self
+    {
+        self.rotation_radians = rotation_radians;
+        self
+    }
+
+    /// Mirrors the image horizontally, so that the left edge of the image is
+    /// drawn on the right.
+    #[inline]
+    #[must_use]
+    pub fn with_flip_x(mut self, flip_x: bool) -> Self
+    {
+        self.flip_x = flip_x;
+        self
+    }
+
+    /// Mirrors the image vertically, so that the top edge of the image is
+    /// drawn at the bottom.
+    #[inline]
+    #[must_use]
+    pub fn with_flip_y(mut self, flip_y: bool) -> Self
+    {
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Sets the point within the image which is placed at the position, and
+    /// around which the image is rotated. This is relative to the size of the
+    /// image, so `(0.0, 0.0)` is the top left (the default), and `(0.5, 0.5)`
+    /// is the center.
+    #[inline]
+    #[must_use]
+    pub fn with_anchor(mut self, anchor: impl Into<Vec2>) -> Self
+    {
+        self.anchor = anchor.into();
+        self
+    }
+
+    /// The tint color. See [DrawImageOptions::with_tint].
+    #[inline]
+    #[must_use]
+    pub fn tint(&self) -> Color
+    {
+        self.tint
+    }
+
+    /// Returns the corners of the quad to draw, and the corresponding
+    /// normalized image coordinates, in clockwise order starting from the
+    /// (unrotated) top left.
+    pub(crate) fn vertices(&self, image_size: UVec2) -> ([Vec2; 4], [Vec2; 4])
+    {
+        let image_size = image_size.into_f32();
+
+        let source_rect = match &self.source_rect {
+            Some(source_rect) => source_rect.clone(),
+            None => Rectangle::new(Vec2::ZERO, image_size)
+        };
+
+        let size = self.size.unwrap_or_else(|| source_rect.size());
+        let top_left = Vec2::new(-self.anchor.x * size.x, -self.anchor.y * size.y);
+
+        let place = |offset: Vec2| {
+            self.position + (top_left + offset).rotate(self.rotation_radians)
+        };
+
+        let positions = [
+            place(Vec2::ZERO),
+            place(Vec2::new(size.x, 0.0)),
+            place(size),
+            place(Vec2::new(0.0, size.y))
+        ];
+
+        let normalize_x = |x: f32| x / image_size.x.max(1.0);
+        let normalize_y = |y: f32| y / image_size.y.max(1.0);
+
+        let (mut left, mut right) = (
+            normalize_x(source_rect.top_left().x),
+            normalize_x(source_rect.bottom_right().x)
+        );
+
+        let (mut top, mut bottom) = (
+            normalize_y(source_rect.top_left().y),
+            normalize_y(source_rect.bottom_right().y)
+        );
+
+        if self.flip_x {
+            std::mem::swap(&mut left, &mut right);
+        }
+
+        if self.flip_y {
+            std::mem::swap(&mut top, &mut bottom);
+        }
+
+        let image_coords = [
+            Vec2::new(left, top),
+            Vec2::new(right, top),
+            Vec2::new(right, bottom),
+            Vec2::new(left, bottom)
+        ];
+
+        (positions, image_coords)
+    }
+}
+
+/// Makes pixels of a specific color transparent when creating an image. This
+/// is useful for sprite sheets which use a background color (often magenta)
+/// rather than an alpha channel to indicate transparency.
+///
+/// Color keys are applied using the `create_image_*_with_color_key()`
+/// functions on [Graphics2D](crate::Graphics2D) and
+/// [GLRenderer](crate::GLRenderer).
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColorKey
+{
+    color: Color,
+    tolerance: f32
+}
+
+impl ColorKey
+{
+    /// Instantiates a new `ColorKey`, which makes pixels transparent if they
+    /// exactly match the red, green, and blue components of `color`. The alpha
+    /// component of `color` is ignored.
+    #[inline]
+    #[must_use]
+    pub fn new(color: Color) -> Self
+    {
+        ColorKey {
+            color,
+            tolerance: 0.0
+        }
+    }
+
+    /// Also makes pixels transparent if each of their red, green, and blue
+    /// components is within `tolerance` of the key color, where `1.0` is the
+    /// full range of each component. This is useful for images which have
+    /// been compressed lossily. The default is `0.0`, which requires an exact
+    /// match.
+    #[inline]
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self
+    {
+        self.tolerance = tolerance.max(0.0);
+        self
+    }
+
+    /// The key color. See [ColorKey::new].
+    #[inline]
+    #[must_use]
+    pub fn color(&self) -> Color
+    {
+        self.color
+    }
+
+    /// The tolerance. See [ColorKey::with_tolerance].
+    #[inline]
+    #[must_use]
+    pub fn tolerance(&self) -> f32
+    {
+        self.tolerance
+    }
+
+    /// Returns a copy of the pixel data with the alpha of each matching pixel
+    /// set to zero. As transparency requires an alpha channel, `RGB` data is
+    /// converted to `RGBA`.
+    pub(crate) fn apply(
+        &self,
+        data_type: ImageDataType,
+        data: &[u8]
+    ) -> (ImageDataType, Vec<u8>)
+    {
+        let to_int = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as i32;
+
+        let key = [
+            to_int(self.color.r()),
+            to_int(self.color.g()),
+            to_int(self.color.b())
+        ];
+
+        let tolerance = (self.tolerance.min(1.0) * 255.0).round() as i32;
+
+        let matches = |pixel: &[u8]| {
+            pixel
+                .iter()
+                .zip(key.iter())
+                .all(|(value, key)| (*value as i32 - key).abs() <= tolerance)
+        };
+
+        match data_type {
+            ImageDataType::RGB => {
+                let mut result = Vec::with_capacity(data.len() / 3 * 4);
+
+                for pixel in data.chunks_exact(3) {
+                    result.extend_from_slice(pixel);
+                    result.push(if matches(pixel) { 0 } else { 255 });
+                }
+
+                (ImageDataType::RGBA, result)
+            }
+
+            ImageDataType::RGBA | ImageDataType::RGBAPremultiplied => {
+                let mut result = data.to_vec();
+
+                for pixel in result.chunks_exact_mut(4) {
+                    if matches(&pixel[..3]) {
+                        if data_type == ImageDataType::RGBAPremultiplied {
+                            pixel.fill(0);
+                        } else {
+                            pixel[3] = 0;
+                        }
+                    }
+                }
+
+                (data_type, result)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    #[test]
+    fn test_downsample_rgb()
+    {
+        let data = RawBitmapData::new(
+            vec![
+                0, 0, 0, 255, 255, 255, 10, 20, 30, 10, 20, 30, //
+                255, 255, 255, 255, 255, 255, 10, 20, 30, 10, 20, 30,
+            ],
+            (4, 2),
+            ImageDataType::RGB
+        );
+
+        let result = data.downsampled(2);
+
+        assert_eq!(UVec2::new(2, 1), result.size());
+        assert_eq!(&vec![191, 191, 191, 10, 20, 30], result.data());
+    }
+
+    #[test]
+    fn test_downsample_rgba()
+    {
+        let data = RawBitmapData::new(
+            vec![
+                255, 0, 0, 255, 0, 0, 0, 0, //
+                0, 0, 0, 0, 255, 0, 0, 255,
+            ],
+            (2, 2),
+            ImageDataType::RGBA
+        );
+
+        assert_eq!(&vec![255, 0, 0, 128], data.downsampled(2).data());
+
+        let premultiplied = RawBitmapData::new(
+            data.data().clone(),
+            (2, 2),
+            ImageDataType::RGBAPremultiplied
+        );
+
+        assert_eq!(&vec![128, 0, 0, 128], premultiplied.downsampled(2).data());
+        assert_eq!(data.data(), data.downsampled(1).data());
+    }
+
+    fn assert_vertices_eq(expected: [(f32, f32); 4], actual: [Vec2; 4])
+    {
+        for (expected, actual) in expected.iter().zip(actual.iter()) {
+            assert!(
+                (Vec2::from(*expected) - *actual).magnitude() < 0.0001,
+                "Expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn test_draw_image_options_vertices()
+    {
+        let image_size = UVec2::new(40, 20);
+
+        let (positions, image_coords) =
+            DrawImageOptions::new((5.0, 6.0)).vertices(image_size);
+
+        assert_vertices_eq(
+            [(5.0, 6.0), (45.0, 6.0), (45.0, 26.0), (5.0, 26.0)],
+            positions
+        );
+        assert_vertices_eq(
+            [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+            image_coords
+        );
+
+        // Source rect determines the default size
+        let (positions, image_coords) = DrawImageOptions::new((0.0, 0.0))
+            .with_source_rect(Rectangle::from_tuples((10.0, 0.0), (20.0, 10.0)))
+            .vertices(image_size);
+
+        assert_vertices_eq(
+            [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+            positions
+        );
+        assert_vertices_eq(
+            [(0.25, 0.0), (0.5, 0.0), (0.5, 0.5), (0.25, 0.5)],
+            image_coords
+        );
+
+        // Anchored at the center, scaled, and rotated by 90 degrees
+        let (positions, _) = DrawImageOptions::new((100.0, 100.0))
+            .with_size((20.0, 10.0))
+            .with_anchor((0.5, 0.5))
+            .with_rotation(std::f32::consts::FRAC_PI_2)
+            .vertices(image_size);
+
+        assert_vertices_eq(
+            [(105.0, 90.0), (105.0, 110.0), (95.0, 110.0), (95.0, 90.0)],
+            positions
+        );
+    }
+
+    #[test]
+    fn test_draw_image_options_flip()
+    {
+        let image_size = UVec2::new(40, 20);
+
+        let (positions, image_coords) = DrawImageOptions::new((0.0, 0.0))
+            .with_flip_x(true)
+            .vertices(image_size);
+
+        assert_vertices_eq(
+            [(0.0, 0.0), (40.0, 0.0), (40.0, 20.0), (0.0, 20.0)],
+            positions
+        );
+        assert_vertices_eq(
+            [(1.0, 0.0), (0.0, 0.0), (0.0, 1.0), (1.0, 1.0)],
+            image_coords
+        );
+
+        let (_, image_coords) = DrawImageOptions::new((0.0, 0.0))
+            .with_flip_x(true)
+            .with_flip_y(true)
+            .vertices(image_size);
+
+        assert_vertices_eq(
+            [(1.0, 1.0), (0.0, 1.0), (0.0, 0.0), (1.0, 0.0)],
+            image_coords
+        );
+    }
+
+    #[test]
+    fn test_color_key()
+    {
+        let rgb = [255, 0, 255, 10, 20, 30, 250, 5, 250];
+
+        let (data_type, data) =
+            ColorKey::new(Color::MAGENTA).apply(ImageDataType::RGB, &rgb);
+
+        assert_eq!(ImageDataType::RGBA, data_type);
+        assert_eq!(
+            vec![255, 0, 255, 0, 10, 20, 30, 255, 250, 5, 250, 255],
+            data
+        );
+
+        // Within a tolerance of 6/255
+        let (_, data) = ColorKey::new(Color::MAGENTA)
+            .with_tolerance(0.025)
+            .apply(ImageDataType::RGB, &rgb);
+
+        assert_eq!(vec![255, 0, 255, 0, 10, 20, 30, 255, 250, 5, 250, 0], data);
+
+        let rgba = [255, 0, 255, 255, 10, 20, 30, 128];
+
+        let (data_type, data) =
+            ColorKey::new(Color::MAGENTA).apply(ImageDataType::RGBA, &rgba);
+
+        assert_eq!(ImageDataType::RGBA, data_type);
+        assert_eq!(vec![255, 0, 255, 0, 10, 20, 30, 128], data);
+
+        let (data_type, data) =
+            ColorKey::new(Color::MAGENTA).apply(ImageDataType::RGBAPremultiplied, &rgba);
+
+        assert_eq!(ImageDataType::RGBAPremultiplied, data_type);
+        assert_eq!(vec![0, 0, 0, 0, 10, 20, 30, 128], data);
+    }
 }
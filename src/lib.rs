@@ -32,6 +32,15 @@
 //! context. If you'd like to handle this yourself, and use Speedy2D only for
 //! rendering, you can disable the `windowing` feature.
 //!
+//! To render without a window, for example to check the output of drawing
+//! code in automated tests, enable the `headless` feature and use
+//! `headless::HeadlessRenderer`.
+//!
+//! Gamepad input is available on Windows, Mac, and Linux by enabling the
+//! `gamepad` feature, and implementing the gamepad callbacks in
+//! `window::WindowHandler`. The feature has no effect when targeting
+//! WebAssembly.
+//!
 //! # Useful Links
 //!
 //! * [Source repository](https://github.com/QuantumBadger/Speedy2D)
@@ -89,6 +98,8 @@
 //! fn on_mouse_move()
 //! fn on_mouse_button_down()
 //! fn on_mouse_button_up()
+//! fn on_mouse_wheel_scroll()
+//! fn on_touch()
 //! fn on_key_down()
 //! fn on_key_up()
 //! fn on_keyboard_char()
@@ -304,20 +315,43 @@ use std::rc::Rc;
 use {
     crate::image::ImageFileFormat,
     std::io::{BufRead, Seek},
-    std::path::Path
+    std::path::Path,
+    std::time::Duration
 };
 
-use crate::color::Color;
-use crate::dimen::{UVec2, Vec2};
+use crate::color::{Color, Gradient, GradientDirection};
+use crate::dimen::{IVec2, Matrix3, UVec2, Vec2};
 use crate::error::{BacktraceError, ErrorMessage};
-use crate::font::FormattedTextBlock;
+use crate::font::{Font, FormattedTextBlock};
 use crate::glbackend::GLBackend;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::glbackend::GLBackendGlow;
 use crate::glwrapper::{GLContextManager, GLVersion};
-use crate::image::{ImageDataType, ImageHandle, ImageSmoothingMode, RawBitmapData};
+use crate::image::{
+    CaptureOptions,
+    ColorKey,
+    DrawImageOptions,
+    ImageAtlas,
+    ImageDataType,
+    ImageHandle,
+    ImageSmoothingMode,
+    RawBitmapData,
+    RenderTarget,
+    SpriteBatch,
+    TextureWrapMode
+};
 use crate::renderer2d::Renderer2D;
-use crate::shape::{Polygon, Rect, Rectangle, RoundedRectangle};
+use crate::shader::Shader;
+use crate::shape::{
+    dash_segments,
+    flatten_cubic_bezier,
+    flatten_quadratic_bezier,
+    Polygon,
+    Rect,
+    Rectangle,
+    RoundedRectangle,
+    BEZIER_TOLERANCE
+};
 #[cfg(target_arch = "wasm32")]
 use crate::web::WebCanvasElement;
 #[cfg(any(doc, doctest, feature = "windowing"))]
@@ -350,7 +384,7 @@ pub mod shape;
 /// Components for loading fonts and laying out text.
 pub mod font;
 
-/// Types representing sizes and positions.
+/// Types representing sizes, positions, and transforms.
 pub mod dimen;
 
 /// Utilities and traits for numeric values.
@@ -362,6 +396,9 @@ pub mod error;
 /// Types relating to images.
 pub mod image;
 
+/// Custom shaders for advanced rendering effects.
+pub mod shader;
+
 /// Utilities for accessing the system clock on all platforms.
 pub mod time;
 
@@ -369,6 +406,10 @@ pub mod time;
 #[cfg(any(doc, doctest, feature = "windowing"))]
 pub mod window;
 
+/// Offscreen rendering without a window, for example in automated tests.
+#[cfg(all(feature = "headless", not(target_arch = "wasm32")))]
+pub mod headless;
+
 #[cfg(all(
     feature = "windowing",
     not(target_arch = "wasm32"),
@@ -450,7 +491,7 @@ impl GLRenderer
     /// loader function takes the name of an OpenGL function, and returns the
     /// associated function pointer. `viewport_size_pixels` should be set to
     /// the initial viewport size, however this can be changed later using
-    /// [GLRenderer:: set_viewport_size_pixels()].
+    /// [GLRenderer::set_viewport_size_pixels()].
     ///
     /// Note: This function must not be called if you are letting Speedy2D
     /// create a window for you.
@@ -486,7 +527,7 @@ impl GLRenderer
     ///
     /// The parameter `viewport_size_pixels` should be set to
     /// the initial canvas size, however this can be changed later using
-    /// [GLRenderer:: set_viewport_size_pixels()].
+    /// [GLRenderer::set_viewport_size_pixels()].
     #[cfg(any(doc, doctest, target_arch = "wasm32"))]
     pub fn new_for_web_canvas_by_id<V, S>(
         viewport_size_pixels: V,
@@ -537,6 +578,31 @@ impl GLRenderer
             .set_viewport_size_pixels(viewport_size_pixels)
     }
 
+    /// Sets the ratio of physical pixels to logical pixels (also known as the
+    /// DPI scale) of the window being rendered to. This is made available to
+    /// drawing code using [Graphics2D::get_scale_factor], and doesn't affect
+    /// rendering itself.
+    ///
+    /// When Speedy2D creates the window, this is set automatically. If you are
+    /// managing the GL context yourself, call this when the renderer is
+    /// created, and whenever the scale factor changes. The default is `1.0`.
+    pub fn set_scale_factor(&mut self, scale_factor: f64)
+    {
+        self.renderer.renderer.set_scale_factor(scale_factor)
+    }
+
+    /// Discards all cached text glyphs, along with the textures containing
+    /// them. Glyphs will be rendered again the next time they are drawn.
+    ///
+    /// When Speedy2D creates the window, this is done automatically when the
+    /// application resumes after being suspended (see
+    /// [WindowHandler::on_resume]). If you are managing the GL context
+    /// yourself, call this after the context's textures have been lost.
+    pub fn clear_glyph_cache(&mut self)
+    {
+        self.renderer.renderer.clear_glyph_cache()
+    }
+
     /// Creates a new [ImageHandle] from the specified raw pixel data.
     ///
     /// The data provided in the `data` parameter must be in the format
@@ -556,6 +622,111 @@ impl GLRenderer
             .create_image_from_raw_pixels(data_type, smoothing_mode, size, data)
     }
 
+    /// Creates a new [ImageHandle] from the specified raw pixel data, making
+    /// the pixels which match the [ColorKey] transparent. See
+    /// [Graphics2D::create_image_from_raw_pixels_with_color_key] for details.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    pub fn create_image_from_raw_pixels_with_color_key(
+        &mut self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: UVec2,
+        data: &[u8],
+        color_key: &ColorKey
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_from_raw_pixels_with_color_key(
+            data_type,
+            smoothing_mode,
+            size,
+            data,
+            color_key
+        )
+    }
+
+    /// Creates a new, empty [ImageAtlas] of the specified size. See
+    /// [Graphics2D::create_image_atlas] for details.
+    ///
+    /// The returned [ImageAtlas] is valid only for the current graphics
+    /// context.
+    pub fn create_image_atlas(
+        &mut self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: UVec2
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .create_image_atlas(data_type, smoothing_mode, size)
+    }
+
+    /// Packs an image into free space in the specified [ImageAtlas]. See
+    /// [Graphics2D::add_image_to_atlas] for details.
+    pub fn add_image_to_atlas(
+        &mut self,
+        atlas: &mut ImageAtlas,
+        size: UVec2,
+        data: &[u8]
+    ) -> Result<Rectangle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.add_image_to_atlas(atlas, size, data)
+    }
+
+    /// Returns the number of GPU draw calls made while drawing the most
+    /// recent frame using [GLRenderer::draw_frame].
+    ///
+    /// Consecutive drawing operations are combined into a single draw call
+    /// where possible. A new draw call is needed whenever the texture
+    /// changes, so drawing many small images from an [ImageAtlas] results in
+    /// fewer draw calls than drawing the same number of separate images.
+    #[inline]
+    #[must_use]
+    pub fn last_frame_draw_call_count(&self) -> usize
+    {
+        self.renderer.renderer.last_frame_draw_call_count()
+    }
+
+    /// Enables or disables sRGB-correct rendering. When enabled, the
+    /// framebuffer is treated as sRGB, and blending is performed in linear
+    /// space. Colors are still specified in sRGB as usual, and are converted
+    /// to linear space automatically.
+    ///
+    /// Blending in linear space gives more physically accurate results.
+    /// Gradients and partially transparent shapes blend more smoothly, for
+    /// example the midpoint of a gradient from red to green is a brighter
+    /// yellow, rather than a muddy brown. However, the results will look
+    /// different to other software which blends in sRGB space.
+    ///
+    /// This should only be enabled if the GL context's framebuffer is
+    /// sRGB-capable, otherwise colors will appear too dark. When creating a
+    /// window, use the `with_srgb_framebuffer()` function of
+    /// [WindowCreationOptions](crate::window::WindowCreationOptions) instead.
+    ///
+    /// Images created while this is enabled are stored in sRGB textures, so
+    /// that they are also converted to linear space when drawn. This should
+    /// therefore be set before creating any images.
+    ///
+    /// This is not supported in WebGL, and an error is returned if enabling
+    /// it is attempted.
+    pub fn set_srgb_framebuffer_enabled(
+        &mut self,
+        enabled: bool
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.renderer.renderer.set_srgb_framebuffer_enabled(enabled)
+    }
+
+    /// Returns true if sRGB-correct rendering is enabled. See
+    /// [GLRenderer::set_srgb_framebuffer_enabled].
+    #[inline]
+    #[must_use]
+    pub fn is_srgb_framebuffer_enabled(&self) -> bool
+    {
+        self.renderer.renderer.is_srgb_framebuffer_enabled()
+    }
+
     /// Loads an image from the specified file path.
     ///
     /// If no `data_type` is provided, an attempt will be made to guess the file
@@ -621,6 +792,66 @@ impl GLRenderer
             .create_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
     }
 
+    /// Loads an image from the provided encoded image file data, making the
+    /// pixels which match the [ColorKey] transparent. See
+    /// [Graphics2D::create_image_from_file_bytes_with_color_key] for details.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_bytes_with_color_key<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R,
+        color_key: &ColorKey
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_from_file_bytes_with_color_key(
+            data_type,
+            smoothing_mode,
+            file_bytes,
+            color_key
+        )
+    }
+
+    /// Loads each frame of an animated image from the provided encoded image
+    /// file data. See [Graphics2D::create_image_sequence_from_file_bytes] for
+    /// details.
+    ///
+    /// The returned [ImageHandle] objects are valid only for the current
+    /// graphics context.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_sequence_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R
+    ) -> Result<Vec<(ImageHandle, Duration)>, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_sequence_from_file_bytes(
+            data_type,
+            smoothing_mode,
+            file_bytes
+        )
+    }
+
+    /// Limits the number of rendered glyphs which are kept in the text cache.
+    /// When the limit is exceeded, the least recently used glyphs are evicted,
+    /// and will be rendered again if they are needed later. This is useful for
+    /// applications which display text in many different sizes, or with a
+    /// large number of unique characters.
+    ///
+    /// Glyphs drawn in the current frame are never evicted, so the cache may
+    /// temporarily exceed this limit.
+    ///
+    /// By default, the number of glyphs in the cache is not limited.
+    #[inline]
+    pub fn set_glyph_cache_limit(&mut self, max_glyphs: usize)
+    {
+        self.renderer.renderer.set_glyph_cache_limit(max_glyphs);
+    }
+
     /// Starts the process of drawing a frame. A `Graphics2D` object will be
     /// provided to the callback. When the callback returns, the internal
     /// render queue will be flushed.
@@ -629,11 +860,47 @@ impl GLRenderer
     /// window context buffers if necessary.
     #[inline]
     pub fn draw_frame<F: FnOnce(&mut Graphics2D) -> R, R>(&mut self, callback: F) -> R
+    {
+        let result = callback(self.begin_frame());
+        self.end_frame();
+        result
+    }
+
+    /// Starts the process of drawing a frame, and returns the `Graphics2D`
+    /// object used for drawing. The clip area, blend mode, shader, depth, and
+    /// transform are reset to their defaults.
+    ///
+    /// Once drawing is complete, [GLRenderer::end_frame] must be called to
+    /// flush the internal render queue. This is equivalent to using
+    /// [GLRenderer::draw_frame], but allows the drawing to be split across
+    /// several calls, or to be performed in multiple passes, with other logic
+    /// in between.
+    ///
+    /// Note: the safety requirements of [GLRenderer::new_for_gl_context]
+    /// still apply. Drawing operations are queued, and may not be sent to
+    /// the GPU until [GLRenderer::end_frame] is called, so the GL context
+    /// must not be modified between the two calls. Any other changes to the
+    /// GL context must also leave its state exactly as it was found before
+    /// the next call to `begin_frame()`.
+    ///
+    /// If calling this method, you are responsible for swapping the window
+    /// context buffers if necessary.
+    pub fn begin_frame(&mut self) -> &mut Graphics2D
     {
         self.renderer.set_clip(None);
-        let result = callback(&mut self.renderer);
+        self.renderer.set_blend_mode(BlendMode::default());
+        self.renderer.set_shader(None);
+        self.renderer.set_depth(None);
+        self.renderer.renderer.reset_transform();
+        &mut self.renderer
+    }
+
+    /// Finishes drawing a frame started using [GLRenderer::begin_frame],
+    /// flushing the internal render queue so that all drawing operations are
+    /// sent to the GPU.
+    pub fn end_frame(&mut self)
+    {
         self.renderer.renderer.finish_frame();
-        result
     }
 }
 
@@ -645,6 +912,61 @@ impl Drop for GLRenderer
     }
 }
 
+/// Determines how pixels being drawn are combined with the existing contents of
+/// the framebuffer.
+///
+/// Images created using [ImageDataType::RGBAPremultiplied] are drawn using the
+/// premultiplied-alpha equivalent of the [BlendMode::Normal] and
+/// [BlendMode::Additive] modes, with any tint color premultiplied to match.
+/// The [BlendMode::Multiply] and [BlendMode::None] modes ignore the alpha
+/// value, and so treat premultiplied images in the same way as any other
+/// image.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BlendMode
+{
+    /// Standard alpha blending, where the new pixels are drawn over the
+    /// existing ones according to their alpha value. This is the default.
+    ///
+    /// This uses the blend factors `(SRC_ALPHA, ONE_MINUS_SRC_ALPHA)`, or
+    /// `(ONE, ONE_MINUS_SRC_ALPHA)` for premultiplied images.
+    #[default]
+    Normal,
+    /// The new pixels (multiplied by their alpha value) are added to the
+    /// existing ones. This is useful for particle, glow, and lighting effects.
+    ///
+    /// This uses the blend factors `(SRC_ALPHA, ONE)`, or `(ONE, ONE)` for
+    /// premultiplied images.
+    Additive,
+    /// The existing pixels are multiplied by the new ones, darkening the
+    /// framebuffer. The alpha value of the new pixels is ignored, other than
+    /// fully transparent pixels, which are not drawn.
+    Multiply,
+    /// The new pixels replace the existing ones, including their alpha value.
+    /// Fully transparent pixels also replace the existing ones, so this mode
+    /// can be used to clear part of a render target.
+    ///
+    /// As a result, text and images with transparent areas clear their whole
+    /// bounding box. This includes the space around each text glyph. Circles
+    /// are an exception: only the pixels inside the circle are drawn.
+    None
+}
+
+/// Determines how the segments of a polyline are connected. See
+/// [Graphics2D::draw_polyline_with_join].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LineJoin
+{
+    /// The outer edges of the segments are extended until they meet, forming
+    /// a sharp corner. Very sharp corners are drawn using a bevel join
+    /// instead, to avoid long spikes. This is the default.
+    #[default]
+    Miter,
+    /// The outer corners of the segments are connected with a straight edge.
+    Bevel,
+    /// The segments are connected with a circular arc.
+    Round
+}
+
 /// A `Graphics2D` object allows you to draw shapes, images, and text to the
 /// screen.
 ///
@@ -682,6 +1004,74 @@ impl Graphics2D
         )
     }
 
+    /// Creates a new [ImageHandle] from the specified raw pixel data, in the
+    /// same way as [Graphics2D::create_image_from_raw_pixels], but makes the
+    /// pixels which match the [ColorKey] transparent.
+    ///
+    /// As transparency requires an alpha channel, `RGB` data is converted to
+    /// `RGBA` before it is uploaded.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    pub fn create_image_from_raw_pixels_with_color_key<S: Into<UVec2>>(
+        &mut self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: S,
+        data: &[u8],
+        color_key: &ColorKey
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let (data_type, data) = color_key.apply(data_type, data);
+
+        self.renderer.create_image_from_raw_pixels(
+            data_type,
+            smoothing_mode,
+            size.into(),
+            &data
+        )
+    }
+
+    /// Replaces the pixels in a region of an existing image with the
+    /// specified raw pixel data. This is far cheaper than creating a new
+    /// image, and is suitable for content which changes every frame, such as
+    /// a software-rendered canvas or a video.
+    ///
+    /// The region starts at `offset` (in pixels from the top left of the
+    /// image), and has the specified `size`. The data provided in the `data`
+    /// parameter must be in the format returned by [ImageHandle::data_type],
+    /// and must contain exactly `size.x * size.y` pixels.
+    ///
+    /// An error is returned if the region extends outside the image, or if
+    /// the length of `data` is incorrect.
+    pub fn update_image_region<O: Into<UVec2>, S: Into<UVec2>>(
+        &mut self,
+        image: &ImageHandle,
+        offset: O,
+        size: S,
+        data: &[u8]
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .update_image_region(image, offset.into(), size.into(), data)
+    }
+
+    /// Sets how the specified image is drawn when the image coordinates fall
+    /// outside the range `0.0` to `1.0`. By default, images use
+    /// [TextureWrapMode::ClampToEdge].
+    ///
+    /// For example, to tile a background image ten times in each direction
+    /// using a single draw call, set the wrap mode to
+    /// [TextureWrapMode::Repeat], and pass image coordinates of `(0.0, 0.0)`
+    /// to `(10.0, 10.0)` to
+    /// [Graphics2D::draw_rectangle_image_subset_tinted].
+    ///
+    /// The wrap mode is shared by all clones of the [ImageHandle].
+    pub fn set_image_wrap_mode(&mut self, image: &ImageHandle, wrap_mode: TextureWrapMode)
+    {
+        self.renderer.set_image_wrap_mode(image, wrap_mode)
+    }
+
     /// Loads an image from the specified file path.
     ///
     /// If no `data_type` is provided, an attempt will be made to guess the file
@@ -749,6 +1139,208 @@ impl Graphics2D
             .create_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
     }
 
+    /// Loads an image from the provided encoded image file data, in the same
+    /// way as [Graphics2D::create_image_from_file_bytes], but makes the pixels
+    /// which match the [ColorKey] transparent.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_bytes_with_color_key<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R,
+        color_key: &ColorKey
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_from_file_bytes_with_color_key(
+            data_type,
+            smoothing_mode,
+            file_bytes,
+            color_key
+        )
+    }
+
+    /// Loads each frame of an animated image from the provided encoded image
+    /// file data, returning the frames in order, along with the length of
+    /// time for which each frame should be displayed.
+    ///
+    /// Animated GIF and APNG files are supported. Any other image, including a
+    /// non-animated GIF or PNG, is returned as a single frame with a delay of
+    /// zero.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format. As with [Graphics2D::create_image_from_file_bytes], the data
+    /// source must implement `std::io::BufRead` and `std::io::Seek`.
+    ///
+    /// The returned [ImageHandle] objects are valid only for the current
+    /// graphics context.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_sequence_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R
+    ) -> Result<Vec<(ImageHandle, Duration)>, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_sequence_from_file_bytes(
+            data_type,
+            smoothing_mode,
+            file_bytes
+        )
+    }
+
+    /// Loads a font from the specified TrueType or OpenType file data. This
+    /// is equivalent to calling [Font::new].
+    ///
+    /// If the data cannot be loaded, the returned error describes the problem,
+    /// for example if the data is not a font file, or if the font is malformed.
+    /// Its cause is a [font::FontLoadError], which gives the type of error.
+    ///
+    /// Unlike [ImageHandle], the returned [Font] is not tied to the current
+    /// graphics context.
+    #[inline]
+    pub fn create_font_from_bytes(
+        &self,
+        bytes: &[u8]
+    ) -> Result<Font, BacktraceError<ErrorMessage>>
+    {
+        Font::new(bytes)
+    }
+
+    /// Creates a new offscreen [RenderTarget] of the specified size. The
+    /// target is initially fully transparent.
+    ///
+    /// Use [Graphics2D::draw_to_target] to draw into the target, and
+    /// [RenderTarget::image] to draw its contents using any of the image
+    /// drawing functions. The contents of the target persist between frames,
+    /// so it can be used to cache expensive drawing operations.
+    ///
+    /// The returned [RenderTarget] is valid only for the current graphics
+    /// context.
+    pub fn create_render_target<S: Into<UVec2>>(
+        &mut self,
+        smoothing_mode: ImageSmoothingMode,
+        size: S
+    ) -> Result<RenderTarget, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_render_target(smoothing_mode, size)
+    }
+
+    /// Creates a new, empty [ImageAtlas] of the specified size, into which
+    /// smaller images can be packed using [Graphics2D::add_image_to_atlas].
+    ///
+    /// All images added to the atlas must be in the format specified by
+    /// `data_type`, and will be drawn using the specified `smoothing_mode`.
+    ///
+    /// The returned [ImageAtlas] is valid only for the current graphics
+    /// context.
+    pub fn create_image_atlas<S: Into<UVec2>>(
+        &mut self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: S
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .create_image_atlas(data_type, smoothing_mode, size)
+    }
+
+    /// Packs an image into free space in the specified [ImageAtlas], and
+    /// returns the region of the atlas containing the image. The data
+    /// provided in the `data` parameter must be in the format used by the
+    /// atlas.
+    ///
+    /// The returned region is normalized to the range `0.0` to `1.0`, so it
+    /// can be passed directly to
+    /// [Graphics2D::draw_rectangle_image_subset_tinted] along with
+    /// [ImageAtlas::image]. Drawing many images from the same atlas in this
+    /// way uses far fewer GPU draw calls than drawing separate images.
+    ///
+    /// Images created using [Graphics2D::create_image_from_raw_pixels] are
+    /// never added to an atlas automatically, so that they can be updated and
+    /// repeated independently. See [ImageAtlas] for details.
+    ///
+    /// A one-pixel transparent border is left around each image. When using
+    /// [ImageSmoothingMode::Linear], this border may be blended into the
+    /// edges of the image.
+    ///
+    /// Returns an error if there is not enough free space in the atlas.
+    pub fn add_image_to_atlas<S: Into<UVec2>>(
+        &mut self,
+        atlas: &mut ImageAtlas,
+        size: S,
+        data: &[u8]
+    ) -> Result<Rectangle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.add_image_to_atlas(atlas, size.into(), data)
+    }
+
+    /// Runs the provided callback, with all drawing operations redirected to
+    /// the specified [RenderTarget]. Once the callback returns, drawing
+    /// continues on the previous target.
+    ///
+    /// Inside the callback, coordinates are relative to the top left of the
+    /// render target, and the transform stack and clip area start out empty.
+    /// The transform stack and clip area are restored afterwards.
+    ///
+    /// Note: the target's own image must not be drawn inside the callback.
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::GLRenderer;
+    /// # use speedy2d::color::Color;
+    /// # use speedy2d::image::ImageSmoothingMode;
+    /// # use speedy2d::shape::Rectangle;
+    /// # let mut renderer = unsafe {
+    /// #     GLRenderer::new_for_gl_context((640, 480), |fn_name| {
+    /// #         std::ptr::null() as *const _
+    /// #     })
+    /// # }.unwrap();
+    /// # renderer.draw_frame(|graphics| {
+    /// let target = graphics
+    ///     .create_render_target(ImageSmoothingMode::Linear, (200, 100))
+    ///     .unwrap();
+    ///
+    /// graphics.draw_to_target(&target, |graphics| {
+    ///     graphics.clear_screen(Color::BLUE);
+    ///     graphics.draw_circle((100.0, 50.0), 40.0, Color::YELLOW);
+    /// });
+    ///
+    /// graphics.draw_rectangle_image(
+    ///     Rectangle::from_tuples((50.0, 50.0), (250.0, 150.0)),
+    ///     target.image()
+    /// );
+    /// # });
+    /// ```
+    pub fn draw_to_target<F: FnOnce(&mut Graphics2D) -> R, R>(
+        &mut self,
+        target: &RenderTarget,
+        callback: F
+    ) -> R
+    {
+        self.renderer.begin_render_target(target);
+        let result = callback(self);
+        self.renderer.end_render_target();
+        result
+    }
+
+    /// Compiles a custom [Shader] from the provided GLSL sources. If
+    /// `vertex_source` is `None`, the built-in vertex shader is used.
+    ///
+    /// See the documentation for [Shader] for details of the attributes and
+    /// uniforms available to the shader.
+    ///
+    /// The returned [Shader] is valid only for the current graphics context.
+    pub fn create_shader(
+        &mut self,
+        vertex_source: Option<&str>,
+        fragment_source: &str
+    ) -> Result<Shader, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_shader(vertex_source, fragment_source)
+    }
+
     /// Fills the screen with the specified color.
     pub fn clear_screen(&mut self, color: Color)
     {
@@ -768,11 +1360,16 @@ impl Graphics2D
     /// once, and then re-use the same [crate::font::FormattedTextLine]
     /// object whenever you need to draw that text to the screen.
     ///
+    /// The text is drawn using the specified color, unless it was laid out
+    /// using [crate::font::TextLayout::layout_spans], in which case each glyph
+    /// is drawn using the color of its span.
+    ///
     /// Note: Text will be rendered with subpixel precision. If the subpixel
     /// position changes between frames, performance may be degraded, as the
     /// text will need to be re-rendered and re-uploaded. To avoid this,
     /// call `round()` on the position coordinates, to ensure that
-    /// the text is always located at an integer pixel position.
+    /// the text is always located at an integer pixel position, or lay out
+    /// the text using [crate::font::TextOptions::with_pixel_snapping].
     pub fn draw_text<V: Into<Vec2>>(
         &mut self,
         position: V,
@@ -783,6 +1380,58 @@ impl Graphics2D
         self.renderer.draw_text(position, color, text);
     }
 
+    /// Draws the provided block of text at the specified position, in the same
+    /// way as [Graphics2D::draw_text], and returns the area occupied by the
+    /// text. This avoids a separate measurement when, for example, laying out
+    /// further content after the text.
+    ///
+    /// The returned area is the layout box of the text, as described in
+    /// [FormattedTextBlock::bounds]. To get the tight ink box, which includes
+    /// the overhang of any glyphs, offset [FormattedTextBlock::ink_bounds] by
+    /// the same amount.
+    ///
+    /// The area is in the same coordinate space as `position`, before any
+    /// transforms are applied. If the text is pixel snapped, the area is
+    /// offset by the snapped position.
+    pub fn draw_text_bounded<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        color: Color,
+        text: &FormattedTextBlock
+    ) -> Rect
+    {
+        let position = self.renderer.draw_text(position, color, text);
+        text.bounds().with_offset(position)
+    }
+
+    /// Draws the provided block of text rotated by the specified angle. The
+    /// text is rotated around `position`, which is the top left corner of the
+    /// block before rotation. As the Y axis points downwards, positive angles
+    /// result in a clockwise rotation on the screen.
+    ///
+    /// For example, a rotation of `-std::f32::consts::FRAC_PI_2` draws the
+    /// text from bottom to top, as used for the title of a vertical chart
+    /// axis.
+    ///
+    /// This is equivalent to calling [Graphics2D::draw_text] at the origin,
+    /// after translating to `position` and rotating using the transform stack.
+    /// See the documentation for [Graphics2D::draw_text] for more details.
+    pub fn draw_text_rotated<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        rotation_radians: f32,
+        color: Color,
+        text: &FormattedTextBlock
+    )
+    {
+        self.push_transform(
+            Matrix3::from_translation(position)
+                * Matrix3::from_rotation(rotation_radians)
+        );
+        self.draw_text((0.0, 0.0), color, text);
+        self.pop_transform();
+    }
+
     /// Draws the provided block of text at the specified position, cropped to
     /// the specified window. Characters outside this window will not be
     /// rendered. Characters partially inside the window will be cropped.
@@ -911,6 +1560,12 @@ impl Graphics2D
     /// `0.0` to `1.0`, and define the portion of the source image which
     /// should be drawn.
     ///
+    /// Coordinates outside this range are passed through unchanged, and are
+    /// handled according to the image's [TextureWrapMode]. For example, if the
+    /// wrap mode is set to [TextureWrapMode::Repeat] using
+    /// [Graphics2D::set_image_wrap_mode], coordinates of `(0.0, 0.0)` to
+    /// `(4.0, 4.0)` tile the image four times in each direction.
+    ///
     /// The tinting is performed by for each pixel by multiplying each color
     /// component in the image pixel by the corresponding color component in
     /// the `color` parameter.
@@ -986,22 +1641,64 @@ impl Graphics2D
         );
     }
 
-    /// Draws an image, tinted with the provided color, at the specified
-    /// location. The image will be scaled to fill the pixel coordinates in
-    /// the provided rectangle.
+    /// Draws part of an image, tinted with the provided color, at the specified
+    /// location. The sub-image will be scaled to fill the pixel coordinates
+    /// in the provided rectangle.
+    ///
+    /// This is the same as [Graphics2D::draw_rectangle_image_subset_tinted],
+    /// except that `image_rect_pixels` defines the portion of the source image
+    /// in pixels, measured from the top left of the image. This is useful for
+    /// drawing a single cell of a sprite sheet.
     ///
     /// The tinting is performed by for each pixel by multiplying each color
     /// component in the image pixel by the corresponding color component in
     /// the `color` parameter.
     #[inline]
-    pub fn draw_rectangle_image_tinted(
+    pub fn draw_rectangle_image_subset_pixels_tinted(
         &mut self,
         rect: impl AsRef<Rectangle>,
         color: Color,
+        image_rect_pixels: impl AsRef<Rectangle<i32>>,
         image: &ImageHandle
     )
     {
-        self.draw_rectangle_image_subset_tinted(
+        let image_rect_pixels = image_rect_pixels.as_ref();
+        let image_size = image.size().into_f32();
+
+        let normalize = |position: &IVec2| {
+            Vec2::new(
+                position.x as f32 / image_size.x.max(1.0),
+                position.y as f32 / image_size.y.max(1.0)
+            )
+        };
+
+        self.draw_rectangle_image_subset_tinted(
+            rect,
+            color,
+            Rectangle::new(
+                normalize(image_rect_pixels.top_left()),
+                normalize(image_rect_pixels.bottom_right())
+            ),
+            image
+        );
+    }
+
+    /// Draws an image, tinted with the provided color, at the specified
+    /// location. The image will be scaled to fill the pixel coordinates in
+    /// the provided rectangle.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter.
+    #[inline]
+    pub fn draw_rectangle_image_tinted(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        color: Color,
+        image: &ImageHandle
+    )
+    {
+        self.draw_rectangle_image_subset_tinted(
             rect,
             color,
             Rectangle::new(Vec2::ZERO, Vec2::new(1.0, 1.0)),
@@ -1021,15 +1718,240 @@ impl Graphics2D
         self.draw_rectangle_image_tinted(rect, Color::WHITE, image);
     }
 
+    /// Draws every sprite in the specified [SpriteBatch], in the order they
+    /// were added.
+    ///
+    /// Consecutive sprites which use the same image are combined into a
+    /// single GPU draw call, so this is much faster than drawing each sprite
+    /// individually with a different image.
+    pub fn draw_sprite_batch(&mut self, batch: &SpriteBatch)
+    {
+        for sprite in &batch.sprites {
+            let coords = &sprite.image_coords_normalized;
+
+            self.draw_quad_image_tinted_four_color(
+                sprite.corners_clockwise,
+                [sprite.tint, sprite.tint, sprite.tint, sprite.tint],
+                [
+                    *coords.top_left(),
+                    coords.top_right(),
+                    *coords.bottom_right(),
+                    coords.bottom_left()
+                ],
+                &sprite.image
+            );
+        }
+    }
+
+    /// Draws an image using nine-patch (or "nine-slice") scaling, which is
+    /// useful for stretchable UI elements such as buttons and dialog
+    /// backgrounds.
+    ///
+    /// The `insets` parameter is the area of the image (in image pixels)
+    /// which may be stretched in both directions. The parts of the image
+    /// outside this area form a fixed border: the corners are drawn at their
+    /// original size, and the edges are only stretched along their length.
+    ///
+    /// If `rect` is too small to fit the border at its original size, the
+    /// border is scaled down to fit, and the center is not drawn.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter.
+    pub fn draw_nine_patch(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        color: Color,
+        insets: impl AsRef<Rectangle>,
+        image: &ImageHandle
+    )
+    {
+        let rect = rect.as_ref();
+        let insets = insets.as_ref();
+        let image_size = image.size().into_f32();
+
+        if image_size.x <= 0.0 || image_size.y <= 0.0 {
+            return;
+        }
+
+        // Positions of the slice boundaries in the source image, in pixels
+        let source_boundaries = |start: f32, end: f32, size: f32| {
+            let start = start.clamp(0.0, size);
+            [0.0, start, end.clamp(start, size), size]
+        };
+
+        let source_x =
+            source_boundaries(insets.top_left().x, insets.bottom_right().x, image_size.x);
+        let source_y =
+            source_boundaries(insets.top_left().y, insets.bottom_right().y, image_size.y);
+
+        // Positions of the slice boundaries on the screen
+        let dest_boundaries = |source: [f32; 4], start: f32, end: f32| {
+            let first = source[1] - source[0];
+            let last = source[3] - source[2];
+            let available = (end - start).max(0.0);
+
+            let scale = if first + last > available {
+                available / (first + last)
+            } else {
+                1.0
+            };
+
+            [start, start + first * scale, end - last * scale, end]
+        };
+
+        let dest_x = dest_boundaries(source_x, rect.top_left().x, rect.bottom_right().x);
+        let dest_y = dest_boundaries(source_y, rect.top_left().y, rect.bottom_right().y);
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let dest = Rectangle::new(
+                    Vec2::new(dest_x[col], dest_y[row]),
+                    Vec2::new(dest_x[col + 1], dest_y[row + 1])
+                );
+
+                let source = Rectangle::new(
+                    Vec2::new(source_x[col], source_y[row]),
+                    Vec2::new(source_x[col + 1], source_y[row + 1])
+                );
+
+                if !dest.is_positive_area() || !source.is_positive_area() {
+                    continue;
+                }
+
+                self.draw_rectangle_image_subset_tinted(
+                    dest,
+                    color,
+                    Rectangle::new(
+                        Vec2::new(
+                            source_x[col] / image_size.x,
+                            source_y[row] / image_size.y
+                        ),
+                        Vec2::new(
+                            source_x[col + 1] / image_size.x,
+                            source_y[row + 1] / image_size.y
+                        )
+                    ),
+                    image
+                );
+            }
+        }
+    }
+
+    /// Draws an image, tinted with the provided color, rotated around its
+    /// center. The image will be scaled to the specified `size`, centered at
+    /// `center`, and then rotated by `rotation_radians`. As the Y axis points
+    /// downwards, positive angles result in a clockwise rotation on the
+    /// screen.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter.
+    #[inline]
+    pub fn draw_rectangle_image_rotated_tinted(
+        &mut self,
+        center: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+        rotation_radians: f32,
+        color: Color,
+        image: &ImageHandle
+    )
+    {
+        let center = center.into();
+        let half_size = size.into() / 2.0;
+
+        let rect = Rectangle::new(center - half_size, center + half_size);
+
+        self.draw_quad_image_tinted_four_color(
+            rect.rotated_corners_clockwise(rotation_radians),
+            [color, color, color, color],
+            [
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(0.0, 1.0)
+            ],
+            image
+        );
+    }
+
+    /// Draws an image rotated around its center. The image will be scaled to
+    /// the specified `size`, centered at `center`, and then rotated by
+    /// `rotation_radians`. As the Y axis points downwards, positive angles
+    /// result in a clockwise rotation on the screen.
+    #[inline]
+    pub fn draw_rectangle_image_rotated(
+        &mut self,
+        center: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+        rotation_radians: f32,
+        image: &ImageHandle
+    )
+    {
+        self.draw_rectangle_image_rotated_tinted(
+            center,
+            size,
+            rotation_radians,
+            Color::WHITE,
+            image
+        );
+    }
+
     /// Draws an image at the specified pixel location. The image will be
     /// drawn at its original size with no scaling.
+    ///
+    /// The area covered by the image is given by
+    /// [Graphics2D::image_destination_rect].
     #[inline]
     pub fn draw_image<P: Into<Vec2>>(&mut self, position: P, image: &ImageHandle)
+    {
+        self.draw_rectangle_image(Self::image_destination_rect(position, image), image);
+    }
+
+    /// Returns the area which [Graphics2D::draw_image] covers when drawing
+    /// `image` at `position`. This is useful for detecting clicks on the
+    /// image, using [Rectangle::contains]:
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::dimen::Vec2;
+    /// # use speedy2d::image::ImageHandle;
+    /// # use speedy2d::Graphics2D;
+    /// # fn on_click(image: &ImageHandle, mouse_position: Vec2) {
+    /// let position = Vec2::new(100.0, 50.0);
+    ///
+    /// if Graphics2D::image_destination_rect(position, image).contains(mouse_position) {
+    ///     println!("Image clicked");
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn image_destination_rect<P: Into<Vec2>>(
+        position: P,
+        image: &ImageHandle
+    ) -> Rectangle
     {
         let position = position.into();
+        Rectangle::new(position, position + image.size().into_f32())
+    }
 
-        self.draw_rectangle_image(
-            Rectangle::new(position, position + image.size().into_f32()),
+    /// Draws an image using the specified [DrawImageOptions], which allow the
+    /// image to be scaled, cropped, tinted, rotated, and flipped in a single
+    /// call.
+    #[inline]
+    pub fn draw_image_with_options(
+        &mut self,
+        image: &ImageHandle,
+        options: &DrawImageOptions
+    )
+    {
+        let (positions, image_coords) = options.vertices(*image.size());
+        let tint = options.tint();
+
+        self.draw_quad_image_tinted_four_color(
+            positions,
+            [tint, tint, tint, tint],
+            image_coords,
             image
         );
     }
@@ -1052,6 +1974,36 @@ impl Graphics2D
         );
     }
 
+    /// Draws a rectangle with a different color at each corner, with the
+    /// colors blended smoothly across the rectangle. This is useful for
+    /// gradient backgrounds and heatmap cells.
+    ///
+    /// The colors are specified in clockwise order, starting from the top
+    /// left: `[top_left, top_right, bottom_right, bottom_left]`.
+    ///
+    /// The rectangle is drawn as two triangles, split along the diagonal from
+    /// the top left to the bottom right corner, so the center of the
+    /// rectangle is an equal blend of the top left and bottom right colors.
+    #[inline]
+    pub fn draw_rectangle_four_color(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        corner_colors_clockwise: [Color; 4]
+    )
+    {
+        let rect = rect.as_ref();
+
+        self.draw_quad_four_color(
+            [
+                *rect.top_left(),
+                rect.top_right(),
+                *rect.bottom_right(),
+                rect.bottom_left()
+            ],
+            corner_colors_clockwise
+        );
+    }
+
     /// Draws a single-color rounded rectangle at the specified location. The
     /// coordinates of the rounded rectangle are specified in pixels.
     #[inline]
@@ -1260,6 +2212,405 @@ impl Graphics2D
         );
     }
 
+    /// Draws a single-color, anti-aliased line between the given points,
+    /// specified in pixels.
+    ///
+    /// The edges of the line are feathered using a one-pixel alpha ramp, so
+    /// diagonal lines appear smooth without multisampling. The line covers
+    /// the same area as a line drawn using [Graphics2D::draw_line], so the
+    /// pixel alignment advice for that function also applies here.
+    pub fn draw_line_aa<VStart: Into<Vec2>, VEnd: Into<Vec2>>(
+        &mut self,
+        start_position: VStart,
+        end_position: VEnd,
+        thickness: f32,
+        color: Color
+    )
+    {
+        let start_position = start_position.into();
+        let end_position = end_position.into();
+
+        let gradient_normalized = match (end_position - start_position).normalize() {
+            None => return,
+            Some(gradient) => gradient
+        };
+
+        // Lines thinner than a pixel are drawn with reduced opacity instead
+        let inner_color = if thickness < 1.0 {
+            Color::from_rgba(color.r(), color.g(), color.b(), color.a() * thickness)
+        } else {
+            color
+        };
+
+        let outer_color = Color::from_rgba(color.r(), color.g(), color.b(), 0.0);
+
+        let inner_half_thickness = (thickness / 2.0 - 0.5).max(0.0);
+        let outer_half_thickness = inner_half_thickness + 1.0;
+
+        let inset = (start_position - end_position).magnitude().min(1.0) / 2.0;
+
+        let inner_start = start_position + gradient_normalized * inset;
+        let inner_end = end_position - gradient_normalized * inset;
+        let outer_start = start_position - gradient_normalized * 0.5;
+        let outer_end = end_position + gradient_normalized * 0.5;
+
+        let inner_offset_anticlockwise = (gradient_normalized * inner_half_thickness)
+            .rotate_90_degrees_anticlockwise();
+        let outer_offset_anticlockwise = (gradient_normalized * outer_half_thickness)
+            .rotate_90_degrees_anticlockwise();
+
+        let inner = [
+            inner_start + inner_offset_anticlockwise,
+            inner_end + inner_offset_anticlockwise,
+            inner_end - inner_offset_anticlockwise,
+            inner_start - inner_offset_anticlockwise
+        ];
+
+        let outer = [
+            outer_start + outer_offset_anticlockwise,
+            outer_end + outer_offset_anticlockwise,
+            outer_end - outer_offset_anticlockwise,
+            outer_start - outer_offset_anticlockwise
+        ];
+
+        self.draw_quad(inner, inner_color);
+
+        for &(i, next) in [(0, 1), (1, 2), (2, 3), (3, 0)].iter() {
+            self.draw_quad_four_color(
+                [outer[i], outer[next], inner[next], inner[i]],
+                [outer_color, outer_color, inner_color, inner_color]
+            );
+        }
+    }
+
+    /// Draws a single-color dashed line between the given points, specified
+    /// in pixels. The line begins with a dash of length `dash_length`,
+    /// followed by a gap of length `gap_length`, with the pattern repeating
+    /// until the end of the line.
+    ///
+    /// Each dash is drawn in the same way as [Graphics2D::draw_line], so the
+    /// pixel alignment advice for that function also applies here.
+    ///
+    /// See [Graphics2D::draw_dashed_line_with_offset] to shift the pattern
+    /// along the line.
+    pub fn draw_dashed_line<VStart: Into<Vec2>, VEnd: Into<Vec2>>(
+        &mut self,
+        start_position: VStart,
+        end_position: VEnd,
+        thickness: f32,
+        color: Color,
+        dash_length: f32,
+        gap_length: f32
+    )
+    {
+        self.draw_dashed_line_with_offset(
+            start_position,
+            end_position,
+            thickness,
+            color,
+            dash_length,
+            gap_length,
+            0.0
+        );
+    }
+
+    /// Draws a single-color dashed line between the given points, specified
+    /// in pixels, with the dash pattern shifted towards the end of the line
+    /// by `offset` pixels.
+    ///
+    /// The pattern depends only on the start position and the offset, so a
+    /// "marching ants" animation can be created by increasing the offset
+    /// each frame. The pattern repeats every `dash_length + gap_length`
+    /// pixels.
+    ///
+    /// If `gap_length` is zero, a solid line is drawn. If `dash_length` is
+    /// zero, nothing is drawn.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_dashed_line_with_offset<VStart: Into<Vec2>, VEnd: Into<Vec2>>(
+        &mut self,
+        start_position: VStart,
+        end_position: VEnd,
+        thickness: f32,
+        color: Color,
+        dash_length: f32,
+        gap_length: f32,
+        offset: f32
+    )
+    {
+        let dashes = dash_segments(
+            start_position.into(),
+            end_position.into(),
+            dash_length,
+            gap_length,
+            offset
+        );
+
+        for (start, end) in dashes {
+            self.draw_line(start, end, thickness, color);
+        }
+    }
+
+    /// Draws a continuous single-color line through the given points,
+    /// specified in pixels. Adjacent segments are connected using
+    /// [LineJoin::Miter].
+    ///
+    /// See [Graphics2D::draw_polyline_with_join] for more details.
+    pub fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color)
+    {
+        self.draw_polyline_with_join(points, thickness, LineJoin::default(), color);
+    }
+
+    /// Draws a continuous single-color line through the given points,
+    /// specified in pixels. Adjacent segments are connected using the
+    /// specified [LineJoin].
+    ///
+    /// Each segment is drawn in the same way as [Graphics2D::draw_line], so
+    /// the pixel alignment advice for that function also applies here. The
+    /// ends of the line are not extended past the first and last points.
+    ///
+    /// Note: segments overlap slightly on the inside of each join, so this
+    /// area may appear darker when using a translucent color. To avoid this,
+    /// draw the line using an opaque color into a [RenderTarget], and then
+    /// draw the target's image with the desired opacity.
+    pub fn draw_polyline_with_join(
+        &mut self,
+        points: &[Vec2],
+        thickness: f32,
+        join: LineJoin,
+        color: Color
+    )
+    {
+        self.draw_polyline_internal(points, false, thickness, join, color);
+    }
+
+    fn draw_polyline_internal(
+        &mut self,
+        points: &[Vec2],
+        closed: bool,
+        thickness: f32,
+        join: LineJoin,
+        color: Color
+    )
+    {
+        let closing_segment = match (closed, points.first(), points.last()) {
+            (true, Some(first), Some(last)) => Some((last, first)),
+            _ => None
+        };
+
+        let mut first_direction: Option<Vec2> = None;
+        let mut previous_direction: Option<Vec2> = None;
+
+        for (&start, &end) in points
+            .iter()
+            .zip(points.iter().skip(1))
+            .chain(closing_segment)
+        {
+            let direction = match (end - start).normalize() {
+                None => continue,
+                Some(direction) => direction
+            };
+
+            self.draw_line(start, end, thickness, color);
+
+            first_direction.get_or_insert(direction);
+
+            if let Some(previous_direction) = previous_direction.replace(direction) {
+                self.draw_line_join(
+                    start,
+                    previous_direction,
+                    direction,
+                    thickness,
+                    join,
+                    color
+                );
+            }
+        }
+
+        if let (Some(first), Some(first_direction), Some(last_direction), true) =
+            (points.first(), first_direction, previous_direction, closed)
+        {
+            self.draw_line_join(
+                *first,
+                last_direction,
+                first_direction,
+                thickness,
+                join,
+                color
+            );
+        }
+    }
+
+    fn draw_line_join(
+        &mut self,
+        point: Vec2,
+        previous_direction: Vec2,
+        direction: Vec2,
+        thickness: f32,
+        join: LineJoin,
+        color: Color
+    )
+    {
+        // Beyond this ratio between the miter length and half the line
+        // thickness, a bevel join is used instead. This matches the default
+        // miter limit in SVG.
+        const MITER_LIMIT: f32 = 4.0;
+
+        let dot = |a: Vec2, b: Vec2| a.x * b.x + a.y * b.y;
+
+        let half_thickness = thickness / 2.0;
+
+        if join == LineJoin::Round {
+            self.draw_circle(point, half_thickness, color);
+            return;
+        }
+
+        let cross =
+            previous_direction.x * direction.y - previous_direction.y * direction.x;
+
+        if cross.abs() < 0.0001 {
+            // The segments are collinear, so there's no gap to fill
+            return;
+        }
+
+        // Find the normals pointing towards the outside of the corner
+        let mut outer_previous = previous_direction.rotate_90_degrees_clockwise();
+        if dot(outer_previous, direction) > 0.0 {
+            outer_previous *= -1.0;
+        }
+
+        let mut outer_next = direction.rotate_90_degrees_clockwise();
+        if dot(outer_next, previous_direction) < 0.0 {
+            outer_next *= -1.0;
+        }
+
+        let corner_previous = point + outer_previous * half_thickness;
+        let corner_next = point + outer_next * half_thickness;
+
+        let normals_dot = dot(outer_previous, outer_next);
+        let miter_ratio = (2.0 / (1.0 + normals_dot)).sqrt();
+
+        if join == LineJoin::Miter && miter_ratio <= MITER_LIMIT {
+            let miter_point = point
+                + (outer_previous + outer_next) * (half_thickness / (1.0 + normals_dot));
+
+            self.draw_quad([point, corner_previous, miter_point, corner_next], color);
+        } else {
+            self.draw_triangle([point, corner_previous, corner_next], color);
+        }
+    }
+
+    /// Draws the outline of a rectangle, with the specified line thickness in
+    /// pixels. The line is centered on the edge of the rectangle, so half of
+    /// the thickness lies inside the rectangle, and half lies outside.
+    ///
+    /// The corners are drawn using [LineJoin::Miter], so the outline is
+    /// continuous with square corners.
+    ///
+    /// Note: to get crisp edges, place the rectangle's edges on pixel
+    /// boundaries when using an even thickness, and in the center of a pixel
+    /// when using an odd thickness.
+    pub fn draw_rectangle_outline(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        thickness: f32,
+        color: Color
+    )
+    {
+        let rect = rect.as_ref();
+
+        self.draw_polyline_internal(
+            &[
+                *rect.top_left(),
+                rect.top_right(),
+                *rect.bottom_right(),
+                rect.bottom_left()
+            ],
+            true,
+            thickness,
+            LineJoin::Miter,
+            color
+        );
+    }
+
+    /// Draws the outline of a polygon, with the specified offset and line
+    /// thickness in pixels. As with [Graphics2D::draw_rectangle_outline], the
+    /// line is centered on the edge of the polygon, and the corners are
+    /// joined using [LineJoin::Miter], falling back to a bevel for very sharp
+    /// corners.
+    pub fn draw_polygon_outline<V: Into<Vec2>>(
+        &mut self,
+        polygon: &Polygon,
+        offset: V,
+        thickness: f32,
+        color: Color
+    )
+    {
+        let offset = offset.into();
+
+        let points: Vec<Vec2> = polygon
+            .vertices
+            .iter()
+            .map(|vertex| *vertex + offset)
+            .collect();
+
+        self.draw_polyline_internal(&points, true, thickness, LineJoin::Miter, color);
+    }
+
+    /// Draws a quadratic Bézier curve from `start` to `end`, using `control`
+    /// as the control point. The coordinates are specified in pixels.
+    ///
+    /// The curve is approximated using enough straight line segments that it
+    /// deviates from the true curve by no more than a fraction of a pixel,
+    /// and is then drawn using [Graphics2D::draw_polyline].
+    pub fn draw_quadratic_bezier<VStart, VControl, VEnd>(
+        &mut self,
+        start: VStart,
+        control: VControl,
+        end: VEnd,
+        thickness: f32,
+        color: Color
+    ) where
+        VStart: Into<Vec2>,
+        VControl: Into<Vec2>,
+        VEnd: Into<Vec2>
+    {
+        let points = flatten_quadratic_bezier(
+            [start.into(), control.into(), end.into()],
+            BEZIER_TOLERANCE
+        );
+
+        self.draw_polyline(&points, thickness, color);
+    }
+
+    /// Draws a cubic Bézier curve from `start` to `end`, using `control_1`
+    /// and `control_2` as the control points. The coordinates are specified
+    /// in pixels.
+    ///
+    /// The curve is approximated using enough straight line segments that it
+    /// deviates from the true curve by no more than a fraction of a pixel,
+    /// and is then drawn using [Graphics2D::draw_polyline].
+    pub fn draw_cubic_bezier<VStart, VControl1, VControl2, VEnd>(
+        &mut self,
+        start: VStart,
+        control_1: VControl1,
+        control_2: VControl2,
+        end: VEnd,
+        thickness: f32,
+        color: Color
+    ) where
+        VStart: Into<Vec2>,
+        VControl1: Into<Vec2>,
+        VControl2: Into<Vec2>,
+        VEnd: Into<Vec2>
+    {
+        let points = flatten_cubic_bezier(
+            [start.into(), control_1.into(), control_2.into(), end.into()],
+            BEZIER_TOLERANCE
+        );
+
+        self.draw_polyline(&points, thickness, color);
+    }
+
     /// Draws a circle, filled with a single color, at the specified pixel
     /// location.
     pub fn draw_circle<V: Into<Vec2>>(
@@ -1297,6 +2648,258 @@ impl Graphics2D
         );
     }
 
+    /// Draws the outline of a circle at the specified pixel location, with
+    /// anti-aliased edges. The line is centered on the edge of a circle with
+    /// the specified `radius`.
+    ///
+    /// See [Graphics2D::draw_arc] for more details.
+    #[inline]
+    pub fn draw_circle_outline<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        thickness: f32,
+        color: Color
+    )
+    {
+        self.draw_arc(
+            center_position,
+            radius,
+            0.0,
+            std::f32::consts::PI * 2.0,
+            thickness,
+            color
+        );
+    }
+
+    /// Draws part of the outline of a circle at the specified pixel location,
+    /// with anti-aliased edges. This is useful for gauges, pie charts, and
+    /// progress indicators.
+    ///
+    /// The line is centered on the edge of a circle with the specified
+    /// `radius`, and is drawn clockwise from `start_angle` to `end_angle`.
+    /// Angles are specified in radians, clockwise from the positive X axis.
+    /// If `end_angle` is not greater than `start_angle`, nothing is drawn.
+    ///
+    /// As with [Graphics2D::draw_line_aa], the edges of the line are feathered
+    /// using a one-pixel alpha ramp. The ends of the arc are not feathered.
+    pub fn draw_arc<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        thickness: f32,
+        color: Color
+    )
+    {
+        // The maximum distance in pixels between the curve and the straight
+        // segments used to approximate it
+        const TOLERANCE: f32 = 0.1;
+
+        let center_position = center_position.into();
+        let sweep = (end_angle - start_angle).min(std::f32::consts::PI * 2.0);
+
+        if sweep <= 0.0 || thickness <= 0.0 {
+            return;
+        }
+
+        // Lines thinner than a pixel are drawn with reduced opacity instead
+        let inner_color = if thickness < 1.0 {
+            Color::from_rgba(color.r(), color.g(), color.b(), color.a() * thickness)
+        } else {
+            color
+        };
+
+        let outer_color = Color::from_rgba(color.r(), color.g(), color.b(), 0.0);
+
+        let half_thickness = (thickness / 2.0 - 0.5).max(0.0);
+
+        // Radii of the feathered outer edge, the solid part, and the feathered
+        // inner edge
+        let radii = [
+            radius + half_thickness + 1.0,
+            radius + half_thickness,
+            (radius - half_thickness).max(0.0),
+            (radius - half_thickness - 1.0).max(0.0)
+        ];
+
+        let max_step = 2.0 * (1.0 - TOLERANCE / radii[0]).max(0.0).acos();
+        let segments = ((sweep / max_step).ceil() as usize).max(1);
+
+        let directions: Vec<Vec2> = (0..=segments)
+            .map(|i| {
+                let angle = start_angle + sweep * (i as f32 / segments as f32);
+                Vec2::new(angle.cos(), angle.sin())
+            })
+            .collect();
+
+        for (&dir_a, &dir_b) in directions.iter().zip(directions.iter().skip(1)) {
+            let ring = |radius: f32| {
+                (
+                    center_position + dir_a * radius,
+                    center_position + dir_b * radius
+                )
+            };
+
+            let (outer_a, outer_b) = ring(radii[0]);
+            let (solid_outer_a, solid_outer_b) = ring(radii[1]);
+            let (solid_inner_a, solid_inner_b) = ring(radii[2]);
+            let (inner_a, inner_b) = ring(radii[3]);
+
+            self.draw_quad_four_color(
+                [outer_a, outer_b, solid_outer_b, solid_outer_a],
+                [outer_color, outer_color, inner_color, inner_color]
+            );
+
+            self.draw_quad(
+                [solid_outer_a, solid_outer_b, solid_inner_b, solid_inner_a],
+                inner_color
+            );
+
+            self.draw_quad_four_color(
+                [solid_inner_a, solid_inner_b, inner_b, inner_a],
+                [inner_color, inner_color, outer_color, outer_color]
+            );
+        }
+    }
+
+    /// Draws a rectangle filled with a linear gradient, at the specified
+    /// location. The coordinates of the rectangle are specified in pixels.
+    pub fn draw_rectangle_gradient(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        direction: GradientDirection,
+        gradient: &Gradient
+    )
+    {
+        let rect = rect.as_ref();
+
+        for stops in gradient.stops().windows(2) {
+            let (start_position, start_color) = stops[0];
+            let (end_position, end_color) = stops[1];
+
+            if end_position <= start_position {
+                continue;
+            }
+
+            match direction {
+                GradientDirection::LeftToRight => {
+                    let left = rect.left() + rect.width() * start_position;
+                    let right = rect.left() + rect.width() * end_position;
+
+                    self.draw_quad_four_color(
+                        [
+                            Vec2::new(left, rect.top()),
+                            Vec2::new(right, rect.top()),
+                            Vec2::new(right, rect.bottom()),
+                            Vec2::new(left, rect.bottom())
+                        ],
+                        [start_color, end_color, end_color, start_color]
+                    );
+                }
+
+                GradientDirection::TopToBottom => {
+                    let top = rect.top() + rect.height() * start_position;
+                    let bottom = rect.top() + rect.height() * end_position;
+
+                    self.draw_quad_four_color(
+                        [
+                            Vec2::new(rect.left(), top),
+                            Vec2::new(rect.right(), top),
+                            Vec2::new(rect.right(), bottom),
+                            Vec2::new(rect.left(), bottom)
+                        ],
+                        [start_color, start_color, end_color, end_color]
+                    );
+                }
+            }
+        }
+    }
+
+    /// Draws a circle filled with a radial gradient, at the specified pixel
+    /// location. The start of the gradient is at the center of the circle,
+    /// and the end of the gradient is at its edge.
+    pub fn draw_circle_gradient<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        gradient: &Gradient
+    )
+    {
+        const SEGMENTS: usize = 64;
+
+        let center_position = center_position.into();
+
+        // The outermost ring is extended so that it fully encloses the circle,
+        // and the edge is then cut precisely by the circle shader.
+        let outer_edge_scale = 1.0 / (std::f32::consts::PI / SEGMENTS as f32).cos();
+
+        let directions: Vec<Vec2> = (0..SEGMENTS)
+            .map(|i| {
+                let angle = (i as f32 / SEGMENTS as f32) * std::f32::consts::PI * 2.0;
+                Vec2::new(angle.cos(), angle.sin())
+            })
+            .collect();
+
+        for stops in gradient.stops().windows(2) {
+            let (inner_position, inner_color) = stops[0];
+            let (outer_position, outer_color) = stops[1];
+
+            if outer_position <= inner_position {
+                continue;
+            }
+
+            let outer_position = if outer_position >= 1.0 {
+                outer_edge_scale
+            } else {
+                outer_position
+            };
+
+            for (i, dir_a) in directions.iter().enumerate() {
+                let dir_b = directions[(i + 1) % SEGMENTS];
+
+                let outer_a = dir_a * outer_position;
+                let outer_b = dir_b * outer_position;
+
+                if inner_position <= 0.0 {
+                    self.renderer.draw_circle_section(
+                        [
+                            center_position,
+                            center_position + outer_a * radius,
+                            center_position + outer_b * radius
+                        ],
+                        [inner_color, outer_color, outer_color],
+                        [Vec2::ZERO, outer_a, outer_b]
+                    );
+                } else {
+                    let inner_a = dir_a * inner_position;
+                    let inner_b = dir_b * inner_position;
+
+                    self.renderer.draw_circle_section(
+                        [
+                            center_position + inner_a * radius,
+                            center_position + outer_a * radius,
+                            center_position + outer_b * radius
+                        ],
+                        [inner_color, outer_color, outer_color],
+                        [inner_a, outer_a, outer_b]
+                    );
+
+                    self.renderer.draw_circle_section(
+                        [
+                            center_position + outer_b * radius,
+                            center_position + inner_b * radius,
+                            center_position + inner_a * radius
+                        ],
+                        [outer_color, inner_color, inner_color],
+                        [outer_b, inner_b, inner_a]
+                    );
+                }
+            }
+        }
+    }
+
     /// Draws a triangular subset of a circle.
     ///
     /// Put simply, this function will draw a triangle on the screen, textured
@@ -1345,6 +2948,23 @@ impl Graphics2D
         );
     }
 
+    /// Immediately sends any queued drawing operations to the GPU, without
+    /// ending the frame.
+    ///
+    /// Drawing operations are normally queued and combined into as few GPU
+    /// draw calls as possible. This is useful when mixing Speedy2D with your
+    /// own OpenGL calls, to ensure that everything drawn so far has been
+    /// submitted at a known point.
+    ///
+    /// Note that functions which change the drawing state, such as
+    /// [Graphics2D::set_clip], [Graphics2D::set_blend_mode], and
+    /// [Graphics2D::set_shader], already flush the queue when necessary, so
+    /// it's not required to call this before them.
+    pub fn flush(&mut self)
+    {
+        self.renderer.flush_render_queue();
+    }
+
     /// Sets the current clip to the rectangle specified by the given
     /// coordinates. Rendering operations have no effect outside of the
     /// clipping area.
@@ -1353,6 +2973,192 @@ impl Graphics2D
         self.renderer.set_clip(rect);
     }
 
+    /// Pushes a transform onto the transform stack. The transform will be
+    /// combined with any transforms already on the stack, and applied to the
+    /// vertices of everything drawn until the corresponding call to
+    /// [Graphics2D::pop_transform].
+    ///
+    /// The transform stack is cleared at the start of each frame. Note that
+    /// the clip area set using [Graphics2D::set_clip] is not affected by
+    /// transforms.
+    pub fn push_transform(&mut self, transform: Matrix3)
+    {
+        self.renderer.push_transform(transform);
+    }
+
+    /// Removes the most recently pushed transform from the transform stack,
+    /// restoring the previous transform.
+    pub fn pop_transform(&mut self)
+    {
+        self.renderer.pop_transform();
+    }
+
+    /// Pushes a translation onto the transform stack. This is equivalent to
+    /// calling [Graphics2D::push_transform] with
+    /// [Matrix3::from_translation].
+    #[inline]
+    pub fn translate<V: Into<Vec2>>(&mut self, offset: V)
+    {
+        self.push_transform(Matrix3::from_translation(offset));
+    }
+
+    /// Pushes a rotation around the origin onto the transform stack. This is
+    /// equivalent to calling [Graphics2D::push_transform] with
+    /// [Matrix3::from_rotation].
+    #[inline]
+    pub fn rotate(&mut self, radians: f32)
+    {
+        self.push_transform(Matrix3::from_rotation(radians));
+    }
+
+    /// Pushes a scale relative to the origin onto the transform stack. This is
+    /// equivalent to calling [Graphics2D::push_transform] with
+    /// [Matrix3::from_scale].
+    #[inline]
+    pub fn scale<V: Into<Vec2>>(&mut self, factor: V)
+    {
+        self.push_transform(Matrix3::from_scale(factor));
+    }
+
+    /// Sets the blend mode used for subsequent drawing operations. Anything
+    /// drawn before this call will continue to use the previous blend mode.
+    ///
+    /// The blend mode is reset to [BlendMode::Normal] at the start of each
+    /// frame.
+    pub fn set_blend_mode(&mut self, mode: BlendMode)
+    {
+        self.renderer.set_blend_mode(mode);
+    }
+
+    /// Sets the depth used for subsequent drawing operations, or disables
+    /// depth testing if `None` is specified. Anything drawn before this call
+    /// will continue to use the previous depth.
+    ///
+    /// The depth must be in the range `0.0` to `1.0` (values outside this
+    /// range are clamped). While depth testing is enabled, shapes drawn with
+    /// a higher depth will appear on top of shapes with a lower depth,
+    /// regardless of the order in which they were drawn. Shapes at the same
+    /// depth are drawn in the order of the calls, as normal.
+    ///
+    /// Shapes drawn while the depth is `None` don't take part in depth
+    /// testing: they're drawn on top of everything already on the screen,
+    /// and don't hide anything drawn later.
+    ///
+    /// Note that each shape hides anything with a lower depth that is drawn
+    /// after it, even if the shape is translucent, so the shapes underneath
+    /// won't show through. To blend translucent shapes correctly, draw them
+    /// after any opaque shapes, in order of increasing depth.
+    ///
+    /// Changing the depth flushes the render queue, so for best performance,
+    /// group together drawing operations which share the same depth.
+    ///
+    /// Depth testing requires the framebuffer to have a depth buffer. This is
+    /// requested automatically when creating a window, but render targets
+    /// and framebuffers provided using [GLRenderer::new_for_gl_context] may
+    /// not have one, in which case shapes are always drawn in call order.
+    /// Custom vertex shaders must use the `in_Depth` uniform to take part in
+    /// depth testing.
+    ///
+    /// The depth is reset to `None` at the start of each frame.
+    pub fn set_depth(&mut self, depth: Option<f32>)
+    {
+        self.renderer.set_depth(depth);
+    }
+
+    /// Returns the depth set using [Graphics2D::set_depth], or `None` if depth
+    /// testing is disabled.
+    #[inline]
+    #[must_use]
+    pub fn get_depth(&self) -> Option<f32>
+    {
+        self.renderer.get_depth()
+    }
+
+    /// Returns the ratio of physical pixels to logical pixels (also known as
+    /// the DPI scale) of the window being rendered to. This is useful for
+    /// drawing lines which are exactly one physical pixel wide, or for
+    /// snapping positions to physical pixels.
+    ///
+    /// When Speedy2D creates the window, this is the same as
+    /// [crate::window::WindowHelper::get_scale_factor]. When rendering using a
+    /// [GLRenderer] created for your own GL context, this is the value set
+    /// using [GLRenderer::set_scale_factor], and is `1.0` if the scale factor
+    /// was never set.
+    #[inline]
+    #[must_use]
+    pub fn get_scale_factor(&self) -> f64
+    {
+        self.renderer.get_scale_factor()
+    }
+
+    /// Sets the custom shader used for subsequent drawing operations, or
+    /// restores the built-in shader if `None` is specified. Anything drawn
+    /// before this call will continue to use the previous shader.
+    ///
+    /// The built-in shader is restored at the start of each frame.
+    pub fn set_shader(&mut self, shader: Option<&Shader>)
+    {
+        self.renderer.set_shader(shader);
+    }
+
+    /// Sets the value of a `float` uniform in the specified shader. Anything
+    /// drawn before this call will continue to use the previous value.
+    ///
+    /// An error is returned if the shader has no uniform with the specified
+    /// name. Note that GLSL compilers remove any uniforms which aren't used.
+    pub fn set_shader_uniform_f32(
+        &mut self,
+        shader: &Shader,
+        name: &str,
+        value: f32
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .set_shader_uniform(shader, name, |handle, context| {
+                handle.set_value_float(context, value)
+            })
+    }
+
+    /// Sets the value of a `vec2` uniform in the specified shader. Anything
+    /// drawn before this call will continue to use the previous value.
+    ///
+    /// An error is returned if the shader has no uniform with the specified
+    /// name. Note that GLSL compilers remove any uniforms which aren't used.
+    pub fn set_shader_uniform_vec2<V: Into<Vec2>>(
+        &mut self,
+        shader: &Shader,
+        name: &str,
+        value: V
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        let value = value.into();
+
+        self.renderer
+            .set_shader_uniform(shader, name, |handle, context| {
+                handle.set_value_vec2(context, value.x, value.y)
+            })
+    }
+
+    /// Sets the value of a `vec4` uniform in the specified shader to the
+    /// red, green, blue, and alpha components of the specified color. Anything
+    /// drawn before this call will continue to use the previous value.
+    ///
+    /// An error is returned if the shader has no uniform with the specified
+    /// name. Note that GLSL compilers remove any uniforms which aren't used.
+    pub fn set_shader_uniform_color(
+        &mut self,
+        shader: &Shader,
+        name: &str,
+        value: Color
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .set_shader_uniform(shader, name, |handle, context| {
+                handle
+                    .set_value_vec4(context, [value.r(), value.g(), value.b(), value.a()])
+            })
+    }
+
     /// Captures a screenshot of the render window. The returned data contains
     /// the color of each pixel. Pixels are represented using a `u8` for each
     /// component (red, green, blue, and alpha). Use the `format` parameter to
@@ -1361,9 +3167,87 @@ impl Graphics2D
     {
         self.renderer.capture(format)
     }
+
+    /// Draws the contents of the callback into an offscreen target of the
+    /// specified size, and captures the result. This does not affect the
+    /// render window.
+    ///
+    /// If supersampling is enabled using
+    /// [CaptureOptions::with_supersampling], the offscreen target is larger
+    /// than `size` by the supersampling factor, and a scale transform is
+    /// pushed before calling the callback, so that the callback can draw
+    /// using the coordinates of the final image. The result is then
+    /// downscaled on the CPU, which smooths the edges of shapes. Text is
+    /// scaled as an image, so for the best quality, lay it out at the final
+    /// scale multiplied by the supersampling factor, and draw it inside a
+    /// transform which undoes the scale.
+    ///
+    /// As with [Graphics2D::draw_to_target], the offscreen target is
+    /// initially fully transparent.
+    ///
+    /// An error is returned if the offscreen target couldn't be created, or if
+    /// its size, after multiplying by the supersampling factor, exceeds the
+    /// maximum texture size supported by the GPU.
+    pub fn capture_offscreen<S: Into<UVec2>, F: FnOnce(&mut Graphics2D)>(
+        &mut self,
+        size: S,
+        options: &CaptureOptions,
+        callback: F
+    ) -> Result<RawBitmapData, BacktraceError<ErrorMessage>>
+    {
+        let size = size.into();
+        let factor = options.supersampling();
+        let max_size = self.renderer.max_texture_size();
+
+        let target_size = match (size.x.checked_mul(factor), size.y.checked_mul(factor)) {
+            (Some(x), Some(y)) if x <= max_size && y <= max_size => UVec2::new(x, y),
+            _ => {
+                return Err(ErrorMessage::msg(format!(
+                    "Offscreen target size {}x{} with supersampling factor {} exceeds \
+                     the maximum texture size of {}",
+                    size.x, size.y, factor, max_size
+                )))
+            }
+        };
+
+        let target =
+            self.create_render_target(ImageSmoothingMode::NearestNeighbor, target_size)?;
+
+        self.draw_to_target(&target, |graphics| {
+            graphics.scale((factor as f32, factor as f32));
+            callback(graphics);
+        });
+
+        Ok(self
+            .read_image_pixels(target.image(), options.format())?
+            .downsampled(factor))
+    }
+
+    /// Reads back the pixels of the specified image, which is useful for
+    /// building collision masks, color picking, or saving an image which has
+    /// been drawn into using a [RenderTarget]. As with
+    /// [Graphics2D::capture], use the `format` parameter to specify the byte
+    /// layout (and size) of each pixel.
+    ///
+    /// An error is returned if the image was created in a different graphics
+    /// context.
+    pub fn read_image_pixels(
+        &mut self,
+        image: &ImageHandle,
+        format: ImageDataType
+    ) -> Result<RawBitmapData, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.read_image_pixels(image, format)
+    }
 }
 
 /// Struct representing a window.
+///
+/// Note: only one window can be created per process, as
+/// [Window::run_loop] takes over the thread and never returns. Applications
+/// needing several views (such as an editor with a separate properties panel)
+/// should currently draw them as regions of a single window. See
+/// [window::WindowId] for details.
 #[cfg(any(doc, doctest, all(feature = "windowing", not(target_arch = "wasm32"))))]
 pub struct Window<UserEventType = ()>
 where
@@ -1456,6 +3340,40 @@ impl<UserEventType: 'static> Window<UserEventType>
     /// If calling this, specify the type of the event data using
     /// `Window::<YourTypeHere>::new_with_user_events()`.
     ///
+    /// The sender can be created before the event loop starts running, and
+    /// any events sent before then will be delivered once it starts.
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::Window;
+    /// # use speedy2d::window::{WindowCreationOptions, WindowHandler, WindowHelper};
+    /// struct MyWindowHandler {}
+    ///
+    /// impl WindowHandler<String> for MyWindowHandler
+    /// {
+    ///     fn on_user_event(&mut self, helper: &mut WindowHelper<String>, event: String)
+    ///     {
+    ///         println!("Received: {}", event);
+    ///     }
+    /// }
+    ///
+    /// let window = Window::<String>::new_with_user_events(
+    ///     "Title",
+    ///     WindowCreationOptions::new_windowed(
+    ///         speedy2d::window::WindowSize::PhysicalPixels((640, 480).into()),
+    ///         None
+    ///     )
+    /// )
+    /// .unwrap();
+    ///
+    /// let sender = window.create_user_event_sender();
+    ///
+    /// std::thread::spawn(move || {
+    ///     sender.send_event("Hello from another thread".to_string()).unwrap();
+    /// });
+    ///
+    /// window.run_loop(MyWindowHandler {});
+    /// ```
+    ///
     /// See [UserEventSender::send_event], [WindowHandler::on_user_event].
     pub fn create_user_event_sender(&self) -> UserEventSender<UserEventType>
     {
@@ -14,6 +14,9 @@
  *  limitations under the License.
  */
 
+use crate::color::Color;
+use crate::dimen::Vec2;
+
 /// A trait defined for primitives which have a zero value.
 pub trait PrimitiveZero
 {
@@ -126,3 +129,206 @@ pub(crate) fn max<T: PartialOrd + Copy>(a: T, b: T) -> T
         b
     }
 }
+
+/// Types implementing this trait can be linearly interpolated between two
+/// values. This is useful for animating positions and colors between frames,
+/// optionally in combination with one of the easing functions in this module,
+/// such as [ease_in_out_cubic].
+pub trait Lerp
+{
+    /// Linearly interpolates between this value and `other`. A `t` value of
+    /// `0.0` returns this value, and `1.0` returns `other`.
+    #[must_use]
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32
+{
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self
+    {
+        *self * (1.0 - t) + *other * t
+    }
+}
+
+impl Lerp for Vec2
+{
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self
+    {
+        Vec2::lerp(self, *other, t)
+    }
+}
+
+impl Lerp for Color
+{
+    /// Interpolates between the two colors. As with [Color::lerp], `t` is
+    /// clamped to the range `0.0` to `1.0`.
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self
+    {
+        Color::lerp(self, other, t)
+    }
+}
+
+/// Accelerates from zero velocity. The input `t` is clamped to the range `0.0`
+/// to `1.0`, and the output is in the same range.
+#[inline]
+#[must_use]
+pub fn ease_in_quad(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+    t * t
+}
+
+/// Decelerates to zero velocity. The input `t` is clamped to the range `0.0`
+/// to `1.0`, and the output is in the same range.
+#[inline]
+#[must_use]
+pub fn ease_out_quad(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+    t * (2.0 - t)
+}
+
+/// Accelerates until halfway, then decelerates. The input `t` is clamped to
+/// the range `0.0` to `1.0`, and the output is in the same range.
+#[inline]
+#[must_use]
+pub fn ease_in_out_quad(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+    }
+}
+
+/// Accelerates from zero velocity, more sharply than [ease_in_quad]. The input
+/// `t` is clamped to the range `0.0` to `1.0`, and the output is in the same
+/// range.
+#[inline]
+#[must_use]
+pub fn ease_in_cubic(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+    t * t * t
+}
+
+/// Decelerates to zero velocity, more sharply than [ease_out_quad]. The input
+/// `t` is clamped to the range `0.0` to `1.0`, and the output is in the same
+/// range.
+#[inline]
+#[must_use]
+pub fn ease_out_cubic(t: f32) -> f32
+{
+    let inv = 1.0 - t.clamp(0.0, 1.0);
+    1.0 - inv * inv * inv
+}
+
+/// Accelerates until halfway, then decelerates, more sharply than
+/// [ease_in_out_quad]. The input `t` is clamped to the range `0.0` to `1.0`,
+/// and the output is in the same range.
+#[inline]
+#[must_use]
+pub fn ease_in_out_cubic(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        let inv = 1.0 - t;
+        1.0 - 4.0 * inv * inv * inv
+    }
+}
+
+/// Smooth Hermite interpolation, equivalent to the GLSL `smoothstep()`
+/// function with edges of `0.0` and `1.0`. The input `t` is clamped to the
+/// range `0.0` to `1.0`, and the output is in the same range.
+#[inline]
+#[must_use]
+pub fn smoothstep(t: f32) -> f32
+{
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    #[test]
+    fn test_easing_endpoints()
+    {
+        let functions: [fn(f32) -> f32; 7] = [
+            ease_in_quad,
+            ease_out_quad,
+            ease_in_out_quad,
+            ease_in_cubic,
+            ease_out_cubic,
+            ease_in_out_cubic,
+            smoothstep
+        ];
+
+        for function in functions.iter() {
+            assert_eq!(0.0, function(0.0));
+            assert_eq!(1.0, function(1.0));
+            assert_eq!(0.0, function(-1.0));
+            assert_eq!(1.0, function(2.0));
+        }
+    }
+
+    #[test]
+    fn test_easing_midpoint()
+    {
+        assert_eq!(0.25, ease_in_quad(0.5));
+        assert_eq!(0.75, ease_out_quad(0.5));
+        assert_eq!(0.5, ease_in_out_quad(0.5));
+        assert_eq!(0.125, ease_in_cubic(0.5));
+        assert_eq!(0.875, ease_out_cubic(0.5));
+        assert_eq!(0.5, ease_in_out_cubic(0.5));
+        assert_eq!(0.5, smoothstep(0.5));
+
+        assert_eq!(0.03125, ease_in_out_quad(0.125));
+        assert_eq!(0.0625, ease_in_out_cubic(0.25));
+        assert_eq!(0.9375, ease_in_out_cubic(0.75));
+    }
+
+    #[test]
+    fn test_lerp()
+    {
+        assert_eq!(10.0, Lerp::lerp(&10.0, &20.0, 0.0));
+        assert_eq!(20.0, Lerp::lerp(&10.0, &20.0, 1.0));
+        assert_eq!(15.0, Lerp::lerp(&10.0, &20.0, 0.5));
+
+        let start = Vec2::new(0.0, 10.0);
+        let end = Vec2::new(10.0, 30.0);
+
+        assert_eq!(start, Lerp::lerp(&start, &end, 0.0));
+        assert_eq!(end, Lerp::lerp(&start, &end, 1.0));
+        assert_eq!(Vec2::new(5.0, 20.0), Lerp::lerp(&start, &end, 0.5));
+
+        assert_eq!(Color::BLACK, Lerp::lerp(&Color::BLACK, &Color::WHITE, 0.0));
+        assert_eq!(Color::WHITE, Lerp::lerp(&Color::BLACK, &Color::WHITE, 1.0));
+        assert_eq!(
+            Color::from_rgb(0.5, 0.5, 0.5),
+            Lerp::lerp(&Color::BLACK, &Color::WHITE, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_lerp_with_easing()
+    {
+        let start = Vec2::new(0.0, 0.0);
+        let end = Vec2::new(100.0, 200.0);
+
+        assert_eq!(
+            Vec2::new(87.5, 175.0),
+            Lerp::lerp(&start, &end, ease_out_cubic(0.5))
+        );
+    }
+}
@@ -18,21 +18,32 @@ use std::rc::Rc;
 
 #[cfg(any(feature = "image-loading", doc, doctest))]
 use {
-    crate::image::ImageFileFormat,
-    image::GenericImageView,
+    crate::image::{ColorKey, ImageFileFormat},
+    image::{AnimationDecoder, GenericImageView},
     std::fs::File,
-    std::io::{BufRead, BufReader, Seek},
-    std::path::Path
+    std::io::{BufRead, BufReader, Cursor, Seek},
+    std::path::Path,
+    std::time::Duration
 };
 
 use crate::color::Color;
-use crate::dimen::{UVec2, Vec2};
+use crate::dimen::{Matrix3, UVec2, Vec2};
 use crate::error::{BacktraceError, Context, ErrorMessage};
 use crate::font::{FormattedGlyph, FormattedTextBlock};
 use crate::font_cache::GlyphCache;
 use crate::glwrapper::*;
-use crate::image::{ImageDataType, ImageHandle, ImageSmoothingMode};
-use crate::{Polygon, RawBitmapData, Rect, Rectangle};
+use crate::image::{
+    ImageAtlas,
+    ImageDataType,
+    ImageHandle,
+    ImageSmoothingMode,
+    RenderTarget,
+    TextureWrapMode
+};
+use crate::numeric::RoundFloat;
+use crate::shader::Shader;
+use crate::texture_packer::{TexturePacker, TexturePackerError};
+use crate::{BlendMode, Polygon, RawBitmapData, Rect, Rectangle};
 
 struct AttributeBuffers
 {
@@ -42,6 +53,10 @@ struct AttributeBuffers
     texture_mix: Vec<f32>,
     circle_mix: Vec<f32>,
 
+    // When writing to an sRGB framebuffer, colors must be converted to
+    // linear space before being passed to the shader
+    linear_colors: bool,
+
     glbuf_position: GLBuffer,
     glbuf_color: GLBuffer,
     glbuf_texture_coord: GLBuffer,
@@ -63,6 +78,8 @@ impl AttributeBuffers
             texture_mix: Vec::new(),
             circle_mix: Vec::new(),
 
+            linear_colors: false,
+
             glbuf_position: context
                 .new_buffer(
                     GLBufferTarget::Array,
@@ -175,48 +192,91 @@ impl AttributeBuffers
     }
 }
 
-struct Uniforms
+#[derive(Debug)]
+pub(crate) struct Uniforms
 {
-    scale_x: GLUniformHandle,
-    scale_y: GLUniformHandle,
-    texture: GLUniformHandle
+    scale_x: Option<GLUniformHandle>,
+    scale_y: Option<GLUniformHandle>,
+    offset_y: Option<GLUniformHandle>,
+    depth: Option<GLUniformHandle>,
+    texture: Option<GLUniformHandle>,
+    discard_transparent: Option<GLUniformHandle>
 }
 
 impl Uniforms
 {
+    /// If `required` is false, any uniforms which are missing from the program
+    /// are ignored. This is needed for custom shaders, as the GL driver
+    /// removes uniforms which the shader doesn't use.
     fn new(
         context: &GLContextManager,
-        program: &Rc<GLProgram>
+        program: &Rc<GLProgram>,
+        required: bool
     ) -> Result<Uniforms, BacktraceError<ErrorMessage>>
     {
+        let find = |name: &str| match program.get_uniform_handle(context, name) {
+            Ok(handle) => Ok(Some(handle)),
+            Err(_) if !required => Ok(None),
+            Err(err) => Err(err.context(format!("Failed to find {name} uniform")))
+        };
+
         Ok(Uniforms {
-            scale_x: program
-                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_SCALE_X)
-                .context("Failed to find SCALE_X uniform")?,
-            scale_y: program
-                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_SCALE_Y)
-                .context("Failed to find SCALE_Y uniform")?,
-            texture: program
-                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TEXTURE)
-                .context("Failed to find TEXTURE uniform")?
+            scale_x: find(Renderer2D::UNIFORM_NAME_SCALE_X)?,
+            scale_y: find(Renderer2D::UNIFORM_NAME_SCALE_Y)?,
+            offset_y: find(Renderer2D::UNIFORM_NAME_OFFSET_Y)?,
+            depth: find(Renderer2D::UNIFORM_NAME_DEPTH)?,
+            texture: find(Renderer2D::UNIFORM_NAME_TEXTURE)?,
+            discard_transparent: find(Renderer2D::UNIFORM_NAME_DISCARD_TRANSPARENT)?
         })
     }
 
+    /// If `flip_y` is true, the top row of pixels is mapped to the bottom of
+    /// the viewport. This is needed when drawing into a texture, as GL
+    /// framebuffers store their bottom row first.
     fn set_viewport_size_pixels(
         &self,
         context: &GLContextManager,
-        viewport_size_pixels: UVec2
+        viewport_size_pixels: UVec2,
+        flip_y: bool
     )
     {
-        self.scale_x
-            .set_value_float(context, 2.0 / viewport_size_pixels.x as f32);
-        self.scale_y
-            .set_value_float(context, -2.0 / viewport_size_pixels.y as f32);
+        let direction_y = if flip_y { 1.0 } else { -1.0 };
+
+        if let Some(scale_x) = &self.scale_x {
+            scale_x.set_value_float(context, 2.0 / viewport_size_pixels.x as f32);
+        }
+
+        if let Some(scale_y) = &self.scale_y {
+            scale_y.set_value_float(
+                context,
+                direction_y * 2.0 / viewport_size_pixels.y as f32
+            );
+        }
+
+        if let Some(offset_y) = &self.offset_y {
+            offset_y.set_value_float(context, -direction_y);
+        }
+    }
+
+    fn set_depth(&self, context: &GLContextManager, depth: f32)
+    {
+        if let Some(handle) = &self.depth {
+            handle.set_value_float(context, depth);
+        }
     }
 
     fn set_texture_unit(&self, context: &GLContextManager, texture_unit: i32)
     {
-        self.texture.set_value_int(context, texture_unit);
+        if let Some(texture) = &self.texture {
+            texture.set_value_int(context, texture_unit);
+        }
+    }
+
+    fn set_discard_transparent(&self, context: &GLContextManager, discard: bool)
+    {
+        if let Some(handle) = &self.discard_transparent {
+            handle.set_value_float(context, if discard { 1.0 } else { 0.0 });
+        }
     }
 }
 
@@ -232,11 +292,25 @@ pub(crate) struct Renderer2DVertex
 impl Renderer2DVertex
 {
     #[inline]
-    fn append_to_attribute_buffers(&self, attribute_buffers: &mut AttributeBuffers)
+    fn append_to_attribute_buffers(
+        &self,
+        attribute_buffers: &mut AttributeBuffers,
+        premultiply_color: bool
+    )
     {
+        let color = match attribute_buffers.linear_colors {
+            true => self.color.to_linear(),
+            false => self.color
+        };
+
+        let color = match premultiply_color {
+            true => color.premultiplied(),
+            false => color
+        };
+
         attribute_buffers.append(
             &self.position,
-            &self.color,
+            &color,
             &self.texture_coord,
             self.texture_mix,
             self.circle_mix
@@ -247,11 +321,24 @@ impl Renderer2DVertex
 pub(crate) struct Renderer2DAction
 {
     pub texture: Option<GLTexture>,
+    pub texture_premultiplied_alpha: bool,
     pub vertices_clockwise: [Renderer2DVertex; 3]
 }
 
 impl Renderer2DAction
 {
+    #[inline]
+    fn with_transform(mut self, transform: Option<&Matrix3>) -> Self
+    {
+        if let Some(transform) = transform {
+            for vertex in self.vertices_clockwise.iter_mut() {
+                vertex.position = transform.transform_point(vertex.position);
+            }
+        }
+
+        self
+    }
+
     #[inline]
     fn update_current_texture_if_empty(
         &self,
@@ -272,10 +359,14 @@ impl Renderer2DAction
     }
 
     #[inline]
-    fn append_to_attribute_buffers(&self, attribute_buffers: &mut AttributeBuffers)
+    fn append_to_attribute_buffers(
+        &self,
+        attribute_buffers: &mut AttributeBuffers,
+        premultiply_color: bool
+    )
     {
         for vertex in self.vertices_clockwise.iter() {
-            vertex.append_to_attribute_buffers(attribute_buffers);
+            vertex.append_to_attribute_buffers(attribute_buffers, premultiply_color);
         }
     }
 }
@@ -286,7 +377,8 @@ enum RenderQueueItem
     {
         position: Vec2,
         color: Color,
-        block: FormattedTextBlock
+        block: FormattedTextBlock,
+        transform: Option<Matrix3>
     },
 
     FormattedTextGlyph
@@ -294,7 +386,8 @@ enum RenderQueueItem
         position: Vec2,
         color: Color,
         glyph: FormattedGlyph,
-        crop_window: Rect
+        crop_window: Rect,
+        transform: Option<Matrix3>
     },
 
     CircleSectionColored
@@ -315,7 +408,8 @@ enum RenderQueueItem
         vertex_positions_clockwise: [Vec2; 3],
         vertex_colors_clockwise: [Color; 3],
         vertex_texture_coords_clockwise: [Vec2; 3],
-        texture: GLTexture
+        texture: GLTexture,
+        texture_premultiplied_alpha: bool
     }
 }
 
@@ -332,12 +426,21 @@ impl RenderQueueItem
             RenderQueueItem::FormattedTextBlock {
                 position,
                 color,
-                block
+                block,
+                transform
             } => {
+                let runner = &mut |action| {
+                    runner(Renderer2DAction::with_transform(action, transform.as_ref()))
+                };
+
                 for line in block.iter_lines() {
                     for glyph in line.iter_glyphs() {
                         glyph_cache.get_renderer2d_actions(
-                            glyph, *position, *color, None, runner
+                            glyph,
+                            *position,
+                            glyph.color().unwrap_or(*color),
+                            None,
+                            runner
                         );
                     }
                 }
@@ -347,14 +450,20 @@ impl RenderQueueItem
                 glyph,
                 position,
                 color,
-                crop_window
+                crop_window,
+                transform
             } => {
                 glyph_cache.get_renderer2d_actions(
                     glyph,
                     *position,
                     *color,
                     Some(crop_window),
-                    runner
+                    &mut |action| {
+                        runner(Renderer2DAction::with_transform(
+                            action,
+                            transform.as_ref()
+                        ))
+                    }
                 );
             }
 
@@ -364,6 +473,7 @@ impl RenderQueueItem
                 vertex_normalized_circle_coords_clockwise
             } => runner(Renderer2DAction {
                 texture: None,
+                texture_premultiplied_alpha: false,
                 vertices_clockwise: [
                     Renderer2DVertex {
                         position: vertex_positions_clockwise[0],
@@ -394,6 +504,7 @@ impl RenderQueueItem
                 vertex_colors_clockwise
             } => runner(Renderer2DAction {
                 texture: None,
+                texture_premultiplied_alpha: false,
                 vertices_clockwise: [
                     Renderer2DVertex {
                         position: vertex_positions_clockwise[0],
@@ -423,9 +534,11 @@ impl RenderQueueItem
                 vertex_positions_clockwise,
                 vertex_colors_clockwise,
                 vertex_texture_coords_clockwise,
-                texture
+                texture,
+                texture_premultiplied_alpha
             } => runner(Renderer2DAction {
                 texture: Some(texture.clone()),
+                texture_premultiplied_alpha: *texture_premultiplied_alpha,
                 vertices_clockwise: [
                     Renderer2DVertex {
                         position: vertex_positions_clockwise[0],
@@ -454,6 +567,17 @@ impl RenderQueueItem
     }
 }
 
+struct SavedRenderTargetState
+{
+    framebuffer: Option<GLFramebuffer>,
+    viewport_size_pixels: UVec2,
+    viewport_flip_y: bool,
+    clip: Option<Rectangle<i32>>,
+    transform: Matrix3,
+    transform_stack: Vec<Matrix3>,
+    target_image: ImageHandle
+}
+
 pub struct Renderer2D
 {
     context: GLContextManager,
@@ -465,9 +589,24 @@ pub struct Renderer2D
     glyph_cache: GlyphCache,
     attribute_buffers: AttributeBuffers,
     current_texture: Option<GLTexture>,
+    blend_mode: GLBlendEnabled,
+
+    transform: Matrix3,
+    transform_stack: Vec<Matrix3>,
+
+    viewport_size_pixels: UVec2,
+    viewport_flip_y: bool,
+    clip: Option<Rectangle<i32>>,
+    render_target_stack: Vec<SavedRenderTargetState>,
+
+    shader: Option<Shader>,
+    uniforms: Uniforms,
 
-    #[allow(dead_code)]
-    uniforms: Uniforms
+    depth: Option<f32>,
+    scale_factor: f64,
+
+    draw_call_count: usize,
+    last_frame_draw_call_count: usize
 }
 
 impl Renderer2D
@@ -480,7 +619,10 @@ impl Renderer2D
 
     const UNIFORM_NAME_SCALE_X: &'static str = "in_ScaleX";
     const UNIFORM_NAME_SCALE_Y: &'static str = "in_ScaleY";
+    const UNIFORM_NAME_OFFSET_Y: &'static str = "in_OffsetY";
+    const UNIFORM_NAME_DEPTH: &'static str = "in_Depth";
     const UNIFORM_NAME_TEXTURE: &'static str = "in_Texture";
+    const UNIFORM_NAME_DISCARD_TRANSPARENT: &'static str = "in_DiscardTransparent";
 
     const ALL_ATTRIBUTES: [&'static str; 5] = [
         Renderer2D::ATTR_NAME_POSITION,
@@ -497,22 +639,8 @@ impl Renderer2D
     {
         log::info!("Creating vertex shader");
 
-        let (vertex_shader_src, fragment_shader_src) = match context.version() {
-            GLVersion::OpenGL2_0 => {
-                log::info!("Using OpenGL 2.0 shaders");
-                (
-                    include_str!("shaders/r2d_vertex_v110.glsl"),
-                    include_str!("shaders/r2d_fragment_v110.glsl")
-                )
-            }
-            GLVersion::WebGL2_0 => {
-                log::info!("Using WebGL 2.0 shaders");
-                (
-                    include_str!("shaders/r2d_vertex_v300es.glsl"),
-                    include_str!("shaders/r2d_fragment_v300es.glsl")
-                )
-            }
-        };
+        let (vertex_shader_src, fragment_shader_src) =
+            Renderer2D::builtin_shader_sources(context.version());
 
         let vertex_shader = context
             .new_shader(GLShaderType::Vertex, vertex_shader_src)
@@ -535,13 +663,13 @@ impl Renderer2D
             .context("Failed to create Renderer2D program")?;
 
         let attribute_buffers = AttributeBuffers::new(context, &program)?;
-        let uniforms = Uniforms::new(context, &program)?;
+        let uniforms = Uniforms::new(context, &program, true)?;
 
         context.use_program(&program);
 
         uniforms.set_texture_unit(context, 0);
 
-        uniforms.set_viewport_size_pixels(context, viewport_size_pixels);
+        uniforms.set_viewport_size_pixels(context, viewport_size_pixels, false);
 
         context.set_viewport_size(viewport_size_pixels);
 
@@ -552,25 +680,97 @@ impl Renderer2D
             glyph_cache: GlyphCache::new(),
             attribute_buffers,
             current_texture: None,
-            uniforms
+            blend_mode: GLBlendEnabled::Enabled(GLBlendMode::OneMinusSrcAlpha),
+            transform: Matrix3::IDENTITY,
+            transform_stack: Vec::new(),
+            viewport_size_pixels,
+            viewport_flip_y: false,
+            clip: None,
+            render_target_stack: Vec::new(),
+            shader: None,
+            uniforms,
+            depth: None,
+            scale_factor: 1.0,
+            draw_call_count: 0,
+            last_frame_draw_call_count: 0
         })
     }
 
-    pub fn set_viewport_size_pixels(&self, viewport_size_pixels: UVec2)
+    fn builtin_shader_sources(version: GLVersion) -> (&'static str, &'static str)
     {
-        self.uniforms
-            .set_viewport_size_pixels(&self.context, viewport_size_pixels);
+        match version {
+            GLVersion::OpenGL2_0 => {
+                log::info!("Using OpenGL 2.0 shaders");
+                (
+                    include_str!("shaders/r2d_vertex_v110.glsl"),
+                    include_str!("shaders/r2d_fragment_v110.glsl")
+                )
+            }
+            GLVersion::WebGL2_0 => {
+                log::info!("Using WebGL 2.0 shaders");
+                (
+                    include_str!("shaders/r2d_vertex_v300es.glsl"),
+                    include_str!("shaders/r2d_fragment_v300es.glsl")
+                )
+            }
+        }
+    }
+
+    pub fn set_viewport_size_pixels(&mut self, viewport_size_pixels: UVec2)
+    {
+        self.set_viewport(viewport_size_pixels, false);
+    }
+
+    fn set_viewport(&mut self, viewport_size_pixels: UVec2, flip_y: bool)
+    {
+        self.viewport_size_pixels = viewport_size_pixels;
+        self.viewport_flip_y = flip_y;
+
+        self.context.use_program(&self.program);
+
+        self.uniforms.set_viewport_size_pixels(
+            &self.context,
+            viewport_size_pixels,
+            flip_y
+        );
+
+        if let Some(shader) = &self.shader {
+            self.context.use_program(&shader.program);
+
+            shader.uniforms.set_viewport_size_pixels(
+                &self.context,
+                viewport_size_pixels,
+                flip_y
+            );
+        }
 
         self.context.set_viewport_size(viewport_size_pixels);
     }
 
+    pub(crate) fn set_glyph_cache_limit(&mut self, max_glyphs: usize)
+    {
+        self.glyph_cache.set_max_entries(max_glyphs);
+    }
+
+    pub(crate) fn clear_glyph_cache(&mut self)
+    {
+        self.glyph_cache.clear();
+    }
+
     pub fn finish_frame(&mut self)
     {
         self.flush_render_queue();
         self.glyph_cache.on_new_frame_start();
+        self.last_frame_draw_call_count = std::mem::take(&mut self.draw_call_count);
     }
 
-    fn flush_render_queue(&mut self)
+    #[inline]
+    pub(crate) fn last_frame_draw_call_count(&self) -> usize
+    {
+        self.last_frame_draw_call_count
+    }
+
+    pub(crate) fn flush_render_queue(&mut self)
     {
         if self.render_queue.is_empty() {
             return;
@@ -616,45 +816,107 @@ impl Renderer2D
             }
         }
 
+        // Images with premultiplied alpha are drawn in separate batches, using
+        // the premultiplied equivalent of the current blend mode. Untextured
+        // triangles can join either kind of batch, as their colors are
+        // premultiplied as they are added.
+        let premultiplied_blend_mode = self.blend_mode.for_premultiplied_alpha();
+        let mut batch_premultiplied = false;
+
         {
             let current_texture = &mut self.current_texture;
             let context = &self.context;
-            let program = &self.program;
+            let program = match &self.shader {
+                None => &self.program,
+                Some(shader) => &shader.program
+            };
+            let blend_mode = &self.blend_mode;
+            let premultiplied_blend_mode = &premultiplied_blend_mode;
+            let batch_premultiplied = &mut batch_premultiplied;
             let attribute_buffers = &mut self.attribute_buffers;
+            let draw_call_count = &mut self.draw_call_count;
 
             for item in &self.render_queue {
                 item.generate_actions(&self.glyph_cache, &mut |action| {
-                    if !action.update_current_texture_if_empty(current_texture) {
+                    let action_premultiplied = match &action.texture {
+                        None => *batch_premultiplied,
+                        Some(_) => {
+                            action.texture_premultiplied_alpha
+                                && premultiplied_blend_mode.is_some()
+                        }
+                    };
+
+                    if !action.update_current_texture_if_empty(current_texture)
+                        || action_premultiplied != *batch_premultiplied
+                    {
                         Renderer2D::draw_buffers(
                             context,
                             program,
+                            Renderer2D::batch_blend_mode(
+                                blend_mode,
+                                premultiplied_blend_mode,
+                                *batch_premultiplied
+                            ),
                             attribute_buffers,
-                            current_texture
+                            current_texture,
+                            draw_call_count
                         );
 
                         *current_texture = action.texture.clone();
+                        *batch_premultiplied = action_premultiplied;
                     }
 
-                    action.append_to_attribute_buffers(attribute_buffers);
+                    action.append_to_attribute_buffers(
+                        attribute_buffers,
+                        *batch_premultiplied
+                    );
                 });
             }
         }
 
         self.render_queue.clear();
 
+        let program = match &self.shader {
+            None => &self.program,
+            Some(shader) => &shader.program
+        };
+
         Renderer2D::draw_buffers(
             &self.context,
-            &self.program,
+            program,
+            Renderer2D::batch_blend_mode(
+                &self.blend_mode,
+                &premultiplied_blend_mode,
+                batch_premultiplied
+            ),
             &mut self.attribute_buffers,
-            &mut self.current_texture
+            &mut self.current_texture,
+            &mut self.draw_call_count
         );
     }
 
+    #[inline]
+    fn batch_blend_mode<'a>(
+        blend_mode: &'a GLBlendEnabled,
+        premultiplied_blend_mode: &'a Option<GLBlendEnabled>,
+        batch_premultiplied: bool
+    ) -> &'a GLBlendEnabled
+    {
+        match premultiplied_blend_mode {
+            Some(premultiplied_blend_mode) if batch_premultiplied => {
+                premultiplied_blend_mode
+            }
+            _ => blend_mode
+        }
+    }
+
     fn draw_buffers(
         context: &GLContextManager,
         program: &Rc<GLProgram>,
+        blend_mode: &GLBlendEnabled,
         attribute_buffers: &mut AttributeBuffers,
-        current_texture: &mut Option<GLTexture>
+        current_texture: &mut Option<GLTexture>,
+        draw_call_count: &mut usize
     )
     {
         let vertex_count = attribute_buffers.get_vertex_count();
@@ -674,10 +936,8 @@ impl Renderer2D
             Some(texture) => context.bind_texture(texture)
         }
 
-        context.draw_triangles(
-            GLBlendEnabled::Enabled(GLBlendMode::OneMinusSrcAlpha),
-            vertex_count
-        );
+        context.draw_triangles(blend_mode.clone(), vertex_count);
+        *draw_call_count += 1;
     }
 
     pub(crate) fn create_image_from_raw_pixels<S: Into<UVec2>>(
@@ -692,7 +952,7 @@ impl Renderer2D
 
         let pixel_bytes = match data_type {
             ImageDataType::RGB => 3,
-            ImageDataType::RGBA => 4
+            ImageDataType::RGBA | ImageDataType::RGBAPremultiplied => 4
         };
 
         {
@@ -714,7 +974,8 @@ impl Renderer2D
 
         let gl_smoothing = match smoothing_mode {
             ImageSmoothingMode::NearestNeighbor => GLTextureSmoothing::NearestNeighbour,
-            ImageSmoothingMode::Linear => GLTextureSmoothing::Linear
+            ImageSmoothingMode::Linear => GLTextureSmoothing::Linear,
+            ImageSmoothingMode::LinearMipmap => GLTextureSmoothing::LinearMipmap
         };
 
         let texture = self
@@ -726,7 +987,227 @@ impl Renderer2D
             .set_image_data(&self.context, gl_format, gl_smoothing, &size, data)
             .context("Failed to upload image data")?;
 
-        Ok(ImageHandle { size, texture })
+        Ok(ImageHandle {
+            size,
+            texture,
+            data_type,
+            smoothing_mode
+        })
+    }
+
+    pub(crate) fn update_image_region(
+        &mut self,
+        image: &ImageHandle,
+        offset: UVec2,
+        size: UVec2,
+        data: &[u8]
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        let fits = |offset: u32, size: u32, limit: u32| {
+            offset.checked_add(size).is_some_and(|end| end <= limit)
+        };
+
+        if !fits(offset.x, size.x, image.size.x) || !fits(offset.y, size.y, image.size.y)
+        {
+            return Err(ErrorMessage::msg(format!(
+                "Region at ({}, {}) with size {}x{} exceeds the image size of {}x{}",
+                offset.x, offset.y, size.x, size.y, image.size.x, image.size.y
+            )));
+        }
+
+        let gl_format: GLTextureImageFormatU8 = image.data_type.into();
+        let pixel_bytes = gl_format.get_bytes_per_pixel();
+        let expected_bytes = pixel_bytes * size.x as usize * size.y as usize;
+
+        if expected_bytes != data.len() {
+            return Err(ErrorMessage::msg(format!(
+                "Expecting {} bytes ({}x{}x{}), got {}",
+                expected_bytes,
+                size.x,
+                size.y,
+                pixel_bytes,
+                data.len()
+            )));
+        }
+
+        if expected_bytes == 0 {
+            return Ok(());
+        }
+
+        // Any queued drawing operations must use the previous image contents
+        self.flush_render_queue();
+
+        image
+            .texture
+            .set_image_sub_data(&self.context, gl_format, &offset, &size, data)
+            .context("Failed to upload image data")?;
+
+        if image.smoothing_mode == ImageSmoothingMode::LinearMipmap {
+            image.texture.generate_mipmaps(&self.context);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn set_image_wrap_mode(
+        &mut self,
+        image: &ImageHandle,
+        wrap_mode: TextureWrapMode
+    )
+    {
+        // Any queued drawing operations must use the previous wrap mode
+        self.flush_render_queue();
+
+        image.texture.set_wrap(
+            &self.context,
+            match wrap_mode {
+                TextureWrapMode::ClampToEdge => GLTextureWrap::ClampToEdge,
+                TextureWrapMode::Repeat => GLTextureWrap::Repeat,
+                TextureWrapMode::MirrorRepeat => GLTextureWrap::MirroredRepeat
+            }
+        );
+    }
+
+    pub(crate) fn create_image_atlas<S: Into<UVec2>>(
+        &self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: S
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        let size = size.into();
+
+        let gl_format: GLTextureImageFormatU8 = data_type.into();
+        let pixel_bytes = gl_format.get_bytes_per_pixel();
+
+        let image = self.create_image_from_raw_pixels(
+            data_type,
+            smoothing_mode,
+            size,
+            &vec![0; size.x as usize * size.y as usize * pixel_bytes]
+        )?;
+
+        Ok(ImageAtlas {
+            image,
+            packer: TexturePacker::new(size.x, size.y)
+        })
+    }
+
+    pub(crate) fn add_image_to_atlas(
+        &mut self,
+        atlas: &mut ImageAtlas,
+        size: UVec2,
+        data: &[u8]
+    ) -> Result<Rect, BacktraceError<ErrorMessage>>
+    {
+        let gl_format: GLTextureImageFormatU8 = atlas.image.data_type.into();
+        let pixel_bytes = gl_format.get_bytes_per_pixel();
+        let expected_bytes = pixel_bytes * size.x as usize * size.y as usize;
+
+        // Check this before allocating space, so that it isn't wasted
+        if expected_bytes != data.len() {
+            return Err(ErrorMessage::msg(format!(
+                "Expecting {} bytes ({}x{}x{}), got {}",
+                expected_bytes,
+                size.x,
+                size.y,
+                pixel_bytes,
+                data.len()
+            )));
+        }
+
+        let region = atlas.packer.try_allocate(size).map_err(|err| match err {
+            TexturePackerError::NotEnoughSpace => ErrorMessage::msg(format!(
+                "Not enough space in the {}x{} atlas for an image of size {}x{}",
+                atlas.size().x,
+                atlas.size().y,
+                size.x,
+                size.y
+            ))
+        })?;
+
+        self.update_image_region(&atlas.image, *region.top_left(), size, data)?;
+
+        let atlas_size = atlas.size().into_f32();
+        let normalize = |point: &UVec2| {
+            Vec2::new(point.x as f32 / atlas_size.x, point.y as f32 / atlas_size.y)
+        };
+
+        Ok(Rect::new(
+            normalize(region.top_left()),
+            normalize(region.bottom_right())
+        ))
+    }
+
+    pub(crate) fn create_render_target<S: Into<UVec2>>(
+        &self,
+        smoothing_mode: ImageSmoothingMode,
+        size: S
+    ) -> Result<RenderTarget, BacktraceError<ErrorMessage>>
+    {
+        let size = size.into();
+
+        let image = self.create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            smoothing_mode,
+            size,
+            &vec![0; size.x as usize * size.y as usize * 4]
+        )?;
+
+        let framebuffer = self
+            .context
+            .new_framebuffer(image.texture.clone())
+            .context("Failed to create framebuffer")?;
+
+        Ok(RenderTarget { image, framebuffer })
+    }
+
+    /// Redirects all drawing to the specified render target, until
+    /// `end_render_target()` is called. The transform stack and clip area are
+    /// reset while drawing to the target, and restored afterwards.
+    pub(crate) fn begin_render_target(&mut self, target: &RenderTarget)
+    {
+        self.flush_render_queue();
+
+        let framebuffer = self.context.bind_framebuffer(Some(&target.framebuffer));
+
+        self.render_target_stack.push(SavedRenderTargetState {
+            framebuffer,
+            viewport_size_pixels: self.viewport_size_pixels,
+            viewport_flip_y: self.viewport_flip_y,
+            clip: self.clip.clone(),
+            transform: self.transform,
+            transform_stack: std::mem::take(&mut self.transform_stack),
+            target_image: target.image.clone()
+        });
+
+        self.transform = Matrix3::IDENTITY;
+        self.set_viewport(*target.size(), true);
+        self.set_clip(None);
+    }
+
+    pub(crate) fn end_render_target(&mut self)
+    {
+        self.flush_render_queue();
+
+        let saved = match self.render_target_stack.pop() {
+            None => {
+                log::warn!("Ignoring call to end_render_target(): no target active");
+                return;
+            }
+            Some(saved) => saved
+        };
+
+        self.context.bind_framebuffer(saved.framebuffer.as_ref());
+
+        if saved.target_image.smoothing_mode == ImageSmoothingMode::LinearMipmap {
+            saved.target_image.texture.generate_mipmaps(&self.context);
+        }
+
+        self.transform = saved.transform;
+        self.transform_stack = saved.transform_stack;
+        self.set_viewport(saved.viewport_size_pixels, saved.viewport_flip_y);
+        self.set_clip(saved.clip);
     }
 
     #[cfg(any(feature = "image-loading", doc, doctest))]
@@ -753,42 +1234,113 @@ impl Renderer2D
         file_bytes: R
     ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
     {
-        let mut reader = image::io::Reader::new(file_bytes);
+        let bitmap = decode_image_file_bytes(data_type, file_bytes)?;
 
-        match data_type {
-            None => {
-                reader = reader
-                    .with_guessed_format()
-                    .context("Could not guess file format")?
+        self.create_image_from_raw_pixels(
+            bitmap.format(),
+            smoothing_mode,
+            bitmap.size(),
+            bitmap.data()
+        )
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_bytes_with_color_key<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R,
+        color_key: &ColorKey
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let bitmap = decode_image_file_bytes(data_type, file_bytes)?;
+        let (data_type, data) = color_key.apply(bitmap.format(), bitmap.data());
+
+        self.create_image_from_raw_pixels(data_type, smoothing_mode, bitmap.size(), &data)
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_sequence_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        mut file_bytes: R
+    ) -> Result<Vec<(ImageHandle, Duration)>, BacktraceError<ErrorMessage>>
+    {
+        let mut bytes = Vec::new();
+
+        file_bytes
+            .read_to_end(&mut bytes)
+            .context("Failed to read image data")?;
+
+        let format = match data_type {
+            Some(format) => to_image_crate_format(format),
+            None => image::guess_format(&bytes).context("Could not guess file format")?
+        };
+
+        let frames = match format {
+            image::ImageFormat::Gif => Some(
+                image::codecs::gif::GifDecoder::new(Cursor::new(&bytes))
+                    .context("Failed to parse GIF data")?
+                    .into_frames()
+            ),
+
+            image::ImageFormat::Png => {
+                let decoder = image::codecs::png::PngDecoder::new(Cursor::new(&bytes))
+                    .context("Failed to parse PNG data")?;
+
+                if decoder.is_apng() {
+                    Some(decoder.apng().into_frames())
+                } else {
+                    None
+                }
             }
-            Some(format) => reader.set_format(match format {
-                ImageFileFormat::PNG => image::ImageFormat::Png,
-                ImageFileFormat::JPEG => image::ImageFormat::Jpeg,
-                ImageFileFormat::GIF => image::ImageFormat::Gif,
-                ImageFileFormat::BMP => image::ImageFormat::Bmp,
-                ImageFileFormat::ICO => image::ImageFormat::Ico,
-                ImageFileFormat::TIFF => image::ImageFormat::Tiff,
-                ImageFileFormat::WebP => image::ImageFormat::WebP,
-                ImageFileFormat::AVIF => image::ImageFormat::Avif,
-                ImageFileFormat::PNM => image::ImageFormat::Pnm,
-                ImageFileFormat::DDS => image::ImageFormat::Dds,
-                ImageFileFormat::TGA => image::ImageFormat::Tga,
-                ImageFileFormat::Farbfeld => image::ImageFormat::Farbfeld
-            })
-        }
 
-        let image = reader.decode().context("Failed to parse image data")?;
+            _ => None
+        };
 
-        let dimensions = image.dimensions();
+        let frames = match frames {
+            Some(frames) => frames
+                .collect_frames()
+                .context("Failed to decode animation frames")?,
 
-        let bytes_rgba8 = image.into_rgba8().into_raw();
+            None => {
+                let image = self.create_image_from_file_bytes(
+                    data_type,
+                    smoothing_mode,
+                    Cursor::new(&bytes)
+                )?;
 
-        self.create_image_from_raw_pixels(
-            ImageDataType::RGBA,
-            smoothing_mode,
-            dimensions,
-            bytes_rgba8.as_slice()
-        )
+                return Ok(vec![(image, Duration::from_secs(0))]);
+            }
+        };
+
+        frames
+            .into_iter()
+            .map(|frame| {
+                let (numerator_ms, denominator_ms) = frame.delay().numer_denom_ms();
+
+                let delay = if denominator_ms == 0 {
+                    Duration::from_secs(0)
+                } else {
+                    Duration::from_nanos(
+                        numerator_ms as u64 * 1_000_000 / denominator_ms as u64
+                    )
+                };
+
+                let buffer = frame.into_buffer();
+                let dimensions = buffer.dimensions();
+
+                let image = self.create_image_from_raw_pixels(
+                    ImageDataType::RGBA,
+                    smoothing_mode,
+                    dimensions,
+                    buffer.into_raw().as_slice()
+                )?;
+
+                Ok((image, delay))
+            })
+            .collect()
     }
 
     #[inline]
@@ -838,6 +1390,9 @@ impl Renderer2D
         vertex_colors_clockwise: [Color; 3]
     )
     {
+        let vertex_positions_clockwise =
+            self.transform_vertices(vertex_positions_clockwise);
+
         self.add_to_render_queue(RenderQueueItem::TriangleColored {
             vertex_positions_clockwise,
             vertex_colors_clockwise
@@ -853,11 +1408,15 @@ impl Renderer2D
         image: &ImageHandle
     )
     {
+        let vertex_positions_clockwise =
+            self.transform_vertices(vertex_positions_clockwise);
+
         self.add_to_render_queue(RenderQueueItem::TriangleTextured {
             vertex_positions_clockwise,
             vertex_colors_clockwise,
             vertex_texture_coords_clockwise,
-            texture: image.texture.clone()
+            texture: image.texture.clone(),
+            texture_premultiplied_alpha: image.is_premultiplied_alpha()
         })
     }
 
@@ -867,13 +1426,25 @@ impl Renderer2D
         position: V,
         color: Color,
         text: &FormattedTextBlock
-    )
+    ) -> Vec2
     {
+        let local_position = self.snap_text_position(position.into(), text);
+        let (position, transform) = self.transform_text_position(local_position);
+
         self.add_to_render_queue(RenderQueueItem::FormattedTextBlock {
-            position: position.into(),
+            position,
             color,
-            block: text.clone()
-        })
+            block: text.clone(),
+            transform
+        });
+
+        for line in text.iter_lines() {
+            for rect in line.decoration_rects() {
+                self.draw_text_decoration(rect.with_offset(local_position), color);
+            }
+        }
+
+        local_position
     }
 
     #[inline]
@@ -885,25 +1456,68 @@ impl Renderer2D
         text: &FormattedTextBlock
     )
     {
-        let position = position.into();
+        let local_position = self.snap_text_position(position.into(), text);
+        let (position, transform) = self.transform_text_position(local_position);
+
+        let crop_window_transformed = match transform {
+            None => crop_window.with_offset(position - local_position),
+            Some(_) => crop_window.clone()
+        };
 
         for line in text.iter_lines() {
             for glyph in line.iter_glyphs() {
                 if let Some(glyph_outline) = glyph.pixel_bounding_box() {
-                    let glyph_outline = glyph_outline.with_offset(position);
+                    let glyph_outline = glyph_outline.with_offset(local_position);
                     if glyph_outline.intersect(&crop_window).is_some() {
                         self.add_to_render_queue(RenderQueueItem::FormattedTextGlyph {
                             position,
-                            color,
+                            color: glyph.color().unwrap_or(color),
                             glyph: glyph.clone(),
-                            crop_window: crop_window.clone()
+                            crop_window: crop_window_transformed.clone(),
+                            transform
                         })
                     }
                 }
             }
+
+            for rect in line.decoration_rects() {
+                if let Some(rect) =
+                    rect.with_offset(local_position).intersect(&crop_window)
+                {
+                    self.draw_text_decoration(rect, color);
+                }
+            }
         }
     }
 
+    /// Draws an underline or strikethrough as a solid rectangle.
+    fn draw_text_decoration(&mut self, rect: Rect, color: Color)
+    {
+        let colors = [color, color, color];
+
+        let vertex_positions_clockwise = self.transform_vertices([
+            *rect.top_left(),
+            rect.top_right(),
+            *rect.bottom_right()
+        ]);
+
+        self.add_to_render_queue(RenderQueueItem::TriangleColored {
+            vertex_positions_clockwise,
+            vertex_colors_clockwise: colors
+        });
+
+        let vertex_positions_clockwise = self.transform_vertices([
+            *rect.bottom_right(),
+            rect.bottom_left(),
+            *rect.top_left()
+        ]);
+
+        self.add_to_render_queue(RenderQueueItem::TriangleColored {
+            vertex_positions_clockwise,
+            vertex_colors_clockwise: colors
+        });
+    }
+
     #[inline]
     pub(crate) fn draw_circle_section(
         &mut self,
@@ -912,6 +1526,9 @@ impl Renderer2D
         vertex_normalized_circle_coords_clockwise: [Vec2; 3]
     )
     {
+        let vertex_positions_clockwise =
+            self.transform_vertices(vertex_positions_clockwise);
+
         self.add_to_render_queue(RenderQueueItem::CircleSectionColored {
             vertex_positions_clockwise,
             vertex_colors_clockwise,
@@ -925,6 +1542,7 @@ impl Renderer2D
         // If we change the clip area, we need to draw everything in a queue
         // through the current clip before setting new one.
         self.flush_render_queue();
+        self.clip = rect.clone();
         match rect {
             None => self.context.set_enable_scissor(false),
             Some(rect) => {
@@ -939,9 +1557,373 @@ impl Renderer2D
         }
     }
 
+    #[inline]
+    pub(crate) fn push_transform(&mut self, transform: Matrix3)
+    {
+        self.transform_stack.push(self.transform);
+        self.transform = self.transform * transform;
+    }
+
+    #[inline]
+    pub(crate) fn pop_transform(&mut self)
+    {
+        match self.transform_stack.pop() {
+            None => log::warn!("Ignoring call to pop_transform(): the stack is empty"),
+            Some(transform) => self.transform = transform
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reset_transform(&mut self)
+    {
+        self.transform_stack.clear();
+        self.transform = Matrix3::IDENTITY;
+    }
+
+    #[inline]
+    fn transform_vertices(&self, vertices: [Vec2; 3]) -> [Vec2; 3]
+    {
+        if self.transform == Matrix3::IDENTITY {
+            return vertices;
+        }
+
+        vertices.map(|vertex| self.transform.transform_point(vertex))
+    }
+
+    /// If the text uses pixel snapping, adjusts the position so that the text
+    /// is drawn at a whole pixel position on the screen.
+    #[inline]
+    fn snap_text_position(&self, position: Vec2, text: &FormattedTextBlock) -> Vec2
+    {
+        if !text.is_pixel_snapped() {
+            return position;
+        }
+
+        snap_to_pixel(position, &self.transform)
+    }
+
+    /// Text is rasterized on the CPU, so where possible we apply the transform
+    /// to the position instead, to avoid resampling the glyphs.
+    #[inline]
+    fn transform_text_position(&self, position: Vec2) -> (Vec2, Option<Matrix3>)
+    {
+        match self.transform.as_translation() {
+            Some(offset) => (position + offset, None),
+            None => (position, Some(self.transform))
+        }
+    }
+
+    pub(crate) fn set_srgb_framebuffer_enabled(
+        &mut self,
+        enabled: bool
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        // Anything already in the queue must be drawn using the previous setting
+        self.flush_render_queue();
+        self.context.set_srgb_framebuffer_enabled(enabled)?;
+        self.attribute_buffers.linear_colors = enabled;
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn is_srgb_framebuffer_enabled(&self) -> bool
+    {
+        self.context.is_srgb_framebuffer_enabled()
+    }
+
+    #[inline]
+    pub(crate) fn set_blend_mode(&mut self, mode: BlendMode)
+    {
+        let blend_mode = match mode {
+            BlendMode::Normal => GLBlendEnabled::Enabled(GLBlendMode::OneMinusSrcAlpha),
+            BlendMode::Additive => GLBlendEnabled::Enabled(GLBlendMode::Additive),
+            BlendMode::Multiply => GLBlendEnabled::Enabled(GLBlendMode::Multiply),
+            BlendMode::None => GLBlendEnabled::Disabled
+        };
+
+        if blend_mode == self.blend_mode {
+            return;
+        }
+
+        // Anything already in the queue must be drawn using the previous mode
+        self.flush_render_queue();
+        self.blend_mode = blend_mode;
+
+        let discard_transparent = self.discards_transparent_pixels();
+
+        self.context.use_program(&self.program);
+        self.uniforms
+            .set_discard_transparent(&self.context, discard_transparent);
+
+        if let Some(shader) = &self.shader {
+            self.context.use_program(&shader.program);
+            shader
+                .uniforms
+                .set_discard_transparent(&self.context, discard_transparent);
+        }
+    }
+
+    /// Multiplicative blending ignores the source alpha, so fully transparent
+    /// pixels must be discarded to avoid affecting the output. Other modes
+    /// draw them as normal, so that blending can be disabled to write
+    /// transparent pixels.
+    #[inline]
+    fn discards_transparent_pixels(&self) -> bool
+    {
+        self.blend_mode == GLBlendEnabled::Enabled(GLBlendMode::Multiply)
+    }
+
+    pub(crate) fn create_shader(
+        &self,
+        vertex_source: Option<&str>,
+        fragment_source: &str
+    ) -> Result<Shader, BacktraceError<ErrorMessage>>
+    {
+        let vertex_source = match vertex_source {
+            None => Renderer2D::builtin_shader_sources(self.context.version()).0,
+            Some(source) => source
+        };
+
+        let vertex_shader = self
+            .context
+            .new_shader(GLShaderType::Vertex, vertex_source)
+            .context("Failed to compile custom vertex shader")?;
+
+        let fragment_shader = self
+            .context
+            .new_shader(GLShaderType::Fragment, fragment_source)
+            .context("Failed to compile custom fragment shader")?;
+
+        let program = self
+            .context
+            .new_program(
+                &vertex_shader,
+                &fragment_shader,
+                &Renderer2D::ALL_ATTRIBUTES
+            )
+            .context("Failed to link custom shader program")?;
+
+        let uniforms = Uniforms::new(&self.context, &program, false)?;
+
+        self.context.use_program(&program);
+        uniforms.set_texture_unit(&self.context, 0);
+
+        Ok(Shader {
+            program,
+            uniforms: Rc::new(uniforms)
+        })
+    }
+
+    #[inline]
+    pub(crate) fn set_shader(&mut self, shader: Option<&Shader>)
+    {
+        if self.shader.as_ref().map(|shader| &shader.program)
+            == shader.map(|shader| &shader.program)
+        {
+            return;
+        }
+
+        // Anything already in the queue must be drawn using the previous shader
+        self.flush_render_queue();
+
+        if let Some(shader) = shader {
+            self.context.use_program(&shader.program);
+
+            shader.uniforms.set_viewport_size_pixels(
+                &self.context,
+                self.viewport_size_pixels,
+                self.viewport_flip_y
+            );
+
+            shader
+                .uniforms
+                .set_depth(&self.context, self.depth.unwrap_or(0.0));
+
+            shader.uniforms.set_discard_transparent(
+                &self.context,
+                self.discards_transparent_pixels()
+            );
+        }
+
+        self.shader = shader.cloned();
+    }
+
+    pub(crate) fn set_depth(&mut self, depth: Option<f32>)
+    {
+        let depth = depth.map(|depth| depth.clamp(0.0, 1.0));
+
+        if depth == self.depth {
+            return;
+        }
+
+        // Anything already in the queue must be drawn at the previous depth
+        self.flush_render_queue();
+        self.depth = depth;
+
+        let value = depth.unwrap_or(0.0);
+
+        self.context.use_program(&self.program);
+        self.uniforms.set_depth(&self.context, value);
+
+        if let Some(shader) = &self.shader {
+            self.context.use_program(&shader.program);
+            shader.uniforms.set_depth(&self.context, value);
+        }
+
+        self.context.set_enable_depth_test(depth.is_some());
+    }
+
+    #[inline]
+    pub(crate) fn get_depth(&self) -> Option<f32>
+    {
+        self.depth
+    }
+
+    #[inline]
+    pub(crate) fn set_scale_factor(&mut self, scale_factor: f64)
+    {
+        self.scale_factor = scale_factor;
+    }
+
+    #[inline]
+    pub(crate) fn get_scale_factor(&self) -> f64
+    {
+        self.scale_factor
+    }
+
+    pub(crate) fn set_shader_uniform<F>(
+        &mut self,
+        shader: &Shader,
+        name: &str,
+        setter: F
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    where
+        F: FnOnce(&GLUniformHandle, &GLContextManager)
+    {
+        let handle = shader
+            .program
+            .get_uniform_handle(&self.context, name)
+            .context(format!("Failed to find uniform '{name}' in shader"))?;
+
+        // Anything already in the queue must be drawn using the previous value
+        if self.shader.as_ref().map(|shader| &shader.program) == Some(&shader.program) {
+            self.flush_render_queue();
+        }
+
+        self.context.use_program(&shader.program);
+        setter(&handle, &self.context);
+
+        Ok(())
+    }
+
     pub(crate) fn capture(&mut self, format: ImageDataType) -> RawBitmapData
     {
         self.flush_render_queue();
         self.context.capture(format)
     }
+
+    #[inline]
+    pub(crate) fn max_texture_size(&self) -> u32
+    {
+        self.context.max_texture_size()
+    }
+
+    pub(crate) fn read_image_pixels(
+        &mut self,
+        image: &ImageHandle,
+        format: ImageDataType
+    ) -> Result<RawBitmapData, BacktraceError<ErrorMessage>>
+    {
+        self.flush_render_queue();
+        self.context
+            .read_texture(&image.texture, image.size, format)
+    }
+}
+
+/// Decodes an image file, returning its pixels in `RGBA` format.
+#[cfg(any(feature = "image-loading", doc, doctest))]
+fn decode_image_file_bytes<R: Seek + BufRead>(
+    data_type: Option<ImageFileFormat>,
+    file_bytes: R
+) -> Result<RawBitmapData, BacktraceError<ErrorMessage>>
+{
+    let mut reader = image::io::Reader::new(file_bytes);
+
+    match data_type {
+        None => {
+            reader = reader
+                .with_guessed_format()
+                .context("Could not guess file format")?
+        }
+        Some(format) => reader.set_format(to_image_crate_format(format))
+    }
+
+    let image = reader.decode().context("Failed to parse image data")?;
+
+    let dimensions = image.dimensions();
+
+    Ok(RawBitmapData::new(
+        image.into_rgba8().into_raw(),
+        dimensions,
+        ImageDataType::RGBA
+    ))
+}
+
+/// Adjusts the position so that, once the transform is applied, it lands on
+/// a whole pixel. Transforms other than translations are left unchanged, as
+/// there is no single whole pixel position to snap to.
+fn snap_to_pixel(position: Vec2, transform: &Matrix3) -> Vec2
+{
+    match transform.as_translation() {
+        Some(offset) => (position + offset).round() - offset,
+        None => position
+    }
+}
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+fn to_image_crate_format(format: ImageFileFormat) -> image::ImageFormat
+{
+    match format {
+        ImageFileFormat::PNG => image::ImageFormat::Png,
+        ImageFileFormat::JPEG => image::ImageFormat::Jpeg,
+        ImageFileFormat::GIF => image::ImageFormat::Gif,
+        ImageFileFormat::BMP => image::ImageFormat::Bmp,
+        ImageFileFormat::ICO => image::ImageFormat::Ico,
+        ImageFileFormat::TIFF => image::ImageFormat::Tiff,
+        ImageFileFormat::WebP => image::ImageFormat::WebP,
+        ImageFileFormat::AVIF => image::ImageFormat::Avif,
+        ImageFileFormat::PNM => image::ImageFormat::Pnm,
+        ImageFileFormat::DDS => image::ImageFormat::Dds,
+        ImageFileFormat::TGA => image::ImageFormat::Tga,
+        ImageFileFormat::Farbfeld => image::ImageFormat::Farbfeld
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    #[test]
+    fn test_snap_to_pixel()
+    {
+        assert_eq!(
+            Vec2::new(10.0, 10.0),
+            snap_to_pixel(Vec2::new(10.3, 9.7), &Matrix3::IDENTITY)
+        );
+
+        let offset = Vec2::new(0.25, 0.5);
+        let transform = Matrix3::from_translation(offset);
+
+        let snapped = snap_to_pixel(Vec2::new(9.6, 9.9), &transform);
+        let screen_position = transform.transform_point(snapped);
+
+        assert_eq!(screen_position, screen_position.round());
+        assert_eq!(Vec2::new(10.0, 10.0), screen_position);
+
+        let rotation = Matrix3::from_rotation(0.5);
+        let position = Vec2::new(9.6, 9.9);
+
+        assert_eq!(position, snap_to_pixel(position, &rotation));
+    }
 }
@@ -0,0 +1,64 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::rc::Rc;
+
+use crate::glwrapper::GLProgram;
+use crate::renderer2d::Uniforms;
+
+/// A custom GPU program, which replaces the built-in shaders for any drawing
+/// operations performed while it is active. Custom shaders can be created
+/// using [Graphics2D::create_shader](crate::Graphics2D::create_shader), and
+/// activated using [Graphics2D::set_shader](crate::Graphics2D::set_shader).
+///
+/// The shader sources must be written in the GLSL version used by the
+/// built-in shaders: `#version 110` for OpenGL, or `#version 300 es` for
+/// WebGL.
+///
+/// The vertex shader receives the following attributes:
+///
+/// * `vec2 in_Position`: the position of the vertex, in pixels.
+/// * `vec4 in_Color`: the color of the vertex.
+/// * `vec2 in_TextureCoord`: the texture coordinate of the vertex. For circles,
+///   this is the position of the vertex relative to the circle, where the unit
+///   circle is the edge of the circle.
+/// * `float in_TextureMix`: `1.0` if the vertex is part of an image, otherwise
+///   `0.0`.
+/// * `float in_CircleMix`: `1.0` if the vertex is part of a circle, otherwise
+///   `0.0`.
+///
+/// The following uniforms are set automatically, if the shader uses them:
+///
+/// * `float in_ScaleX`, `float in_ScaleY`, `float in_OffsetY`: the values
+///   needed to convert the pixel position `(x, y)` to the GL position `(x *
+///   in_ScaleX - 1.0, y * in_ScaleY + in_OffsetY)`.
+/// * `sampler2D in_Texture`: the texture of the image currently being drawn.
+/// * `float in_DiscardTransparent`: `1.0` if fully transparent pixels should be
+///   discarded, as they would otherwise affect the output with the current
+///   [BlendMode](crate::BlendMode), otherwise `0.0`.
+///
+/// If no vertex shader source is provided, the built-in vertex shader is used,
+/// which passes the attributes to the fragment shader as `pass_Color`,
+/// `pass_TextureCoord`, `pass_TextureMix`, and `pass_CircleMix`.
+///
+/// Note: this handle can only be used in the graphics context in which it was
+/// created.
+#[derive(Debug, Clone)]
+pub struct Shader
+{
+    pub(crate) program: Rc<GLProgram>,
+    pub(crate) uniforms: Rc<Uniforms>
+}
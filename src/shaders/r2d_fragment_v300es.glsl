@@ -19,6 +19,7 @@
 precision mediump float;
 
 uniform sampler2D in_Texture;
+uniform float in_DiscardTransparent;
 
 in vec4 pass_Color;
 in vec2 pass_TextureCoord;
@@ -40,4 +41,16 @@ void main(void) {
             vec4(1.0 - pass_TextureMix - pass_CircleMix)
                     + (texCol * pass_TextureMix)
                     + (vec4(vec3(1.0), circleAlpha)) * pass_CircleMix);
+
+    // The corners of circle quads are outside the circle, so they must never
+    // be drawn, even when blending is disabled
+    if (pass_CircleMix > 0.0 && circleAlpha == 0.0) {
+        discard;
+    }
+
+    // Blend modes which ignore the source alpha would otherwise draw fully
+    // transparent pixels, such as the background of text glyphs
+    if (in_DiscardTransparent > 0.5 && out_FragColor.a == 0.0) {
+        discard;
+    }
 }
\ No newline at end of file
@@ -24,6 +24,8 @@ in float in_CircleMix;
 
 uniform float in_ScaleX;
 uniform float in_ScaleY;
+uniform float in_OffsetY;
+uniform float in_Depth;
 
 out vec4 pass_Color;
 out vec2 pass_TextureCoord;
@@ -34,8 +36,8 @@ void main(void) {
 
     gl_Position = vec4(
             in_Position.x * in_ScaleX - 1.0,
-            in_Position.y * in_ScaleY + 1.0,
-            0.0,
+            in_Position.y * in_ScaleY + in_OffsetY,
+            1.0 - 2.0 * in_Depth,
             1.0);
 
     pass_Color = in_Color;
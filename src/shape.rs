@@ -227,6 +227,24 @@ impl<T: std::cmp::PartialOrd + Copy> Rectangle<T>
             None
         }
     }
+
+    /// Finds the union of two rectangles -- in other words, the smallest
+    /// rectangle which contains both of them.
+    #[inline]
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self
+    {
+        Self {
+            top_left: Vector2::new(
+                min(self.top_left.x, other.top_left.x),
+                min(self.top_left.y, other.top_left.y)
+            ),
+            bottom_right: Vector2::new(
+                max(self.bottom_right.x, other.bottom_right.x),
+                max(self.bottom_right.y, other.bottom_right.y)
+            )
+        }
+    }
 }
 
 impl<T: PrimitiveZero> Rectangle<T>
@@ -271,6 +289,23 @@ where
     }
 }
 
+impl<T: Copy> Rectangle<T>
+where
+    Vector2<T>: std::ops::Add<Output = Vector2<T>> + std::ops::Sub<Output = Vector2<T>>
+{
+    /// Returns a new rectangle, which is larger than the current rectangle by
+    /// `margin` on each side. The size of the rectangle increases by twice
+    /// the margin in each dimension. For signed types, a negative margin
+    /// shrinks the rectangle instead.
+    #[inline]
+    #[must_use]
+    pub fn with_margin(&self, margin: T) -> Self
+    {
+        let margin = Vector2::new(margin, margin);
+        Rectangle::new(self.top_left - margin, self.bottom_right + margin)
+    }
+}
+
 impl<T: Copy> Rectangle<T>
 where
     Vector2<T>: std::ops::Sub<Output = Vector2<T>>
@@ -318,10 +353,49 @@ impl<T: num_traits::AsPrimitive<f32> + Copy> Rectangle<T>
     }
 }
 
+impl Rect
+{
+    /// Constructs a new `Rectangle` with the specified center point and size.
+    #[inline]
+    pub fn from_center_size(center: impl Into<Vec2>, size: impl Into<Vec2>) -> Self
+    {
+        let center = center.into();
+        let half_size = size.into() / 2.0;
+
+        Rectangle::new(center - half_size, center + half_size)
+    }
+
+    /// Returns the point at the center of the rectangle.
+    #[inline]
+    pub fn center(&self) -> Vec2
+    {
+        (self.top_left + self.bottom_right) / 2.0
+    }
+
+    /// Returns the four corners of this rectangle after rotating it around its
+    /// center by the specified angle, in clockwise order starting from the
+    /// (unrotated) top left corner. As the Y axis points downwards, positive
+    /// angles result in a clockwise rotation on the screen.
+    pub(crate) fn rotated_corners_clockwise(&self, radians: f32) -> [Vec2; 4]
+    {
+        let center = self.center();
+
+        let rotate = |corner: Vec2| center + (corner - center).rotate(radians);
+
+        [
+            rotate(self.top_left),
+            rotate(self.top_right()),
+            rotate(self.bottom_right),
+            rotate(self.bottom_left())
+        ]
+    }
+}
+
 /// A struct representing a polygon.
 #[derive(Debug, Clone)]
 pub struct Polygon
 {
+    pub(crate) vertices: Vec<Vec2>,
     pub(crate) triangles: Vec<[Vec2; 3]>
 }
 
@@ -332,6 +406,15 @@ impl Polygon
     /// The points must be in either clockwise or couter-clockwise order.
     pub fn new<Point: Into<Vec2> + Copy>(vertices: &[Point]) -> Self
     {
+        let outline: Vec<Vec2> = vertices.iter().map(|vertex| (*vertex).into()).collect();
+
+        if vertices.len() < 3 {
+            return Polygon {
+                vertices: outline,
+                triangles: Vec::new()
+            };
+        }
+
         // We have to flatten the vertices in order for
         // [earcutr](https://github.com/frewsxcv/earcutr/) to accept it.
         // In the future, we can add a triangulation algorithm directly into Speed2D if
@@ -356,14 +439,213 @@ impl Polygon
             ])
         }
 
-        Polygon { triangles }
+        Polygon {
+            vertices: outline,
+            triangles
+        }
     }
 }
 
+/// The maximum distance in pixels between a Bézier curve and the line
+/// segments used to draw it.
+pub(crate) const BEZIER_TOLERANCE: f32 = 0.1;
+
+/// Approximates a quadratic Bézier curve using straight line segments, such
+/// that no point on the curve is further than `tolerance` pixels from the
+/// line. The returned points include both endpoints.
+pub(crate) fn flatten_quadratic_bezier(points: [Vec2; 3], tolerance: f32) -> Vec<Vec2>
+{
+    // Any quadratic curve can be represented exactly as a cubic curve
+    let [p0, p1, p2] = points;
+
+    flatten_cubic_bezier(
+        [
+            p0,
+            p0 + (p1 - p0) * (2.0 / 3.0),
+            p2 + (p1 - p2) * (2.0 / 3.0),
+            p2
+        ],
+        tolerance
+    )
+}
+
+/// Approximates a cubic Bézier curve using straight line segments, such that
+/// no point on the curve is further than `tolerance` pixels from the line.
+/// The returned points include both endpoints.
+pub(crate) fn flatten_cubic_bezier(points: [Vec2; 4], tolerance: f32) -> Vec<Vec2>
+{
+    // Limits the number of segments to 2^MAX_DEPTH, in case of an unreasonably
+    // small tolerance
+    const MAX_DEPTH: u32 = 10;
+
+    fn distance_from_line(point: Vec2, start: Vec2, end: Vec2) -> f32
+    {
+        let line = end - start;
+        let length = line.magnitude();
+
+        if length == 0.0 {
+            (point - start).magnitude()
+        } else {
+            ((point.x - start.x) * line.y - (point.y - start.y) * line.x).abs() / length
+        }
+    }
+
+    fn subdivide(points: [Vec2; 4], tolerance: f32, depth: u32, output: &mut Vec<Vec2>)
+    {
+        let [p0, p1, p2, p3] = points;
+
+        // The curve is contained within the convex hull of its control points
+        let flatness = distance_from_line(p1, p0, p3).max(distance_from_line(p2, p0, p3));
+
+        if flatness <= tolerance || depth >= MAX_DEPTH {
+            output.push(p3);
+            return;
+        }
+
+        // Split the curve in half using de Casteljau's algorithm
+        let p01 = p0.lerp(p1, 0.5);
+        let p12 = p1.lerp(p2, 0.5);
+        let p23 = p2.lerp(p3, 0.5);
+        let p012 = p01.lerp(p12, 0.5);
+        let p123 = p12.lerp(p23, 0.5);
+        let mid = p012.lerp(p123, 0.5);
+
+        subdivide([p0, p01, p012, mid], tolerance, depth + 1, output);
+        subdivide([mid, p123, p23, p3], tolerance, depth + 1, output);
+    }
+
+    let mut output = vec![points[0]];
+    subdivide(points, tolerance, 0, &mut output);
+    output
+}
+
+/// Splits the line between `start` and `end` into dashes of length
+/// `dash_length`, separated by gaps of length `gap_length`, and returns the
+/// start and end points of each dash.
+///
+/// The pattern is shifted along the line by `offset` pixels, so that
+/// increasing the offset over time moves the dashes towards `end`.
+pub(crate) fn dash_segments(
+    start: Vec2,
+    end: Vec2,
+    dash_length: f32,
+    gap_length: f32,
+    offset: f32
+) -> Vec<(Vec2, Vec2)>
+{
+    let direction = match (end - start).normalize() {
+        None => return Vec::new(),
+        Some(direction) => direction
+    };
+
+    if !dash_length.is_finite() || dash_length <= 0.0 {
+        return Vec::new();
+    }
+
+    if !gap_length.is_finite() || gap_length <= 0.0 {
+        return vec![(start, end)];
+    }
+
+    let length = (end - start).magnitude();
+    let period = dash_length + gap_length;
+
+    let phase = if offset.is_finite() {
+        offset.rem_euclid(period)
+    } else {
+        0.0
+    };
+
+    // Start one period early, in case the offset means that the line begins
+    // part-way through a dash
+    let mut position = phase - period;
+
+    let mut result = Vec::new();
+
+    while position < length {
+        let dash_start = position.max(0.0);
+        let dash_end = (position + dash_length).min(length);
+
+        if dash_end > dash_start {
+            result.push((start + direction * dash_start, start + direction * dash_end));
+        }
+
+        position += period;
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test
 {
-    use crate::shape::URect;
+    use crate::dimen::Vec2;
+    use crate::shape::{
+        dash_segments,
+        flatten_cubic_bezier,
+        flatten_quadratic_bezier,
+        IRect,
+        Polygon,
+        Rect,
+        URect
+    };
+
+    fn triangulated_area(polygon: &Polygon) -> f32
+    {
+        polygon
+            .triangles
+            .iter()
+            .map(|[a, b, c]| {
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    pub fn test_polygon_convex_quad()
+    {
+        let polygon = Polygon::new(&[(0.0, 0.0), (30.0, 0.0), (40.0, 20.0), (0.0, 20.0)]);
+
+        assert_eq!(2, polygon.triangles.len());
+        assert_eq!(700.0, triangulated_area(&polygon));
+    }
+
+    #[test]
+    pub fn test_polygon_concave_arrow()
+    {
+        let polygon = Polygon::new(&[
+            (0.0, 10.0),
+            (20.0, 10.0),
+            (20.0, 0.0),
+            (40.0, 20.0),
+            (20.0, 40.0),
+            (20.0, 30.0),
+            (0.0, 30.0)
+        ]);
+
+        assert_eq!(5, polygon.triangles.len());
+        assert_eq!(800.0, triangulated_area(&polygon));
+    }
+
+    #[test]
+    pub fn test_polygon_degenerate()
+    {
+        assert!(Polygon::new::<Vec2>(&[]).triangles.is_empty());
+        assert!(Polygon::new(&[(0.0, 0.0), (10.0, 10.0)])
+            .triangles
+            .is_empty());
+
+        let collinear = Polygon::new(&[(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)]);
+        assert_eq!(0.0, triangulated_area(&collinear));
+
+        let with_midpoint = Polygon::new(&[
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (20.0, 0.0),
+            (20.0, 20.0),
+            (0.0, 20.0)
+        ]);
+        assert_eq!(400.0, triangulated_area(&with_midpoint));
+    }
 
     #[test]
     pub fn test_intersect_1()
@@ -396,6 +678,201 @@ mod test
         let r2 = URect::from_tuples((100, 200), (200, 300));
 
         assert_eq!(None, r1.intersect(&r2));
+
+        // Rectangles touching at a corner also have no common area
+        let r3 = URect::from_tuples((200, 200), (300, 300));
+
+        assert_eq!(None, r1.intersect(&r3));
+        assert_eq!(None, r3.intersect(&r1));
+    }
+
+    #[test]
+    pub fn test_rotated_corners()
+    {
+        let rect = Rect::from_tuples((10.0, 20.0), (50.0, 40.0));
+
+        let assert_corners = |expected: [(f32, f32); 4], actual: [Vec2; 4]| {
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                assert!(
+                    (Vec2::from(*expected) - *actual).magnitude() < 0.0001,
+                    "Expected {:?}, got {:?}",
+                    expected,
+                    actual
+                );
+            }
+        };
+
+        assert_corners(
+            [(10.0, 20.0), (50.0, 20.0), (50.0, 40.0), (10.0, 40.0)],
+            rect.rotated_corners_clockwise(0.0)
+        );
+
+        // Rotated 90 degrees clockwise around the center at (30, 30)
+        assert_corners(
+            [(40.0, 10.0), (40.0, 50.0), (20.0, 50.0), (20.0, 10.0)],
+            rect.rotated_corners_clockwise(std::f32::consts::FRAC_PI_2)
+        );
+
+        assert_corners(
+            [(50.0, 40.0), (10.0, 40.0), (10.0, 20.0), (50.0, 20.0)],
+            rect.rotated_corners_clockwise(std::f32::consts::PI)
+        );
+    }
+
+    #[test]
+    pub fn test_bezier_straight_line()
+    {
+        let start = Vec2::new(10.0, 20.0);
+        let end = Vec2::new(110.0, 70.0);
+
+        // Control points on the line between the endpoints
+        assert_eq!(
+            vec![start, end],
+            flatten_quadratic_bezier([start, start.lerp(end, 0.3), end], 0.1)
+        );
+
+        assert_eq!(
+            vec![start, end],
+            flatten_cubic_bezier(
+                [start, start.lerp(end, 0.25), start.lerp(end, 0.75), end],
+                0.1
+            )
+        );
+    }
+
+    #[test]
+    pub fn test_bezier_curve()
+    {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 100.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(100.0, 0.0)
+        ];
+
+        let coarse = flatten_cubic_bezier(points, 1.0);
+        let fine = flatten_cubic_bezier(points, 0.1);
+
+        assert_eq!(points[0], coarse[0]);
+        assert_eq!(points[3], *coarse.last().unwrap());
+        assert!(coarse.len() > 2);
+        assert!(fine.len() > coarse.len());
+
+        // Every point should lie on the curve, which reaches y = 75 at its
+        // midpoint
+        for point in fine.iter() {
+            assert!(point.y >= 0.0 && point.y <= 75.0);
+        }
+
+        assert!(fine.iter().any(|point| (point.y - 75.0).abs() < 0.0001));
+    }
+
+    #[test]
+    pub fn test_dash_segments()
+    {
+        let start = Vec2::new(0.0, 10.0);
+        let end = Vec2::new(100.0, 10.0);
+
+        let dashes = dash_segments(start, end, 10.0, 5.0, 0.0);
+
+        // Dashes start at 0, 15, 30, 45, 60, 75 and 90, the last one being
+        // cut short by the end of the line
+        assert_eq!(7, dashes.len());
+        assert_eq!((start, Vec2::new(10.0, 10.0)), dashes[0]);
+        assert_eq!((Vec2::new(15.0, 10.0), Vec2::new(25.0, 10.0)), dashes[1]);
+        assert_eq!((Vec2::new(90.0, 10.0), end), dashes[6]);
+
+        // Offsetting the pattern moves the dashes towards the end, so the
+        // line now begins part-way through a dash
+        let dashes = dash_segments(start, end, 10.0, 5.0, 8.0);
+
+        assert_eq!(8, dashes.len());
+        assert_eq!((start, Vec2::new(3.0, 10.0)), dashes[0]);
+        assert_eq!((Vec2::new(8.0, 10.0), Vec2::new(18.0, 10.0)), dashes[1]);
+        assert_eq!((Vec2::new(98.0, 10.0), end), dashes[7]);
+
+        // Offsets are repeated every period
+        assert_eq!(
+            dash_segments(start, end, 10.0, 5.0, 0.0),
+            dash_segments(start, end, 10.0, 5.0, 15.0)
+        );
+
+        assert_eq!(
+            dash_segments(start, end, 10.0, 5.0, 8.0),
+            dash_segments(start, end, 10.0, 5.0, -7.0)
+        );
+
+        assert_eq!(
+            vec![(start, end)],
+            dash_segments(start, end, 10.0, 0.0, 0.0)
+        );
+        assert!(dash_segments(start, end, 0.0, 5.0, 0.0).is_empty());
+        assert!(dash_segments(start, start, 10.0, 5.0, 0.0).is_empty());
+    }
+
+    #[test]
+    pub fn test_union()
+    {
+        let r1 = URect::from_tuples((100, 100), (200, 200));
+        let r2 = URect::from_tuples((150, 50), (250, 150));
+        let r3 = URect::from_tuples((300, 300), (400, 400));
+
+        assert_eq!(URect::from_tuples((100, 50), (250, 200)), r1.union(&r2));
+        assert_eq!(r1.union(&r2), r2.union(&r1));
+
+        // Disjoint rectangles
+        assert_eq!(URect::from_tuples((100, 100), (400, 400)), r1.union(&r3));
+
+        // One rectangle inside the other
+        let inner = URect::from_tuples((120, 120), (180, 180));
+        assert_eq!(r1, r1.union(&inner));
+        assert_eq!(r1, r1.union(&r1));
+    }
+
+    #[test]
+    pub fn test_contains()
+    {
+        let rect = Rect::from_tuples((10.0, 20.0), (30.0, 40.0));
+
+        assert!(rect.contains(Vec2::new(10.0, 20.0)));
+        assert!(rect.contains(Vec2::new(29.9, 39.9)));
+        assert!(rect.contains(Vec2::new(20.0, 30.0)));
+
+        // Exclusive of the bottom and right edges
+        assert!(!rect.contains(Vec2::new(30.0, 30.0)));
+        assert!(!rect.contains(Vec2::new(20.0, 40.0)));
+
+        assert!(!rect.contains(Vec2::new(9.9, 30.0)));
+        assert!(!rect.contains(Vec2::new(20.0, 19.9)));
+    }
+
+    #[test]
+    pub fn test_center_size()
+    {
+        let rect = Rect::from_center_size((50.0, 30.0), (20.0, 10.0));
+
+        assert_eq!(Rect::from_tuples((40.0, 25.0), (60.0, 35.0)), rect);
+        assert_eq!(Vec2::new(50.0, 30.0), rect.center());
+        assert_eq!(Vec2::new(20.0, 10.0), rect.size());
+
+        let rect = Rect::from_tuples((-7.5, 3.0), (12.5, 4.0));
+
+        assert_eq!(rect, Rect::from_center_size(rect.center(), rect.size()));
+    }
+
+    #[test]
+    pub fn test_with_margin()
+    {
+        let rect = IRect::from_tuples((10, 20), (30, 40));
+
+        assert_eq!(IRect::from_tuples((5, 15), (35, 45)), rect.with_margin(5));
+        assert_eq!(IRect::from_tuples((15, 25), (25, 35)), rect.with_margin(-5));
+        assert_eq!(rect, rect.with_margin(0));
+
+        let rect = Rect::from_tuples((10.0, 20.0), (30.0, 40.0));
+
+        assert_eq!(rect.center(), rect.with_margin(2.5).center());
+        assert_eq!(Vec2::new(25.0, 25.0), rect.with_margin(2.5).size());
     }
 }
 
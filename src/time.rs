@@ -22,10 +22,15 @@ use crate::error::{BacktraceError, ErrorMessage};
 use crate::web::{WebPerformance, WebWindow};
 
 /// Measures the amount of time elapsed since its creation.
+///
+/// The [Stopwatch::lap] function can be used to measure the time between
+/// frames, so that movement and animations can run at the same speed
+/// regardless of the frame rate.
 pub struct Stopwatch
 {
     clock: TimeClock,
-    start: TimeInstant
+    start: TimeInstant,
+    last_lap: TimeInstant
 }
 
 impl Stopwatch
@@ -37,7 +42,11 @@ impl Stopwatch
         let clock = TimeClock::new()?;
         let start = clock.now();
 
-        Ok(Self { clock, start })
+        Ok(Self {
+            clock,
+            start,
+            last_lap: start
+        })
     }
 
     /// Returns the number of seconds since the Stopwatch was created.
@@ -46,6 +55,20 @@ impl Stopwatch
     {
         self.clock.secs_elapsed_since(&self.start)
     }
+
+    /// Returns the number of seconds since the previous call to `lap()`, or
+    /// since the Stopwatch was created if this is the first call.
+    ///
+    /// The result is never negative: if the system clock goes backwards, zero
+    /// is returned.
+    #[inline]
+    pub fn lap(&mut self) -> f64
+    {
+        let now = self.clock.now();
+        let elapsed = TimeClock::secs_between(&self.last_lap, &now);
+        self.last_lap = now;
+        elapsed
+    }
 }
 
 /// Allows access to the system clock.
@@ -89,16 +112,28 @@ impl TimeClock
     /// provided [TimeInstant].
     #[inline]
     pub fn secs_elapsed_since(&self, start: &TimeInstant) -> f64
+    {
+        Self::secs_between(start, &self.now())
+    }
+
+    /// Returns the difference in seconds between the two provided
+    /// [TimeInstant] values, or zero if `end` is earlier than `start`.
+    #[inline]
+    pub fn secs_between(start: &TimeInstant, end: &TimeInstant) -> f64
     {
         #[cfg(target_arch = "wasm32")]
-        return (self.now().value - start.value) / 1000.0;
+        return ((end.value - start.value) / 1000.0).max(0.0);
 
         #[cfg(not(target_arch = "wasm32"))]
-        return start.value.elapsed().as_secs_f64();
+        return end
+            .value
+            .saturating_duration_since(start.value)
+            .as_secs_f64();
     }
 }
 
 /// Represents an instant in time.
+#[derive(Clone, Copy)]
 struct TimeInstant
 {
     #[cfg(target_arch = "wasm32")]
@@ -107,3 +142,39 @@ struct TimeInstant
     #[cfg(not(target_arch = "wasm32"))]
     value: Instant
 }
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    #[test]
+    fn test_lap()
+    {
+        let mut stopwatch = Stopwatch::new().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+
+        let first_lap = stopwatch.lap();
+        let second_lap = stopwatch.lap();
+
+        assert!(first_lap >= 0.02);
+        assert!(second_lap >= 0.0);
+        assert!(second_lap < first_lap);
+        assert!(stopwatch.secs_elapsed() >= first_lap + second_lap);
+    }
+
+    #[test]
+    fn test_secs_between_never_negative()
+    {
+        let clock = TimeClock::new().unwrap();
+        let start = clock.now();
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        let end = clock.now();
+
+        assert!(TimeClock::secs_between(&start, &end) > 0.0);
+        assert_eq!(0.0, TimeClock::secs_between(&end, &start));
+    }
+}
@@ -21,14 +21,23 @@ use std::rc::Rc;
 
 #[cfg(feature = "windowing")]
 use wasm_bindgen::closure::{Closure, WasmClosure};
+#[cfg(feature = "windowing")]
+use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsCast;
 #[cfg(feature = "windowing")]
+use wasm_bindgen::JsValue;
+#[cfg(feature = "windowing")]
 use web_sys::{
     AddEventListenerOptions,
+    BeforeUnloadEvent,
+    DragEvent,
     EventTarget,
+    File,
+    FileReader,
     KeyboardEvent,
     MediaQueryListEvent,
-    MouseEvent
+    MouseEvent,
+    TouchEvent
 };
 use web_sys::{Document, Element, HtmlCanvasElement, HtmlElement, Performance, Window};
 
@@ -108,6 +117,39 @@ impl WebCursorType
     }
 }
 
+#[cfg(feature = "windowing")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText, catch)]
+    fn navigator_clipboard_write_text(text: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Reads the contents of the specified file asynchronously, and passes them
+/// to the callback once complete.
+#[cfg(feature = "windowing")]
+pub fn read_file_bytes<F: FnOnce(Vec<u8>) + 'static>(
+    file: &File,
+    callback: F
+) -> Result<(), BacktraceError<ErrorMessage>>
+{
+    let reader = FileReader::new().map_err(|err| {
+        ErrorMessage::msg(format!("Failed to create file reader: '{err:?}'"))
+    })?;
+
+    let reader_inner = reader.clone();
+
+    let on_load = Closure::once_into_js(move || match reader_inner.result() {
+        Ok(result) => callback(js_sys::Uint8Array::new(&result).to_vec()),
+        Err(err) => log::error!("Failed to get file contents: '{err:?}'")
+    });
+
+    reader.set_onload(Some(on_load.unchecked_ref()));
+
+    reader
+        .read_as_array_buffer(file)
+        .map_err(|err| ErrorMessage::msg(format!("Failed to read file: '{err:?}'")))
+}
+
 #[derive(Clone)]
 pub struct WebWindow
 {
@@ -144,6 +186,21 @@ impl WebWindow
         })
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn set_clipboard_text(
+        &self,
+        text: &str
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        // The write happens asynchronously, and the returned promise is
+        // ignored, so only a missing clipboard API is reported here
+        navigator_clipboard_write_text(text).map_err(|err| {
+            ErrorMessage::msg(format!("Failed to write to clipboard: {:?}", err))
+        })?;
+
+        Ok(())
+    }
+
     #[cfg(feature = "windowing")]
     pub fn match_media(
         &self,
@@ -215,6 +272,13 @@ impl WebWindow
         self.window.device_pixel_ratio()
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn screen_size_css_pixels(&self) -> Option<Vector2<i32>>
+    {
+        let screen = self.window.screen().ok()?;
+        Some(Vector2::new(screen.width().ok()?, screen.height().ok()?))
+    }
+
     #[cfg(feature = "windowing")]
     pub fn dyn_into_event_target(
         self
@@ -262,6 +326,12 @@ impl WebDocument
             })
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn is_hidden(&self) -> bool
+    {
+        self.document.hidden()
+    }
+
     #[cfg(feature = "windowing")]
     pub fn fullscreen_element(&self) -> Option<WebElement>
     {
@@ -360,6 +430,14 @@ impl WebElement
         )
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn position(&self) -> Vector2<f64>
+    {
+        let bounding_rect = self.element.get_bounding_client_rect();
+
+        Vector2::new(bounding_rect.left(), bounding_rect.top())
+    }
+
     #[cfg(feature = "windowing")]
     #[inline]
     pub fn document(&self) -> &WebDocument
@@ -665,6 +743,45 @@ impl WebEventTarget
         )
     }
 
+    pub fn register_event_listener_touch<F: FnMut(TouchEvent) + 'static>(
+        &self,
+        listener_type: &str,
+        callback: F
+    ) -> Result<WebPending, BacktraceError<ErrorMessage>>
+    {
+        self.register_event_listener(
+            listener_type,
+            Box::new(callback) as Box<dyn FnMut(_)>,
+            false
+        )
+    }
+
+    pub fn register_event_listener_before_unload<F: FnMut(BeforeUnloadEvent) + 'static>(
+        &self,
+        listener_type: &str,
+        callback: F
+    ) -> Result<WebPending, BacktraceError<ErrorMessage>>
+    {
+        self.register_event_listener(
+            listener_type,
+            Box::new(callback) as Box<dyn FnMut(_)>,
+            false
+        )
+    }
+
+    pub fn register_event_listener_drag<F: FnMut(DragEvent) + 'static>(
+        &self,
+        listener_type: &str,
+        callback: F
+    ) -> Result<WebPending, BacktraceError<ErrorMessage>>
+    {
+        self.register_event_listener(
+            listener_type,
+            Box::new(callback) as Box<dyn FnMut(_)>,
+            false
+        )
+    }
+
     pub fn register_event_listener_media_event_list_once<
         F: FnMut(MediaQueryListEvent) + 'static
     >(
@@ -16,9 +16,15 @@
 
 use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::dimen::{IVec2, UVec2, Vec2};
+use crate::color::Color;
+use crate::dimen::{IVec2, UVec2, Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
+use crate::image::{ImageSmoothingMode, RenderTarget};
+use crate::shape::Rectangle;
 use crate::{GLRenderer, Graphics2D};
 
 #[cfg(all(not(target_arch = "wasm32"), not(any(doc, doctest))))]
@@ -124,9 +130,32 @@ impl Display for WindowCreationError
     }
 }
 
+/// An identifier for a window, which is unique within the process. This is
+/// available in each callback using [WindowHelper::get_window_id].
+///
+/// Currently, only one window can be created per process, as the event loop
+/// is driven by [crate::Window::run_loop], which doesn't return. The
+/// identifier allows handlers to distinguish between windows, so that they
+/// will continue to work if support for multiple windows sharing an event
+/// loop is added in future.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WindowId(usize);
+
+impl WindowId
+{
+    fn next() -> Self
+    {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        WindowId(NEXT_ID.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
 /// A set of callbacks for an active window. If a callback is not implemented,
 /// it will do nothing by default, so it is only necessary to implement the
 /// callbacks you actually need.
+///
+/// Each callback is given a [WindowHelper], which can be used to identify the
+/// window using [WindowHelper::get_window_id].
 pub trait WindowHandler<UserEventType = ()>
 {
     /// Invoked once when the window first starts.
@@ -140,6 +169,24 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when the user attempts to close the window, for example by
+    /// clicking the close button. Return `true` to allow the window to close
+    /// and the application to terminate, or `false` to keep it open, for
+    /// example while asking the user whether to save their work.
+    ///
+    /// The default implementation returns `true`.
+    ///
+    /// When using a web canvas, this is invoked when the user tries to leave
+    /// the page. Returning `false` asks the browser to display a confirmation
+    /// dialog, although browsers may ignore this request unless the user
+    /// has interacted with the page.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_close_requested(&mut self, helper: &mut WindowHelper<UserEventType>) -> bool
+    {
+        true
+    }
+
     /// Invoked when a user-defined event is received, allowing you to wake up
     /// the event loop to handle events from other threads.
     ///
@@ -188,6 +235,53 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when the window is minimized, or when the web page containing
+    /// the canvas is hidden. See [WindowHelper::minimize].
+    ///
+    /// While minimized, the window is not visible, so applications may wish
+    /// to stop drawing and pause any animations to save power.
+    ///
+    /// Note: some platforms don't report when the window is minimized by the
+    /// user, in which case this will only be invoked after calling
+    /// [WindowHelper::minimize].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_minimized(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the window is restored after being minimized, or when the
+    /// web page containing the canvas becomes visible again. See
+    /// [WindowHandler::on_window_minimized].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_restored(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the application is suspended by the operating system.
+    /// This happens on mobile platforms (such as Android and iOS) when the
+    /// application moves to the background, and the window is no longer
+    /// drawn until [WindowHandler::on_resume] is invoked.
+    ///
+    /// Applications may wish to pause any animations or audio, and save their
+    /// state, as the application may be terminated while suspended.
+    ///
+    /// Note: GPU resources may be invalidated while the application is
+    /// suspended. See [WindowHandler::on_resume].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_suspend(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the application resumes after being suspended. See
+    /// [WindowHandler::on_suspend].
+    ///
+    /// Some platforms discard the contents of the GPU while the application
+    /// is suspended. Speedy2D automatically recreates its own resources (such
+    /// as the font glyph cache) when resuming, but any
+    /// [ImageHandle](crate::image::ImageHandle) or [RenderTarget] created by
+    /// the application may no longer be valid, and should be created again
+    /// here.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_resume(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
     /// Invoked when the window scale factor changes.
     #[allow(unused_variables)]
     #[inline]
@@ -227,6 +321,27 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when the mouse moves, providing the raw relative movement
+    /// reported by the device, without any pointer acceleration applied.
+    ///
+    /// The delta is in device units rather than window pixels, and is not
+    /// affected by the window's scale factor. This is useful for camera
+    /// controls, and is typically only meaningful while the cursor is grabbed.
+    ///
+    /// For `WebCanvas`, this provides the movement reported by the browser,
+    /// which may have pointer acceleration applied.
+    ///
+    /// See [WindowHelper::set_cursor_grab].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_mouse_raw_motion(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        delta: Vec2
+    )
+    {
+    }
+
     /// Invoked when a mouse button is pressed.
     #[allow(unused_variables)]
     #[inline]
@@ -260,8 +375,102 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when a finger touches, moves across, or is lifted from a touch
+    /// screen.
+    ///
+    /// The `id` uniquely identifies each finger for the duration of the touch,
+    /// allowing multi-touch gestures to be tracked. The `position` is in
+    /// pixels, relative to the top left of the window, in the same coordinate
+    /// space as [WindowHandler::on_mouse_move].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_touch(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2
+    )
+    {
+    }
+
+    /// Invoked when a gamepad or other game controller is connected.
+    ///
+    /// Note: gamepad events are only delivered when the `gamepad` feature is
+    /// enabled, and are not currently supported for `WebCanvas`.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_connected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId
+    )
+    {
+    }
+
+    /// Invoked when a gamepad is disconnected. See
+    /// [WindowHandler::on_gamepad_connected].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_disconnected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId
+    )
+    {
+    }
+
+    /// Invoked when a button on a gamepad is pressed. See
+    /// [WindowHandler::on_gamepad_connected].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        button: GamepadButton
+    )
+    {
+    }
+
+    /// Invoked when a button on a gamepad is released. See
+    /// [WindowHandler::on_gamepad_connected].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        button: GamepadButton
+    )
+    {
+    }
+
+    /// Invoked when the position of a gamepad axis (such as a thumb stick)
+    /// changes. The value is in the range `-1.0` to `1.0`, where positive
+    /// values are to the right on the X axis, and upwards on the Y axis. See
+    /// [WindowHandler::on_gamepad_connected].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_axis(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        axis: GamepadAxis,
+        value: f32
+    )
+    {
+    }
+
     /// Invoked when a keyboard key is pressed.
     ///
+    /// The `virtual_key_code` identifies the logical key according to the
+    /// current keyboard layout, and the `scancode` identifies the physical
+    /// key. See [KeyScancode] for details.
+    ///
+    /// While a key is held down, this is invoked repeatedly. Repeated presses
+    /// can be identified using [WindowHelper::is_key_repeat].
+    ///
     /// To detect when a character is typed, see the
     /// [WindowHandler::on_keyboard_char] callback.
     #[allow(unused_variables)]
@@ -275,7 +484,8 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
-    /// Invoked when a keyboard key is released.
+    /// Invoked when a keyboard key is released. See
+    /// [WindowHandler::on_key_down].
     #[allow(unused_variables)]
     #[inline]
     fn on_key_up(
@@ -301,6 +511,39 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when the text being composed using an input method editor
+    /// (IME) changes, for example while typing Chinese, Japanese, or Korean
+    /// characters. Applications may display this text at the caret, to
+    /// preview the composition before it is committed.
+    ///
+    /// The cursor range is given in bytes within `text`, and is `None` if the
+    /// cursor should be hidden. An empty `text` indicates that the composition
+    /// has ended, and any preview should be removed.
+    ///
+    /// Note: this is currently only invoked on platforms where the windowing
+    /// backend reports IME composition events. Elsewhere, committed text is
+    /// delivered using [WindowHandler::on_keyboard_char].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_ime_preedit(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        text: &str,
+        cursor_range: Option<Range<usize>>
+    )
+    {
+    }
+
+    /// Invoked when text composed using an input method editor (IME) is
+    /// committed. See [WindowHandler::on_ime_preedit].
+    ///
+    /// Note: this is currently only invoked on platforms where the windowing
+    /// backend reports IME composition events. Elsewhere, committed text is
+    /// delivered using [WindowHandler::on_keyboard_char].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_ime_commit(&mut self, helper: &mut WindowHelper<UserEventType>, text: &str) {}
+
     /// Invoked when the state of the modifier keys has changed.
     #[allow(unused_variables)]
     #[inline]
@@ -311,6 +554,121 @@ pub trait WindowHandler<UserEventType = ()>
     )
     {
     }
+
+    /// Invoked when one or more files are dragged from another application
+    /// and dropped onto the window.
+    ///
+    /// If the platform reports where the files were dropped, `position`
+    /// contains the location in pixels, relative to the top left of the
+    /// window, in the same coordinate space as
+    /// [WindowHandler::on_mouse_move].
+    ///
+    /// Note: when running in a web browser, file paths are not available, and
+    /// [WindowHandler::on_file_dropped_bytes] is invoked instead.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_files_dropped(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        paths: Vec<PathBuf>,
+        position: Option<Vec2>
+    )
+    {
+    }
+
+    /// Invoked once for each file dropped onto the canvas, when running in a
+    /// web browser. The `name` parameter contains the file name, without any
+    /// path, and `bytes` contains the contents of the file.
+    ///
+    /// The files are read asynchronously, so this may be invoked some time
+    /// after the files were dropped, and if multiple files are dropped, they
+    /// may not be delivered in order.
+    ///
+    /// On other platforms, [WindowHandler::on_files_dropped] is invoked
+    /// instead.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_file_dropped_bytes(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        name: String,
+        bytes: Vec<u8>,
+        position: Option<Vec2>
+    )
+    {
+    }
+}
+
+/// Returns the area of the window into which a canvas of the specified
+/// resolution should be drawn, scaled up by the largest integer which fits.
+/// The canvas is centered, leaving an equal margin on either side.
+pub(crate) fn integer_scaled_area(
+    resolution: UVec2,
+    viewport_size_pixels: UVec2
+) -> Rectangle
+{
+    let scale = if resolution.x == 0 || resolution.y == 0 {
+        1
+    } else {
+        (viewport_size_pixels.x / resolution.x)
+            .min(viewport_size_pixels.y / resolution.y)
+            .max(1)
+    };
+
+    let size = resolution * scale;
+
+    let top_left = IVec2::new(
+        (viewport_size_pixels.x as i32 - size.x as i32) / 2,
+        (viewport_size_pixels.y as i32 - size.y as i32) / 2
+    );
+
+    Rectangle::new(top_left.into_f32(), (top_left + size.into_i32()).into_f32())
+}
+
+struct FixedResolution
+{
+    resolution: UVec2,
+    viewport_size_pixels: UVec2,
+    target: Option<RenderTarget>
+}
+
+impl FixedResolution
+{
+    fn area(&self) -> Rectangle
+    {
+        integer_scaled_area(self.resolution, self.viewport_size_pixels)
+    }
+
+    fn window_position_to_canvas(&self, position: Vec2) -> Vec2
+    {
+        let area = self.area();
+        let scale = area.width() / self.resolution.x.max(1) as f32;
+
+        (position - *area.top_left()) / scale
+    }
+
+    fn draw<F: FnOnce(&mut Graphics2D)>(&mut self, graphics: &mut Graphics2D, callback: F)
+    {
+        if self.target.is_none() {
+            match graphics.create_render_target(
+                ImageSmoothingMode::NearestNeighbor,
+                self.resolution
+            ) {
+                Ok(target) => self.target = Some(target),
+                Err(err) => {
+                    log::error!("Failed to create fixed resolution target: {:?}", err);
+                    callback(graphics);
+                    return;
+                }
+            }
+        }
+
+        if let Some(target) = &self.target {
+            graphics.draw_to_target(target, callback);
+            graphics.clear_screen(Color::BLACK);
+            graphics.draw_rectangle_image(self.area(), target.image());
+        }
+    }
 }
 
 pub(crate) struct DrawingWindowHandler<UserEventType, H>
@@ -320,6 +678,8 @@ where
 {
     window_handler: H,
     renderer: GLRenderer,
+    fixed_resolution: Option<FixedResolution>,
+    background_color: Option<Color>,
     phantom: PhantomData<UserEventType>
 }
 
@@ -333,10 +693,37 @@ where
         DrawingWindowHandler {
             window_handler,
             renderer,
+            fixed_resolution: None,
+            background_color: None,
             phantom: PhantomData
         }
     }
 
+    /// Clears the screen to the specified color before each frame is drawn.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn with_background_color(mut self, color: Color) -> Self
+    {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Renders the application into an offscreen canvas of the specified
+    /// resolution, which is then scaled up to fit the viewport.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn with_fixed_resolution(
+        mut self,
+        resolution: UVec2,
+        viewport_size_pixels: UVec2
+    ) -> Self
+    {
+        self.fixed_resolution = Some(FixedResolution {
+            resolution,
+            viewport_size_pixels,
+            target: None
+        });
+        self
+    }
+
     #[inline]
     pub fn on_start(
         &mut self,
@@ -344,6 +731,7 @@ where
         info: WindowStartupInfo
     )
     {
+        self.renderer.set_scale_factor(info.scale_factor());
         self.window_handler.on_start(helper, info);
     }
 
@@ -365,6 +753,11 @@ where
     )
     {
         self.renderer.set_viewport_size_pixels(size_pixels);
+
+        if let Some(fixed_resolution) = &mut self.fixed_resolution {
+            fixed_resolution.viewport_size_pixels = size_pixels;
+        }
+
         self.window_handler.on_resize(helper, size_pixels)
     }
 
@@ -390,6 +783,45 @@ where
             .on_fullscreen_status_changed(helper, fullscreen)
     }
 
+    #[inline]
+    pub fn on_close_requested(&mut self, helper: &mut WindowHelper<UserEventType>)
+        -> bool
+    {
+        self.window_handler.on_close_requested(helper)
+    }
+
+    #[inline]
+    pub fn on_window_minimized(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_minimized(helper)
+    }
+
+    #[inline]
+    pub fn on_window_restored(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_restored(helper)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    #[inline]
+    pub fn on_suspend(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_suspend(helper)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_resume(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        // Textures may have been discarded while suspended
+        self.renderer.clear_glyph_cache();
+
+        if let Some(fixed_resolution) = &mut self.fixed_resolution {
+            fixed_resolution.target = None;
+        }
+
+        self.window_handler.on_resume(helper)
+    }
+
     #[inline]
     pub fn on_scale_factor_changed(
         &mut self,
@@ -397,6 +829,7 @@ where
         scale_factor: f64
     )
     {
+        self.renderer.set_scale_factor(scale_factor);
         self.window_handler
             .on_scale_factor_changed(helper, scale_factor)
     }
@@ -406,8 +839,22 @@ where
     {
         let renderer = &mut self.renderer;
         let window_handler = &mut self.window_handler;
+        let background_color = self.background_color;
 
-        renderer.draw_frame(|graphics| window_handler.on_draw(helper, graphics))
+        let draw = |graphics: &mut Graphics2D| {
+            if let Some(color) = background_color {
+                graphics.clear_screen(color);
+            }
+
+            window_handler.on_draw(helper, graphics)
+        };
+
+        match &mut self.fixed_resolution {
+            None => renderer.draw_frame(draw),
+            Some(fixed_resolution) => {
+                renderer.draw_frame(|graphics| fixed_resolution.draw(graphics, draw))
+            }
+        }
     }
 
     #[inline]
@@ -417,9 +864,21 @@ where
         position: Vec2
     )
     {
+        let position = self.window_position_to_canvas(position);
+
         self.window_handler.on_mouse_move(helper, position)
     }
 
+    #[inline]
+    pub fn on_mouse_raw_motion(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        delta: Vec2
+    )
+    {
+        self.window_handler.on_mouse_raw_motion(helper, delta)
+    }
+
     #[inline]
     pub fn on_mouse_button_down(
         &mut self,
@@ -450,16 +909,113 @@ where
         self.window_handler.on_mouse_wheel_scroll(helper, distance)
     }
 
+    #[inline]
+    pub fn on_touch(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2
+    )
+    {
+        let position = self.window_position_to_canvas(position);
+
+        self.window_handler.on_touch(helper, id, phase, position)
+    }
+
+    #[cfg_attr(
+        any(not(feature = "gamepad"), target_arch = "wasm32"),
+        allow(dead_code)
+    )]
+    #[inline]
+    pub fn on_gamepad_connected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId
+    )
+    {
+        self.window_handler.on_gamepad_connected(helper, gamepad_id)
+    }
+
+    #[cfg_attr(
+        any(not(feature = "gamepad"), target_arch = "wasm32"),
+        allow(dead_code)
+    )]
+    #[inline]
+    pub fn on_gamepad_disconnected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId
+    )
+    {
+        self.window_handler
+            .on_gamepad_disconnected(helper, gamepad_id)
+    }
+
+    #[cfg_attr(
+        any(not(feature = "gamepad"), target_arch = "wasm32"),
+        allow(dead_code)
+    )]
+    #[inline]
+    pub fn on_gamepad_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        button: GamepadButton
+    )
+    {
+        self.window_handler
+            .on_gamepad_button_down(helper, gamepad_id, button)
+    }
+
+    #[cfg_attr(
+        any(not(feature = "gamepad"), target_arch = "wasm32"),
+        allow(dead_code)
+    )]
+    #[inline]
+    pub fn on_gamepad_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        button: GamepadButton
+    )
+    {
+        self.window_handler
+            .on_gamepad_button_up(helper, gamepad_id, button)
+    }
+
+    #[cfg_attr(
+        any(not(feature = "gamepad"), target_arch = "wasm32"),
+        allow(dead_code)
+    )]
+    #[inline]
+    pub fn on_gamepad_axis(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad_id: GamepadId,
+        axis: GamepadAxis,
+        value: f32
+    )
+    {
+        self.window_handler
+            .on_gamepad_axis(helper, gamepad_id, axis, value)
+    }
+
     #[inline]
     pub fn on_key_down(
         &mut self,
         helper: &mut WindowHelper<UserEventType>,
         virtual_key_code: Option<VirtualKeyCode>,
-        scancode: KeyScancode
+        scancode: KeyScancode,
+        is_repeat: bool
     )
     {
+        helper.is_key_repeat = is_repeat;
+
         self.window_handler
-            .on_key_down(helper, virtual_key_code, scancode)
+            .on_key_down(helper, virtual_key_code, scancode);
+
+        helper.is_key_repeat = false;
     }
 
     #[inline]
@@ -495,6 +1051,44 @@ where
         self.window_handler
             .on_keyboard_modifiers_changed(helper, state)
     }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_files_dropped(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        paths: Vec<PathBuf>,
+        position: Option<Vec2>
+    )
+    {
+        let position = position.map(|position| self.window_position_to_canvas(position));
+        self.window_handler
+            .on_files_dropped(helper, paths, position)
+    }
+
+    #[inline]
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub fn on_file_dropped_bytes(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        name: String,
+        bytes: Vec<u8>,
+        position: Option<Vec2>
+    )
+    {
+        let position = position.map(|position| self.window_position_to_canvas(position));
+
+        self.window_handler
+            .on_file_dropped_bytes(helper, name, bytes, position)
+    }
+
+    fn window_position_to_canvas(&self, position: Vec2) -> Vec2
+    {
+        match &self.fixed_resolution {
+            None => position,
+            Some(fixed_resolution) => fixed_resolution.window_position_to_canvas(position)
+        }
+    }
 }
 
 /// A set of helper methods to perform actions on a [crate::Window].
@@ -502,14 +1096,43 @@ pub struct WindowHelper<UserEventType = ()>
 where
     UserEventType: 'static
 {
-    inner: WindowHelperInnerType<UserEventType>
+    inner: WindowHelperInnerType<UserEventType>,
+    window_id: WindowId,
+    is_key_repeat: bool
 }
 
 impl<UserEventType> WindowHelper<UserEventType>
 {
     pub(crate) fn new(inner: WindowHelperInnerType<UserEventType>) -> Self
     {
-        WindowHelper { inner }
+        WindowHelper {
+            inner,
+            window_id: WindowId::next(),
+            is_key_repeat: false
+        }
+    }
+
+    /// Returns the identifier of the window to which this helper belongs. See
+    /// [WindowId] for details.
+    #[inline]
+    #[must_use]
+    pub fn get_window_id(&self) -> WindowId
+    {
+        self.window_id
+    }
+
+    /// Returns true if the key press currently being handled by
+    /// [WindowHandler::on_key_down] was generated automatically, because the
+    /// key is being held down, rather than being a new press of the key.
+    ///
+    /// Games will usually want to ignore repeated key presses, while text
+    /// editing and menu navigation will usually want to handle them. Outside
+    /// of [WindowHandler::on_key_down], this always returns false.
+    #[inline]
+    #[must_use]
+    pub fn is_key_repeat(&self) -> bool
+    {
+        self.is_key_repeat
     }
 
     #[inline]
@@ -554,7 +1177,9 @@ impl<UserEventType> WindowHelper<UserEventType>
         self.inner.set_icon_from_rgba_pixels(data, size.into())
     }
 
-    /// Sets the visibility of the mouse cursor.
+    /// Sets the visibility of the mouse cursor. This may be called from any
+    /// callback, and calling it repeatedly with the same value has no further
+    /// effect.
     pub fn set_cursor_visible(&self, visible: bool)
     {
         self.inner.set_cursor_visible(visible)
@@ -580,7 +1205,13 @@ impl<UserEventType> WindowHelper<UserEventType>
     /// Request that the window is redrawn.
     ///
     /// This will cause the [WindowHandler::on_draw] callback to be invoked on
-    /// the next frame.
+    /// the next frame. Multiple calls before the next frame will result in a
+    /// single redraw.
+    ///
+    /// Frames are otherwise only drawn when necessary (for example, when the
+    /// window is resized or uncovered). To render continuously, such as for
+    /// a game or animation, call this function at the end of each invocation
+    /// of [WindowHandler::on_draw].
     #[inline]
     pub fn request_redraw(&self)
     {
@@ -604,9 +1235,85 @@ impl<UserEventType> WindowHelper<UserEventType>
         self.inner.set_fullscreen_mode(mode)
     }
 
+    /// Switches the window to borderless fullscreen mode on the specified
+    /// monitor, which can be obtained using [WindowHelper::get_monitors].
+    ///
+    /// If the monitor is no longer connected, the window is made fullscreen
+    /// on its current monitor instead.
+    ///
+    /// For `WebCanvas`, this is equivalent to calling
+    /// [WindowHelper::set_fullscreen_mode] with
+    /// [WindowFullscreenMode::FullscreenBorderless].
+    pub fn set_fullscreen_borderless_on_monitor(&self, monitor: &Monitor)
+    {
+        self.inner.set_fullscreen_borderless_on_monitor(monitor)
+    }
+
+    /// Returns a list of the monitors connected to the system.
+    ///
+    /// For `WebCanvas`, the browser doesn't allow monitors to be enumerated,
+    /// so this returns a single monitor describing the screen on which the
+    /// page is displayed.
+    #[must_use]
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        self.inner.get_monitors()
+    }
+
+    /// Returns the primary monitor. If the platform doesn't identify a primary
+    /// monitor, the monitor containing the window is returned instead.
+    ///
+    /// See [WindowHelper::get_monitors].
+    #[must_use]
+    pub fn get_primary_monitor(&self) -> Monitor
+    {
+        self.inner.get_primary_monitor()
+    }
+
+    /// Minimizes the window. The [WindowHandler::on_window_minimized]
+    /// callback will be invoked.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn minimize(&self)
+    {
+        self.inner.minimize()
+    }
+
+    /// Maximizes the window.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn maximize(&self)
+    {
+        self.inner.maximize()
+    }
+
+    /// Restores the window to its normal size, if it is currently minimized
+    /// or maximized. If the window was minimized, the
+    /// [WindowHandler::on_window_restored] callback will be invoked.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn restore(&self)
+    {
+        self.inner.restore()
+    }
+
+    /// Returns whether the window is currently minimized, maximized, or
+    /// neither.
+    ///
+    /// For `WebCanvas`, this returns [WindowState::Minimized] if the page is
+    /// hidden, and [WindowState::Normal] otherwise.
+    #[must_use]
+    pub fn get_window_state(&self) -> WindowState
+    {
+        self.inner.get_window_state()
+    }
+
     /// Sets the window size in pixels. This is the window's inner size,
     /// excluding the border.
     ///
+    /// Once the window has been resized, the [WindowHandler::on_resize]
+    /// callback will be invoked with the new size.
+    ///
     /// For `WebCanvas`, this function has no effect.
     pub fn set_size_pixels<S: Into<UVec2>>(&self, size: S)
     {
@@ -621,7 +1328,11 @@ impl<UserEventType> WindowHelper<UserEventType>
 
     /// Sets the position of the window in pixels. If multiple monitors are in
     /// use, this will be the distance from the top left of the display
-    /// area, spanning all the monitors.
+    /// area, spanning all the monitors. Coordinates may be negative, for
+    /// example on a monitor positioned to the left of the primary monitor.
+    ///
+    /// Some platforms (such as Wayland) do not allow applications to move
+    /// their windows, in which case this function has no effect.
     ///
     /// For `WebCanvas`, this function has no effect.
     pub fn set_position_pixels<P: Into<IVec2>>(&self, position: P)
@@ -632,6 +1343,11 @@ impl<UserEventType> WindowHelper<UserEventType>
     /// Sets the window size in scaled device-independent pixels. This is the
     /// window's inner size, excluding the border.
     ///
+    /// The size is converted to physical pixels using the window's current
+    /// scale factor. Once the window has been resized, the
+    /// [WindowHandler::on_resize] callback will be invoked with the new size
+    /// in physical pixels.
+    ///
     /// For `WebCanvas`, this function has no effect.
     pub fn set_size_scaled_pixels<S: Into<Vec2>>(&self, size: S)
     {
@@ -642,12 +1358,101 @@ impl<UserEventType> WindowHelper<UserEventType>
     /// multiple monitors are in use, this will be the distance from the top
     /// left of the display area, spanning all the monitors.
     ///
+    /// Some platforms (such as Wayland) do not allow applications to move
+    /// their windows, in which case this function has no effect.
+    ///
     /// For `WebCanvas`, this function has no effect.
     pub fn set_position_scaled_pixels<P: Into<Vec2>>(&self, position: P)
     {
         self.inner.set_position_scaled_pixels(position)
     }
 
+    /// Sets the position of the input method editor (IME) candidate window, in
+    /// pixels relative to the top left of the window. This should usually be
+    /// the position of the text caret, so that the candidate window appears
+    /// next to the text being edited.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_ime_position<P: Into<Vec2>>(&self, position: P)
+    {
+        self.inner.set_ime_position(position.into())
+    }
+
+    /// Moves the mouse cursor to the specified position in pixels, relative to
+    /// the top left of the window. This uses the same coordinate space as
+    /// [WindowHandler::on_mouse_move], unless a fixed resolution has been
+    /// set using [WindowCreationOptions::with_fixed_resolution].
+    ///
+    /// While the cursor is grabbed using [WindowHelper::set_cursor_grab], it
+    /// is automatically moved back to the center of the window after each
+    /// movement, so calling this has no lasting effect.
+    ///
+    /// Some platforms (such as Wayland) do not allow applications to move the
+    /// cursor, in which case an error is returned.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_cursor_position_pixels<P: Into<Vec2>>(
+        &self,
+        position: P
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.inner.set_cursor_position_pixels(position.into())
+    }
+
+    /// Moves the mouse cursor to the specified position in scaled
+    /// device-independent pixels, relative to the top left of the window. The
+    /// position is converted to physical pixels using the window's current
+    /// scale factor.
+    ///
+    /// See [WindowHelper::set_cursor_position_pixels] for more details.
+    pub fn set_cursor_position_scaled_pixels<P: Into<Vec2>>(
+        &self,
+        position: P
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        let scale_factor = self.get_scale_factor() as f32;
+        self.set_cursor_position_pixels(position.into() * scale_factor)
+    }
+
+    /// Returns the last known position of the mouse cursor in pixels, relative
+    /// to the top left of the window, or `None` if the cursor has not moved
+    /// over the window, or has since left it.
+    ///
+    /// Unlike the position passed to [WindowHandler::on_mouse_move], this is
+    /// not affected by [WindowHelper::set_cursor_grab] or
+    /// [WindowCreationOptions::with_fixed_resolution]. While the cursor is
+    /// grabbed, this position is not updated for `WebCanvas`.
+    #[must_use]
+    pub fn get_cursor_position_pixels(&self) -> Option<Vec2>
+    {
+        self.inner.get_cursor_position_pixels()
+    }
+
+    /// Converts a [MouseScrollDistance] into an approximate number of lines,
+    /// where one line corresponds to a single notch of a typical mouse wheel.
+    /// This allows scrolling and zooming to behave consistently across
+    /// touchpads, which usually report distances in pixels, and mouse wheels,
+    /// which usually report distances in lines.
+    ///
+    /// The conversion is a heuristic, and the platforms differ as follows:
+    ///
+    /// * On desktop platforms, line distances are used unchanged. Pixel
+    ///   distances are converted to scaled pixels, and every 100 scaled pixels
+    ///   count as one line.
+    /// * For `WebCanvas`, browsers report a mouse wheel notch as either 3 lines
+    ///   (e.g. Firefox) or 100 pixels (e.g. Chrome), so line distances are
+    ///   divided by 3 and pixel distances are divided by 100.
+    /// * Page distances count one page as one line, as this is what a single
+    ///   notch produces when the system is configured to scroll by page.
+    ///
+    /// The `z` component of the distance is ignored.
+    #[must_use]
+    pub fn normalize_scroll_distance(&self, distance: MouseScrollDistance)
+        -> Vector2<f64>
+    {
+        self.inner.normalize_scroll_distance(distance)
+    }
+
     /// Gets the window's scale factor.
     #[inline]
     #[must_use]
@@ -656,6 +1461,46 @@ impl<UserEventType> WindowHelper<UserEventType>
         self.inner.get_scale_factor()
     }
 
+    /// Gets the [PresentMode] in use by the window. This may differ from the
+    /// mode requested using [WindowCreationOptions::with_present_mode], if
+    /// that mode was not available on this platform.
+    ///
+    /// Redraws requested using [WindowHelper::request_redraw] are unaffected
+    /// by the present mode, although in [PresentMode::Vsync] mode, each
+    /// redraw may wait for the display's vertical blank.
+    #[inline]
+    #[must_use]
+    pub fn get_present_mode(&self) -> PresentMode
+    {
+        self.inner.get_present_mode()
+    }
+
+    /// Returns the text currently on the system clipboard, or `None` if the
+    /// clipboard is empty, contains something other than text, or can't be
+    /// accessed.
+    ///
+    /// Note: when running in a web browser, the clipboard can't be read, and
+    /// this function always returns `None`. Instead, consider listening for
+    /// the browser's `paste` event.
+    #[must_use]
+    pub fn get_clipboard_string(&self) -> Option<String>
+    {
+        self.inner.get_clipboard_string()
+    }
+
+    /// Places the specified text on the system clipboard.
+    ///
+    /// Note: when running in a web browser, the text is written
+    /// asynchronously, and the browser may refuse to do so, for example if
+    /// the page doesn't have focus. In this case, no error is returned.
+    pub fn set_clipboard_string<S: AsRef<str>>(
+        &self,
+        text: S
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.inner.set_clipboard_string(text.as_ref())
+    }
+
     /// Creates a [UserEventSender], which can be used to post custom events to
     /// this event loop from another thread.
     ///
@@ -714,6 +1559,80 @@ impl WindowStartupInfo
     }
 }
 
+/// Information about a monitor connected to the system. See
+/// [WindowHelper::get_monitors].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monitor
+{
+    name: Option<String>,
+    size_pixels: UVec2,
+    position_pixels: IVec2,
+    scale_factor: f64,
+    refresh_rate_hz: Option<u32>,
+    is_primary: bool
+}
+
+impl Monitor
+{
+    pub(crate) fn new(
+        name: Option<String>,
+        size_pixels: UVec2,
+        position_pixels: IVec2,
+        scale_factor: f64,
+        refresh_rate_hz: Option<u32>,
+        is_primary: bool
+    ) -> Self
+    {
+        Monitor {
+            name,
+            size_pixels,
+            position_pixels,
+            scale_factor,
+            refresh_rate_hz,
+            is_primary
+        }
+    }
+
+    /// The human-readable name of the monitor, if available.
+    pub fn name(&self) -> Option<&str>
+    {
+        self.name.as_deref()
+    }
+
+    /// The resolution of the monitor in pixels.
+    pub fn size_pixels(&self) -> &UVec2
+    {
+        &self.size_pixels
+    }
+
+    /// The position of the top left of the monitor in pixels, relative to
+    /// the desktop. This is always zero for `WebCanvas`.
+    pub fn position_pixels(&self) -> &IVec2
+    {
+        &self.position_pixels
+    }
+
+    /// The scale factor of the monitor. When a high-dpi display is in use,
+    /// this will be greater than `1.0`.
+    pub fn scale_factor(&self) -> f64
+    {
+        self.scale_factor
+    }
+
+    /// The refresh rate of the monitor's current resolution, in hertz, if
+    /// available. This is always `None` for `WebCanvas`.
+    pub fn refresh_rate_hz(&self) -> Option<u32>
+    {
+        self.refresh_rate_hz
+    }
+
+    /// Returns true if this is the primary monitor.
+    pub fn is_primary(&self) -> bool
+    {
+        self.is_primary
+    }
+}
+
 /// Identifies a mouse button.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum MouseButton
@@ -728,6 +1647,101 @@ pub enum MouseButton
     Other(u16)
 }
 
+/// Identifies a connected gamepad. Identifiers are unique while the gamepad
+/// is connected, but may be reused after it is disconnected.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GamepadId(pub(crate) usize);
+
+/// Identifies a gamepad button. The face buttons are named by their position,
+/// as the labels differ between controllers.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum GamepadButton
+{
+    /// The bottom face button (for example, A on an Xbox controller).
+    South,
+    /// The right face button (for example, B on an Xbox controller).
+    East,
+    /// The top face button (for example, Y on an Xbox controller).
+    North,
+    /// The left face button (for example, X on an Xbox controller).
+    West,
+    /// The C button, found on some controllers.
+    C,
+    /// The Z button, found on some controllers.
+    Z,
+    /// The upper left shoulder button.
+    LeftTrigger,
+    /// The lower left shoulder button.
+    LeftTrigger2,
+    /// The upper right shoulder button.
+    RightTrigger,
+    /// The lower right shoulder button.
+    RightTrigger2,
+    /// The select (or back) button.
+    Select,
+    /// The start button.
+    Start,
+    /// The mode (or guide) button.
+    Mode,
+    /// Pressing the left thumb stick.
+    LeftThumb,
+    /// Pressing the right thumb stick.
+    RightThumb,
+    /// Up on the directional pad.
+    DPadUp,
+    /// Down on the directional pad.
+    DPadDown,
+    /// Left on the directional pad.
+    DPadLeft,
+    /// Right on the directional pad.
+    DPadRight,
+    /// A button which could not be identified.
+    Unknown
+}
+
+/// Identifies a gamepad axis.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum GamepadAxis
+{
+    /// The horizontal axis of the left thumb stick.
+    LeftStickX,
+    /// The vertical axis of the left thumb stick.
+    LeftStickY,
+    /// An additional axis on the left of the controller, found on some
+    /// controllers.
+    LeftZ,
+    /// The horizontal axis of the right thumb stick.
+    RightStickX,
+    /// The vertical axis of the right thumb stick.
+    RightStickY,
+    /// An additional axis on the right of the controller, found on some
+    /// controllers.
+    RightZ,
+    /// The horizontal axis of the directional pad, on controllers which report
+    /// it as an axis rather than as buttons.
+    DPadX,
+    /// The vertical axis of the directional pad, on controllers which report
+    /// it as an axis rather than as buttons.
+    DPadY,
+    /// An axis which could not be identified.
+    Unknown
+}
+
+/// The stage of a touch interaction.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum TouchPhase
+{
+    /// A finger has touched the screen.
+    Started,
+    /// A finger has moved across the screen.
+    Moved,
+    /// A finger has been lifted from the screen.
+    Ended,
+    /// The touch was interrupted by the system, for example because the window
+    /// lost focus.
+    Cancelled
+}
+
 /// Describes a difference in the mouse scroll wheel position.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum MouseScrollDistance
@@ -776,7 +1790,36 @@ pub enum MouseScrollDistance
     }
 }
 
-/// A virtual key code.
+/// The number of scaled pixels treated as one line by
+/// [WindowHelper::normalize_scroll_distance].
+pub(crate) const SCROLL_SCALED_PIXELS_PER_LINE: f64 = 100.0;
+
+impl MouseScrollDistance
+{
+    pub(crate) fn to_lines(
+        self,
+        pixels_per_line: f64,
+        lines_per_line: f64
+    ) -> Vector2<f64>
+    {
+        match self {
+            MouseScrollDistance::Lines { x, y, .. } => {
+                Vector2::new(x / lines_per_line, y / lines_per_line)
+            }
+            MouseScrollDistance::Pixels { x, y, .. } => {
+                Vector2::new(x / pixels_per_line, y / pixels_per_line)
+            }
+            MouseScrollDistance::Pages { x, y, .. } => Vector2::new(x, y)
+        }
+    }
+}
+
+/// A virtual key code, identifying a logical key according to the current
+/// keyboard layout. To identify the physical location of a key, see
+/// [KeyScancode].
+///
+/// Note: for `WebCanvas`, the virtual key code is currently based on the
+/// physical key, as if a US keyboard layout were in use.
 #[allow(missing_docs)]
 #[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
 pub enum VirtualKeyCode
@@ -1048,6 +2091,18 @@ pub enum WindowPosition
     PrimaryMonitorPixelsFromTopLeft(IVec2)
 }
 
+/// The current state of the window. See [WindowHelper::get_window_state].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum WindowState
+{
+    /// The window is neither minimized nor maximized.
+    Normal,
+    /// The window is minimized, and is not visible.
+    Minimized,
+    /// The window is maximized.
+    Maximized
+}
+
 /// Whether or not the window is in fullscreen mode.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum WindowFullscreenMode
@@ -1058,18 +2113,41 @@ pub enum WindowFullscreenMode
     FullscreenBorderless
 }
 
+/// Controls how completed frames are presented to the screen.
+///
+/// Note that this depends on platform support. When running in a web
+/// browser, the browser controls when frames are presented, and the mode is
+/// always [PresentMode::Vsync].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+pub enum PresentMode
+{
+    /// Wait for the display's vertical blank before presenting each frame.
+    /// This limits the frame rate to the refresh rate of the display and
+    /// eliminates tearing, but can increase latency.
+    #[default]
+    Vsync,
+    /// Present each frame as soon as it is complete. This allows the frame
+    /// rate to exceed the refresh rate of the display, for example when
+    /// benchmarking, but may cause tearing.
+    Immediate
+}
+
 /// Options used during the creation of a window.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WindowCreationOptions
 {
     pub(crate) mode: WindowCreationMode,
     pub(crate) multisampling: u16,
-    pub(crate) vsync: bool,
+    pub(crate) present_mode: PresentMode,
+    pub(crate) fixed_resolution: Option<UVec2>,
+    pub(crate) srgb_framebuffer: bool,
+    pub(crate) max_fps: Option<u32>,
     pub(crate) always_on_top: bool,
     pub(crate) resizable: bool,
     pub(crate) maximized: bool,
     pub(crate) transparent: bool,
-    pub(crate) decorations: bool
+    pub(crate) decorations: bool,
+    pub(crate) background_color: Option<Color>
 }
 
 impl WindowCreationOptions
@@ -1097,20 +2175,32 @@ impl WindowCreationOptions
         WindowCreationOptions {
             mode,
             multisampling: 16,
-            vsync: true,
+            present_mode: PresentMode::Vsync,
+            fixed_resolution: None,
+            srgb_framebuffer: false,
+            max_fps: None,
             always_on_top: false,
             resizable: true,
             maximized: false,
             decorations: true,
-            transparent: false
+            transparent: false,
+            background_color: None
         }
     }
 
     /// Sets the maximum level of multisampling which will be applied. By
-    /// default this is set to `16`.
+    /// default this is set to `16`. A value of `0` or `1` disables
+    /// multisampling.
     ///
-    /// Note that this depends on platform support, and setting this may have no
-    /// effect.
+    /// Multisampling smooths the edges of polygons, lines, and other shapes.
+    /// Text is already anti-aliased when it is rendered, so is largely
+    /// unaffected by this setting.
+    ///
+    /// If the requested level is not available, lower levels will be tried
+    /// instead, falling back to no multisampling. Note that this depends on
+    /// platform support, and setting this may have no effect. In particular,
+    /// when running in a web browser, the browser decides whether or not to
+    /// use multisampling.
     #[inline]
     #[must_use]
     pub fn with_multisampling(mut self, multisampling: u16) -> Self
@@ -1122,13 +2212,35 @@ impl WindowCreationOptions
     /// Sets whether or not vsync should be enabled. This can increase latency,
     /// but should eliminate tearing. By default this is set to `true`.
     ///
+    /// This is equivalent to calling [WindowCreationOptions::with_present_mode]
+    /// with either [PresentMode::Vsync] or [PresentMode::Immediate].
+    ///
     /// Note that this depends on platform support, and setting this may have no
     /// effect.
     #[inline]
     #[must_use]
-    pub fn with_vsync(mut self, vsync: bool) -> Self
+    pub fn with_vsync(self, vsync: bool) -> Self
     {
-        self.vsync = vsync;
+        self.with_present_mode(match vsync {
+            true => PresentMode::Vsync,
+            false => PresentMode::Immediate
+        })
+    }
+
+    /// Sets the [PresentMode] of the window. By default this is set to
+    /// [PresentMode::Vsync].
+    ///
+    /// If the requested mode is not available, the other mode will be used
+    /// instead. The mode actually in use can be found using
+    /// [WindowHelper::get_present_mode].
+    ///
+    /// Note that this depends on platform support, and setting this may have no
+    /// effect. In particular, this setting is ignored in web browsers.
+    #[inline]
+    #[must_use]
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self
+    {
+        self.present_mode = present_mode;
         self
     }
 
@@ -1184,7 +2296,213 @@ impl WindowCreationOptions
         self.transparent = transparent;
         self
     }
+
+    /// Causes the application to be drawn at a fixed resolution, which is
+    /// then scaled up by the largest whole number which fits in the window,
+    /// using nearest-neighbor filtering so that each pixel stays crisp. Any
+    /// remaining space around the edges of the window is filled with black.
+    /// This is useful for pixel art.
+    ///
+    /// The [Graphics2D] object passed to [WindowHandler::on_draw] will draw
+    /// into a canvas of the specified size, and mouse and touch positions
+    /// will be relative to the top left of this canvas. Other sizes, such as
+    /// the size passed to [WindowHandler::on_resize], are still in window
+    /// pixels.
+    ///
+    /// If the window is smaller than the specified resolution, the canvas is
+    /// drawn at its original size, and will be cropped.
+    #[inline]
+    #[must_use]
+    pub fn with_fixed_resolution<S: Into<UVec2>>(mut self, resolution: S) -> Self
+    {
+        self.fixed_resolution = Some(resolution.into());
+        self
+    }
+
+    /// Requests an sRGB-capable framebuffer, and enables sRGB-correct
+    /// rendering, so that blending is performed in linear space. The default
+    /// is `false`.
+    ///
+    /// This makes gradients and alpha compositing more physically accurate,
+    /// but the results will look different to blending in sRGB space. See
+    /// [GLRenderer::set_srgb_framebuffer_enabled] for more details.
+    ///
+    /// If the platform cannot provide an sRGB-capable framebuffer, a warning
+    /// is logged and rendering continues in sRGB space.
+    #[inline]
+    #[must_use]
+    pub fn with_srgb_framebuffer(mut self, srgb_framebuffer: bool) -> Self
+    {
+        self.srgb_framebuffer = srgb_framebuffer;
+        self
+    }
+
+    /// Limits the number of frames drawn per second, when redraws are
+    /// requested continuously using [WindowHelper::request_redraw]. The
+    /// event loop waits between frames, rather than drawing as fast as
+    /// possible. By default there is no limit, and a value of `0` also
+    /// removes the limit.
+    ///
+    /// When [PresentMode::Vsync] is in use, the frame rate is also limited by
+    /// the refresh rate of the display, so the effective limit is whichever
+    /// is lower. Setting a limit is most useful with
+    /// [PresentMode::Immediate], or on platforms which ignore vsync.
+    ///
+    /// Note that the timing depends on the precision of the platform's timers,
+    /// so the actual frame rate may be slightly lower than the limit.
+    #[inline]
+    #[must_use]
+    pub fn with_max_fps(mut self, max_fps: u32) -> Self
+    {
+        self.max_fps = Some(max_fps).filter(|max_fps| *max_fps > 0);
+        self
+    }
+
+    /// Clears the window to the specified color at the start of each frame,
+    /// before [WindowHandler::on_draw] is called. This avoids displaying
+    /// undefined contents (for example on the first frame) if the handler
+    /// doesn't clear the screen itself.
+    ///
+    /// By default, the screen isn't cleared automatically, and the handler
+    /// should call [Graphics2D::clear_screen].
+    #[inline]
+    #[must_use]
+    pub fn with_background_color(mut self, color: Color) -> Self
+    {
+        self.background_color = Some(color);
+        self
+    }
 }
 
-/// Type representing a keyboard scancode.
+/// Type representing a keyboard scancode, which identifies the physical
+/// location of a key on the keyboard, regardless of the keyboard layout.
+///
+/// For example, the key to the right of Tab has the same scancode on both
+/// QWERTY and AZERTY keyboards, even though it types `Q` on one and `A` on the
+/// other. This makes scancodes suitable for bindings which depend on the
+/// position of keys, such as WASD movement controls. In contrast,
+/// [VirtualKeyCode] identifies the logical key, taking into account the
+/// keyboard layout, which is more suitable for shortcuts such as Ctrl+Z.
+///
+/// Scancodes are platform-specific, so applications should allow the user to
+/// choose key bindings by pressing the key, rather than hard-coding
+/// scancodes. For `WebCanvas`, the scancode is derived from the physical key
+/// reported by the browser, using the values from a PC keyboard.
 pub type KeyScancode = u32;
+
+#[cfg(test)]
+mod test
+{
+    use crate::dimen::{UVec2, Vector2};
+    use crate::shape::Rectangle;
+    use crate::window::{
+        integer_scaled_area,
+        MouseScrollDistance,
+        UserEventSender,
+        WindowId
+    };
+
+    #[test]
+    pub fn test_scroll_distance_to_lines()
+    {
+        // Native: one line per notch, scale factor of 2
+        assert_eq!(
+            Vector2::new(0.0, -1.0),
+            MouseScrollDistance::Lines {
+                x: 0.0,
+                y: -1.0,
+                z: 0.0
+            }
+            .to_lines(200.0, 1.0)
+        );
+
+        assert_eq!(
+            Vector2::new(0.5, 1.5),
+            MouseScrollDistance::Pixels {
+                x: 100.0,
+                y: 300.0,
+                z: 0.0
+            }
+            .to_lines(200.0, 1.0)
+        );
+
+        // Web: three lines or 100 pixels per notch
+        assert_eq!(
+            Vector2::new(0.0, 1.0),
+            MouseScrollDistance::Lines {
+                x: 0.0,
+                y: 3.0,
+                z: 5.0
+            }
+            .to_lines(100.0, 3.0)
+        );
+
+        assert_eq!(
+            Vector2::new(-1.0, 1.0),
+            MouseScrollDistance::Pixels {
+                x: -100.0,
+                y: 100.0,
+                z: 0.0
+            }
+            .to_lines(100.0, 3.0)
+        );
+
+        assert_eq!(
+            Vector2::new(0.0, -2.0),
+            MouseScrollDistance::Pages {
+                x: 0.0,
+                y: -2.0,
+                z: 0.0
+            }
+            .to_lines(100.0, 3.0)
+        );
+    }
+
+    #[test]
+    pub fn test_integer_scaled_area()
+    {
+        // Exact fit
+        assert_eq!(
+            Rectangle::from_tuples((0.0, 0.0), (640.0, 480.0)),
+            integer_scaled_area(UVec2::new(320, 240), UVec2::new(640, 480))
+        );
+
+        // The height limits the scale to 2, leaving space at the sides
+        assert_eq!(
+            Rectangle::from_tuples((180.0, 10.0), (820.0, 490.0)),
+            integer_scaled_area(UVec2::new(320, 240), UVec2::new(1000, 500))
+        );
+
+        // Odd margins are rounded down
+        assert_eq!(
+            Rectangle::from_tuples((1.0, 1.0), (101.0, 101.0)),
+            integer_scaled_area(UVec2::new(100, 100), UVec2::new(103, 103))
+        );
+
+        // The canvas is never scaled below its original size
+        assert_eq!(
+            Rectangle::from_tuples((-10.0, -20.0), (310.0, 220.0)),
+            integer_scaled_area(UVec2::new(320, 240), UVec2::new(300, 200))
+        );
+    }
+
+    #[test]
+    pub fn test_window_id_unique()
+    {
+        let first = WindowId::next();
+        let second = WindowId::next();
+
+        assert_ne!(first, second);
+    }
+
+    // The web sender is tied to the browser's main thread
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    pub fn test_user_event_sender_can_move_to_another_thread()
+    {
+        fn assert_send_and_clone<T: Send + Clone + 'static>() {}
+
+        assert_send_and_clone::<UserEventSender<String>>();
+        assert_send_and_clone::<UserEventSender<Vec<u8>>>();
+    }
+}
@@ -14,15 +14,21 @@
  *  limitations under the License.
  */
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+use copypasta::{ClipboardContext, ClipboardProvider};
 use glutin::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 use glutin::event::{
+    DeviceEvent as GlutinDeviceEvent,
     ElementState as GlutinElementState,
     Event as GlutinEvent,
     MouseScrollDelta as GlutinMouseScrollDelta,
-    TouchPhase,
+    Touch as GlutinTouch,
+    TouchPhase as GlutinTouchPhase,
     VirtualKeyCode as GlutinVirtualKeyCode,
     WindowEvent as GlutinWindowEvent
 };
@@ -34,6 +40,7 @@ use glutin::window::{
     WindowBuilder as GlutinWindowBuilder
 };
 
+use crate::color::Color;
 use crate::dimen::{IVec2, UVec2, Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
 use crate::glbackend::constants::GL_VERSION;
@@ -41,9 +48,13 @@ use crate::glbackend::{GLBackend, GLBackendGlow};
 use crate::window::{
     DrawingWindowHandler,
     EventLoopSendError,
+    KeyScancode,
     ModifiersState,
+    Monitor,
     MouseButton,
     MouseScrollDistance,
+    PresentMode,
+    TouchPhase,
     UserEventSender,
     VirtualKeyCode,
     WindowCreationError,
@@ -55,8 +66,12 @@ use crate::window::{
     WindowHelper,
     WindowPosition,
     WindowSize,
-    WindowStartupInfo
+    WindowStartupInfo,
+    WindowState,
+    SCROLL_SCALED_PIXELS_PER_LINE
 };
+#[cfg(feature = "gamepad")]
+use crate::window::{GamepadAxis, GamepadButton, GamepadId};
 use crate::GLRenderer;
 
 pub(crate) struct WindowHelperGlutin<UserEventType: 'static>
@@ -66,7 +81,15 @@ pub(crate) struct WindowHelperGlutin<UserEventType: 'static>
     redraw_requested: Cell<bool>,
     terminate_requested: bool,
     physical_size: UVec2,
-    is_mouse_grabbed: Cell<bool>
+    is_mouse_grabbed: Cell<bool>,
+    is_minimized: Cell<bool>,
+    cursor_position: Option<Vec2>,
+    is_focused: bool,
+    present_mode: PresentMode,
+    clipboard: RefCell<Option<ClipboardContext>>,
+    dropped_files: Vec<PathBuf>,
+    frame_limiter: Option<FrameLimiter>,
+    held_keys: HeldKeys
 }
 
 impl<UserEventType> WindowHelperGlutin<UserEventType>
@@ -75,7 +98,8 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
     pub fn new(
         context: &Rc<glutin::ContextWrapper<glutin::PossiblyCurrent, GlutinWindow>>,
         event_proxy: EventLoopProxy<UserEventGlutin<UserEventType>>,
-        initial_physical_size: UVec2
+        initial_physical_size: UVec2,
+        present_mode: PresentMode
     ) -> Self
     {
         WindowHelperGlutin {
@@ -84,7 +108,15 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
             redraw_requested: Cell::new(false),
             terminate_requested: false,
             physical_size: initial_physical_size,
-            is_mouse_grabbed: Cell::new(false)
+            is_mouse_grabbed: Cell::new(false),
+            is_minimized: Cell::new(false),
+            cursor_position: None,
+            is_focused: true,
+            present_mode,
+            clipboard: RefCell::new(None),
+            dropped_files: Vec::new(),
+            frame_limiter: None,
+            held_keys: HeldKeys::new()
         }
     }
 
@@ -101,6 +133,16 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.redraw_requested.set(redraw_requested);
     }
 
+    /// Returns the time at which the next frame may be drawn, if the frame
+    /// rate is limited and it's too soon to draw a frame.
+    #[inline]
+    fn get_frame_wait_deadline(&self, now: Instant) -> Option<Instant>
+    {
+        self.frame_limiter
+            .as_ref()
+            .and_then(|frame_limiter| frame_limiter.wait_deadline(now))
+    }
+
     #[inline]
     pub fn get_event_loop_action(&self) -> WindowEventLoopAction
     {
@@ -140,19 +182,21 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         grabbed: bool
     ) -> Result<(), BacktraceError<ErrorMessage>>
     {
+        // The cursor is recentered when releasing the grab as well, so that it
+        // reappears in a predictable place. Some platforms (e.g. Wayland) don't
+        // support moving the cursor, so don't treat this as fatal to the grab.
         let central_position = self.physical_size / 2;
-        self.window_context
-            .window()
-            .set_cursor_position(PhysicalPosition::new(
-                central_position.x as i32,
-                central_position.y as i32
-            ))
-            .map_err(|err| {
-                ErrorMessage::msg_with_cause(
-                    "Failed to move cursor to center of window",
-                    err
-                )
-            })?;
+
+        if let Err(err) =
+            self.window_context
+                .window()
+                .set_cursor_position(PhysicalPosition::new(
+                    central_position.x as i32,
+                    central_position.y as i32
+                ))
+        {
+            log::warn!("Failed to move cursor to center of window: {:?}", err);
+        }
 
         match self.window_context.window().set_cursor_grab(grabbed) {
             Ok(_) => {
@@ -213,6 +257,118 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         }
     }
 
+    pub fn set_fullscreen_borderless_on_monitor(&self, monitor: &Monitor)
+    {
+        let window = self.window_context.window();
+
+        let handle = window.available_monitors().find(|handle| {
+            handle.name().as_deref() == monitor.name()
+                && IVec2::new(handle.position().x, handle.position().y)
+                    == *monitor.position_pixels()
+        });
+
+        if handle.is_none() {
+            log::warn!("Monitor not found, using current monitor for fullscreen");
+        }
+
+        window.set_fullscreen(Some(glutin::window::Fullscreen::Borderless(handle)));
+
+        if self
+            .event_proxy
+            .send_event(UserEventGlutin::FullscreenStatusChanged(true))
+            .is_err()
+        {
+            log::error!(
+                "Failed to notify app of fullscreen status change: event loop closed"
+            );
+        }
+    }
+
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        let window = self.window_context.window();
+        let primary = window.primary_monitor();
+
+        window
+            .available_monitors()
+            .map(|handle| monitor_from_handle(&handle, primary.as_ref()))
+            .collect()
+    }
+
+    pub fn get_primary_monitor(&self) -> Monitor
+    {
+        let window = self.window_context.window();
+        let primary = window.primary_monitor();
+
+        match primary
+            .clone()
+            .or_else(|| window.current_monitor())
+            .or_else(|| window.available_monitors().next())
+        {
+            Some(handle) => monitor_from_handle(&handle, primary.as_ref()),
+            None => {
+                log::error!("No monitors found, using window size instead");
+
+                Monitor::new(
+                    None,
+                    self.physical_size,
+                    IVec2::ZERO,
+                    window.scale_factor(),
+                    None,
+                    true
+                )
+            }
+        }
+    }
+
+    fn notify_minimized_status(&self, minimized: bool)
+    {
+        if self
+            .event_proxy
+            .send_event(UserEventGlutin::MinimizedStatusChanged(minimized))
+            .is_err()
+        {
+            log::error!(
+                "Failed to notify app of minimized status change: event loop closed"
+            );
+        }
+    }
+
+    pub fn minimize(&self)
+    {
+        self.window_context.window().set_minimized(true);
+        self.notify_minimized_status(true);
+    }
+
+    pub fn maximize(&self)
+    {
+        let window = self.window_context.window();
+
+        window.set_minimized(false);
+        window.set_maximized(true);
+        self.notify_minimized_status(false);
+    }
+
+    pub fn restore(&self)
+    {
+        let window = self.window_context.window();
+
+        window.set_minimized(false);
+        window.set_maximized(false);
+        self.notify_minimized_status(false);
+    }
+
+    pub fn get_window_state(&self) -> WindowState
+    {
+        if self.is_minimized.get() {
+            WindowState::Minimized
+        } else if self.window_context.window().is_maximized() {
+            WindowState::Maximized
+        } else {
+            WindowState::Normal
+        }
+    }
+
     pub fn set_size_pixels<S: Into<UVec2>>(&self, size: S)
     {
         let size = size.into();
@@ -256,6 +412,33 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         );
     }
 
+    pub fn set_cursor_position_pixels(
+        &self,
+        position: Vec2
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.window_context
+            .window()
+            .set_cursor_position(PhysicalPosition::new(position.x, position.y))
+            .map_err(|err| {
+                ErrorMessage::msg_with_cause("Failed to set cursor position", err)
+            })
+    }
+
+    pub fn set_ime_position(&self, position: Vec2)
+    {
+        self.window_context
+            .window()
+            .set_ime_position(PhysicalPosition::new(position.x, position.y));
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_cursor_position_pixels(&self) -> Option<Vec2>
+    {
+        self.cursor_position
+    }
+
     #[inline]
     #[must_use]
     pub fn get_scale_factor(&self) -> f64
@@ -263,6 +446,71 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.window_context.window().scale_factor()
     }
 
+    pub fn normalize_scroll_distance(&self, distance: MouseScrollDistance)
+        -> Vector2<f64>
+    {
+        // Pixel distances are reported in physical pixels
+        distance.to_lines(SCROLL_SCALED_PIXELS_PER_LINE * self.get_scale_factor(), 1.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_present_mode(&self) -> PresentMode
+    {
+        self.present_mode
+    }
+
+    fn with_clipboard<R, F>(&self, action: F) -> Result<R, BacktraceError<ErrorMessage>>
+    where
+        F: FnOnce(&mut ClipboardContext) -> Result<R, BacktraceError<ErrorMessage>>
+    {
+        let mut clipboard = self.clipboard.borrow_mut();
+
+        // The clipboard context is kept for the lifetime of the window, as on
+        // some platforms the copied text is lost when it is dropped
+        if clipboard.is_none() {
+            *clipboard = Some(ClipboardContext::new().map_err(|err| {
+                ErrorMessage::msg(format!("Failed to access clipboard: {:?}", err))
+            })?);
+        }
+
+        match clipboard.as_mut() {
+            Some(clipboard) => action(clipboard),
+            None => Err(ErrorMessage::msg("Clipboard not available"))
+        }
+    }
+
+    pub fn get_clipboard_string(&self) -> Option<String>
+    {
+        let result = self.with_clipboard(|clipboard| {
+            clipboard.get_contents().map_err(|err| {
+                ErrorMessage::msg(format!("Failed to get clipboard contents: {:?}", err))
+            })
+        });
+
+        match result {
+            Ok(text) => Some(text),
+            Err(err) => {
+                // This is expected if the clipboard is empty, or contains
+                // something other than text
+                log::info!("No text available from clipboard: {:?}", err);
+                None
+            }
+        }
+    }
+
+    pub fn set_clipboard_string(
+        &self,
+        text: &str
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.with_clipboard(|clipboard| {
+            clipboard.set_contents(text.to_string()).map_err(|err| {
+                ErrorMessage::msg(format!("Failed to set clipboard contents: {:?}", err))
+            })
+        })
+    }
+
     pub fn create_user_event_sender(&self) -> UserEventSender<UserEventType>
     {
         UserEventSender::new(UserEventSenderGlutin::new(self.event_proxy.clone()))
@@ -273,7 +521,12 @@ pub(crate) struct WindowGlutin<UserEventType: 'static>
 {
     event_loop: EventLoop<UserEventGlutin<UserEventType>>,
     window_context: Rc<glutin::ContextWrapper<glutin::PossiblyCurrent, GlutinWindow>>,
-    gl_backend: Rc<dyn GLBackend>
+    gl_backend: Rc<dyn GLBackend>,
+    present_mode: PresentMode,
+    fixed_resolution: Option<UVec2>,
+    srgb_framebuffer: bool,
+    max_fps: Option<u32>,
+    background_color: Option<Color>
 }
 
 impl<UserEventType: 'static> WindowGlutin<UserEventType>
@@ -336,10 +589,24 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
             }
         }
 
-        let window_context = create_best_context(&window_builder, &event_loop, &options)
-            .ok_or_else(|| {
-                BacktraceError::new(WindowCreationError::SuitableContextNotFound)
-            })?;
+        let (window_context, present_mode) =
+            create_best_context(&window_builder, &event_loop, &options)
+                .or_else(|| {
+                    if !options.srgb_framebuffer {
+                        return None;
+                    }
+
+                    log::warn!("Failed to create sRGB context, retrying without sRGB");
+
+                    create_best_context(
+                        &window_builder,
+                        &event_loop,
+                        &options.clone().with_srgb_framebuffer(false)
+                    )
+                })
+                .ok_or_else(|| {
+                    BacktraceError::new(WindowCreationError::SuitableContextNotFound)
+                })?;
 
         let window_context = Rc::new(match unsafe { window_context.make_current() } {
             Ok(window_context) => window_context,
@@ -351,6 +618,14 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
             }
         });
 
+        // The pixel format can only be queried once the context is current
+        let srgb_framebuffer =
+            options.srgb_framebuffer && window_context.get_pixel_format().srgb;
+
+        if options.srgb_framebuffer && !srgb_framebuffer {
+            log::warn!("sRGB framebuffer not available, blending in sRGB space");
+        }
+
         if let WindowCreationMode::Windowed {
             position: Some(position),
             ..
@@ -397,7 +672,12 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
         Ok(WindowGlutin {
             event_loop,
             window_context,
-            gl_backend
+            gl_backend,
+            present_mode,
+            fixed_resolution: options.fixed_resolution,
+            srgb_framebuffer,
+            max_fps: options.max_fps,
+            background_color: options.background_color
         })
     }
 
@@ -411,6 +691,24 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
         self.window_context.window().inner_size().into()
     }
 
+    fn loop_set_minimized_status<Handler>(
+        handler: &mut DrawingWindowHandler<UserEventType, Handler>,
+        helper: &mut WindowHelper<UserEventType>,
+        minimized: bool
+    ) where
+        Handler: WindowHandler<UserEventType> + 'static
+    {
+        if helper.inner().is_minimized.replace(minimized) == minimized {
+            return;
+        }
+
+        if minimized {
+            handler.on_window_minimized(helper);
+        } else {
+            handler.on_window_restored(helper);
+        }
+    }
+
     fn loop_handle_event<Handler>(
         window_context: &glutin::ContextWrapper<glutin::PossiblyCurrent, GlutinWindow>,
         handler: &mut DrawingWindowHandler<UserEventType, Handler>,
@@ -430,6 +728,9 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                 UserEventGlutin::FullscreenStatusChanged(fullscreen) => {
                     handler.on_fullscreen_status_changed(helper, fullscreen)
                 }
+                UserEventGlutin::MinimizedStatusChanged(minimized) => {
+                    Self::loop_set_minimized_status(handler, helper, minimized)
+                }
                 UserEventGlutin::UserEvent(event) => handler.on_user_event(helper, event)
             },
 
@@ -443,14 +744,25 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                     log::info!("Resized: {:?}", physical_size);
                     window_context.resize(physical_size);
                     helper.inner().physical_size = physical_size.into();
-                    handler.on_resize(helper, physical_size.into())
+                    handler.on_resize(helper, physical_size.into());
+
+                    // Some platforms report minimizing as a resize to zero
+                    let minimized = physical_size.width == 0 && physical_size.height == 0;
+                    Self::loop_set_minimized_status(handler, helper, minimized);
                 }
 
-                GlutinWindowEvent::CloseRequested => return WindowEventLoopAction::Exit,
+                // If the handler vetoes the request, the event is ignored
+                GlutinWindowEvent::CloseRequested
+                    if handler.on_close_requested(helper) =>
+                {
+                    return WindowEventLoopAction::Exit;
+                }
 
                 GlutinWindowEvent::CursorMoved { position, .. } => {
                     let position = Vector2::new(position.x, position.y).into_f32();
 
+                    helper.inner().cursor_position = Some(position);
+
                     if helper.inner().is_mouse_grabbed.get() {
                         let central_position = helper.inner().physical_size / 2;
                         window_context
@@ -471,6 +783,19 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                     };
                 }
 
+                GlutinWindowEvent::Focused(focused) => {
+                    helper.inner().is_focused = focused;
+
+                    // Keys released while unfocused won't be reported
+                    if !focused {
+                        helper.inner().held_keys.clear();
+                    }
+                }
+
+                GlutinWindowEvent::CursorLeft { .. } => {
+                    helper.inner().cursor_position = None;
+                }
+
                 GlutinWindowEvent::MouseInput { state, button, .. } => match state {
                     GlutinElementState::Pressed => {
                         handler.on_mouse_button_down(helper, button.into())
@@ -482,7 +807,7 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
 
                 GlutinWindowEvent::MouseWheel {
                     delta,
-                    phase: TouchPhase::Moved,
+                    phase: GlutinTouchPhase::Moved,
                     ..
                 } => {
                     let distance = match delta {
@@ -505,15 +830,34 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                     handler.on_mouse_wheel_scroll(helper, distance);
                 }
 
+                GlutinWindowEvent::Touch(GlutinTouch {
+                    phase,
+                    location,
+                    id,
+                    ..
+                }) => {
+                    let position = Vector2::new(location.x, location.y).into_f32();
+                    handler.on_touch(helper, id, phase.into(), position);
+                }
+
                 GlutinWindowEvent::KeyboardInput { input, .. } => {
                     let virtual_key_code =
                         input.virtual_keycode.map(VirtualKeyCode::from);
 
                     match input.state {
                         GlutinElementState::Pressed => {
-                            handler.on_key_down(helper, virtual_key_code, input.scancode)
+                            let is_repeat =
+                                helper.inner().held_keys.press(input.scancode);
+
+                            handler.on_key_down(
+                                helper,
+                                virtual_key_code,
+                                input.scancode,
+                                is_repeat
+                            )
                         }
                         GlutinElementState::Released => {
+                            helper.inner().held_keys.release(input.scancode);
                             handler.on_key_up(helper, virtual_key_code, input.scancode)
                         }
                     }
@@ -527,18 +871,55 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                     handler.on_keyboard_modifiers_changed(helper, state.into())
                 }
 
+                GlutinWindowEvent::DroppedFile(path) => {
+                    // Each file arrives in a separate event, so they're
+                    // delivered together once all events have been processed
+                    helper.inner().dropped_files.push(path)
+                }
+
                 _ => {}
             },
 
+            // Device events are received even when the window isn't focused
+            GlutinEvent::DeviceEvent {
+                event: GlutinDeviceEvent::MouseMotion { delta: (x, y) },
+                ..
+            } if helper.inner().is_focused => {
+                handler.on_mouse_raw_motion(helper, Vector2::new(x, y).into_f32());
+            }
+
+            GlutinEvent::Suspended => handler.on_suspend(helper),
+
+            GlutinEvent::Resumed => {
+                handler.on_resume(helper);
+                helper.request_redraw();
+            }
+
+            GlutinEvent::MainEventsCleared => {
+                let paths = std::mem::take(&mut helper.inner().dropped_files);
+
+                if !paths.is_empty() {
+                    handler.on_files_dropped(helper, paths, None);
+                }
+            }
+
             GlutinEvent::RedrawRequested(_) => {
                 helper.inner().set_redraw_requested(true);
             }
 
             GlutinEvent::RedrawEventsCleared => {
-                if helper.inner().is_redraw_requested() {
+                let now = Instant::now();
+
+                if helper.inner().is_redraw_requested()
+                    && helper.inner().get_frame_wait_deadline(now).is_none()
+                {
                     helper.inner().set_redraw_requested(false);
                     handler.on_draw(helper);
                     window_context.swap_buffers().unwrap();
+
+                    if let Some(frame_limiter) = &mut helper.inner().frame_limiter {
+                        frame_limiter.frame_drawn(now);
+                    }
                 }
             }
 
@@ -548,10 +929,16 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
         helper.inner().get_event_loop_action()
     }
 
-    pub fn run_loop<Handler>(self, handler: Handler, renderer: GLRenderer) -> !
+    pub fn run_loop<Handler>(self, handler: Handler, mut renderer: GLRenderer) -> !
     where
         Handler: WindowHandler<UserEventType> + 'static
     {
+        if self.srgb_framebuffer {
+            if let Err(err) = renderer.set_srgb_framebuffer_enabled(true) {
+                log::error!("Failed to enable sRGB framebuffer: {:?}", err);
+            }
+        }
+
         let window_context = self.window_context.clone();
         let event_loop = self.event_loop;
 
@@ -559,12 +946,24 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
 
         let mut handler = DrawingWindowHandler::new(handler, renderer);
 
+        if let Some(resolution) = self.fixed_resolution {
+            handler =
+                handler.with_fixed_resolution(resolution, initial_viewport_size_pixels);
+        }
+
+        if let Some(color) = self.background_color {
+            handler = handler.with_background_color(color);
+        }
+
         let mut helper = WindowHelper::new(WindowHelperGlutin::new(
             &window_context,
             event_loop.create_proxy(),
-            initial_viewport_size_pixels
+            initial_viewport_size_pixels,
+            self.present_mode
         ));
 
+        helper.inner().frame_limiter = self.max_fps.map(FrameLimiter::new);
+
         handler.on_start(
             &mut helper,
             WindowStartupInfo::new(
@@ -584,6 +983,8 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
             }
         }
 
+        let mut gamepads = GamepadPoller::new();
+
         let mut handler = Some(handler);
 
         event_loop.run(
@@ -594,6 +995,10 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
                     if handler.is_none() {
                         ControlFlow::Exit
                     } else {
+                        if let GlutinEvent::MainEventsCleared = event {
+                            gamepads.poll(handler.as_mut().unwrap(), &mut helper);
+                        }
+
                         let action = WindowGlutin::loop_handle_event(
                             &window_context,
                             handler.as_mut().unwrap(),
@@ -603,10 +1008,17 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
 
                         match action {
                             WindowEventLoopAction::Continue => {
-                                if helper.inner().is_redraw_requested() {
-                                    ControlFlow::Poll
+                                if !helper.inner().is_redraw_requested() {
+                                    match gamepads.next_poll_deadline(Instant::now()) {
+                                        None => ControlFlow::Wait,
+                                        Some(deadline) => ControlFlow::WaitUntil(deadline)
+                                    }
+                                } else if let Some(deadline) =
+                                    helper.inner().get_frame_wait_deadline(Instant::now())
+                                {
+                                    ControlFlow::WaitUntil(deadline)
                                 } else {
-                                    ControlFlow::Wait
+                                    ControlFlow::Poll
                                 }
                             }
                             WindowEventLoopAction::Exit => {
@@ -628,20 +1040,192 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
     }
 }
 
+/// Limits the rate at which frames are drawn.
+struct FrameLimiter
+{
+    frame_duration: Duration,
+    next_frame: Option<Instant>
+}
+
+impl FrameLimiter
+{
+    fn new(max_fps: u32) -> Self
+    {
+        FrameLimiter {
+            frame_duration: Duration::from_secs_f64(1.0 / max_fps as f64),
+            next_frame: None
+        }
+    }
+
+    /// Returns the time to wait until before drawing the next frame, or `None`
+    /// if a frame may be drawn now.
+    fn wait_deadline(&self, now: Instant) -> Option<Instant>
+    {
+        self.next_frame.filter(|next_frame| *next_frame > now)
+    }
+
+    fn frame_drawn(&mut self, now: Instant)
+    {
+        // Schedule frames relative to the previous deadline, so that the
+        // frame rate doesn't drift below the limit due to wakeup latency. If
+        // we've fallen more than a frame behind, start again from now.
+        self.next_frame = Some(match self.next_frame {
+            Some(next_frame) if now < next_frame + self.frame_duration => {
+                next_frame + self.frame_duration
+            }
+            _ => now + self.frame_duration
+        });
+    }
+}
+
+/// Tracks which keys are held down, so that automatically repeated key presses
+/// can be identified.
+struct HeldKeys
+{
+    keys: HashSet<KeyScancode>
+}
+
+impl HeldKeys
+{
+    fn new() -> Self
+    {
+        HeldKeys {
+            keys: HashSet::new()
+        }
+    }
+
+    /// Records that the key was pressed, and returns true if the key was
+    /// already held down.
+    fn press(&mut self, scancode: KeyScancode) -> bool
+    {
+        !self.keys.insert(scancode)
+    }
+
+    fn release(&mut self, scancode: KeyScancode)
+    {
+        self.keys.remove(&scancode);
+    }
+
+    fn clear(&mut self)
+    {
+        self.keys.clear();
+    }
+}
+
+/// Polls for gamepad input, if the `gamepad` feature is enabled.
+struct GamepadPoller
+{
+    #[cfg(feature = "gamepad")]
+    gilrs: Option<gilrs::Gilrs>
+}
+
+impl GamepadPoller
+{
+    /// Gamepads don't wake the event loop, so they must be polled
+    /// periodically while waiting for other events.
+    #[cfg(feature = "gamepad")]
+    const POLL_INTERVAL: Duration = Duration::from_millis(8);
+
+    fn new() -> Self
+    {
+        GamepadPoller {
+            #[cfg(feature = "gamepad")]
+            gilrs: match gilrs::Gilrs::new() {
+                Ok(gilrs) => Some(gilrs),
+                Err(err) => {
+                    log::warn!("Failed to initialize gamepad support: {}", err);
+                    None
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "gamepad"), allow(unused_variables))]
+    fn next_poll_deadline(&self, now: Instant) -> Option<Instant>
+    {
+        #[cfg(feature = "gamepad")]
+        if self.gilrs.is_some() {
+            return Some(now + Self::POLL_INTERVAL);
+        }
+
+        None
+    }
+
+    #[cfg_attr(not(feature = "gamepad"), allow(unused_variables))]
+    fn poll<UserEventType, Handler>(
+        &mut self,
+        handler: &mut DrawingWindowHandler<UserEventType, Handler>,
+        helper: &mut WindowHelper<UserEventType>
+    ) where
+        Handler: WindowHandler<UserEventType> + 'static
+    {
+        #[cfg(feature = "gamepad")]
+        if let Some(gilrs) = &mut self.gilrs {
+            while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+                let id = GamepadId::from(id);
+
+                match event {
+                    gilrs::EventType::Connected => {
+                        handler.on_gamepad_connected(helper, id)
+                    }
+                    gilrs::EventType::Disconnected => {
+                        handler.on_gamepad_disconnected(helper, id)
+                    }
+                    gilrs::EventType::ButtonPressed(button, _) => {
+                        handler.on_gamepad_button_down(helper, id, button.into())
+                    }
+                    gilrs::EventType::ButtonReleased(button, _) => {
+                        handler.on_gamepad_button_up(helper, id, button.into())
+                    }
+                    gilrs::EventType::AxisChanged(axis, value, _) => handler
+                        .on_gamepad_axis(helper, id, axis.into(), value.clamp(-1.0, 1.0)),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
 fn create_best_context<UserEventType>(
     window_builder: &GlutinWindowBuilder,
     event_loop: &EventLoop<UserEventType>,
     options: &WindowCreationOptions
-) -> Option<glutin::WindowedContext<glutin::NotCurrent>>
+) -> Option<(glutin::WindowedContext<glutin::NotCurrent>, PresentMode)>
 {
-    for vsync in &[options.vsync, true, false] {
-        for multisampling in &[options.multisampling, 16, 8, 4, 2, 1, 0] {
-            log::info!("Trying vsync={}, multisampling={}...", vsync, multisampling);
+    // If the requested level of multisampling isn't available, fall back to
+    // lower levels, and eventually to no multisampling at all
+    let multisampling_levels: Vec<u16> = std::iter::once(options.multisampling)
+        .chain(
+            [16, 8, 4, 2, 0]
+                .iter()
+                .copied()
+                .filter(|level| *level < options.multisampling)
+        )
+        .collect();
+
+    let present_modes = [
+        options.present_mode,
+        PresentMode::Vsync,
+        PresentMode::Immediate
+    ];
+
+    for present_mode in &present_modes {
+        for multisampling in &multisampling_levels {
+            log::info!(
+                "Trying present_mode={:?}, multisampling={}...",
+                present_mode,
+                multisampling
+            );
 
             let mut windowed_context = glutin::ContextBuilder::new()
-                .with_vsync(*vsync)
+                .with_vsync(*present_mode == PresentMode::Vsync)
+                .with_depth_buffer(24)
                 .with_gl(glutin::GlRequest::Specific(glutin::Api::OpenGl, (2, 0)));
 
+            if options.srgb_framebuffer {
+                windowed_context = windowed_context.with_srgb(true);
+            }
+
             if *multisampling > 1 {
                 windowed_context = windowed_context.with_multisampling(*multisampling);
             }
@@ -652,7 +1236,7 @@ fn create_best_context<UserEventType>(
             match result {
                 Ok(context) => {
                     log::info!("Context created");
-                    return Some(context);
+                    return Some((context, *present_mode));
                 }
                 Err(err) => {
                     log::info!("Failed with error: {:?}", err);
@@ -700,6 +1284,30 @@ fn position_window(
     }
 }
 
+fn monitor_from_handle(handle: &MonitorHandle, primary: Option<&MonitorHandle>)
+    -> Monitor
+{
+    let size = handle.size();
+    let position = handle.position();
+
+    // Report the refresh rate of the video mode matching the current
+    // resolution, if there is one
+    let refresh_rate_hz = handle
+        .video_modes()
+        .filter(|mode| mode.size() == size)
+        .map(|mode| mode.refresh_rate() as u32)
+        .max();
+
+    Monitor::new(
+        handle.name(),
+        UVec2::new(size.width, size.height),
+        IVec2::new(position.x, position.y),
+        handle.scale_factor(),
+        refresh_rate_hz,
+        primary == Some(handle)
+    )
+}
+
 fn compute_window_size(monitor: &MonitorHandle, size: &WindowSize) -> PhysicalSize<u32>
 {
     let monitor_size = monitor.size();
@@ -922,6 +1530,77 @@ impl From<GlutinVirtualKeyCode> for VirtualKeyCode
     }
 }
 
+impl From<GlutinTouchPhase> for TouchPhase
+{
+    fn from(phase: GlutinTouchPhase) -> Self
+    {
+        match phase {
+            GlutinTouchPhase::Started => TouchPhase::Started,
+            GlutinTouchPhase::Moved => TouchPhase::Moved,
+            GlutinTouchPhase::Ended => TouchPhase::Ended,
+            GlutinTouchPhase::Cancelled => TouchPhase::Cancelled
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<gilrs::GamepadId> for GamepadId
+{
+    fn from(id: gilrs::GamepadId) -> Self
+    {
+        GamepadId(usize::from(id))
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<gilrs::Button> for GamepadButton
+{
+    fn from(button: gilrs::Button) -> Self
+    {
+        match button {
+            gilrs::Button::South => GamepadButton::South,
+            gilrs::Button::East => GamepadButton::East,
+            gilrs::Button::North => GamepadButton::North,
+            gilrs::Button::West => GamepadButton::West,
+            gilrs::Button::C => GamepadButton::C,
+            gilrs::Button::Z => GamepadButton::Z,
+            gilrs::Button::LeftTrigger => GamepadButton::LeftTrigger,
+            gilrs::Button::LeftTrigger2 => GamepadButton::LeftTrigger2,
+            gilrs::Button::RightTrigger => GamepadButton::RightTrigger,
+            gilrs::Button::RightTrigger2 => GamepadButton::RightTrigger2,
+            gilrs::Button::Select => GamepadButton::Select,
+            gilrs::Button::Start => GamepadButton::Start,
+            gilrs::Button::Mode => GamepadButton::Mode,
+            gilrs::Button::LeftThumb => GamepadButton::LeftThumb,
+            gilrs::Button::RightThumb => GamepadButton::RightThumb,
+            gilrs::Button::DPadUp => GamepadButton::DPadUp,
+            gilrs::Button::DPadDown => GamepadButton::DPadDown,
+            gilrs::Button::DPadLeft => GamepadButton::DPadLeft,
+            gilrs::Button::DPadRight => GamepadButton::DPadRight,
+            gilrs::Button::Unknown => GamepadButton::Unknown
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<gilrs::Axis> for GamepadAxis
+{
+    fn from(axis: gilrs::Axis) -> Self
+    {
+        match axis {
+            gilrs::Axis::LeftStickX => GamepadAxis::LeftStickX,
+            gilrs::Axis::LeftStickY => GamepadAxis::LeftStickY,
+            gilrs::Axis::LeftZ => GamepadAxis::LeftZ,
+            gilrs::Axis::RightStickX => GamepadAxis::RightStickX,
+            gilrs::Axis::RightStickY => GamepadAxis::RightStickY,
+            gilrs::Axis::RightZ => GamepadAxis::RightZ,
+            gilrs::Axis::DPadX => GamepadAxis::DPadX,
+            gilrs::Axis::DPadY => GamepadAxis::DPadY,
+            gilrs::Axis::Unknown => GamepadAxis::Unknown
+        }
+    }
+}
+
 impl From<glutin::event::ModifiersState> for ModifiersState
 {
     fn from(state: glutin::event::ModifiersState) -> Self
@@ -947,6 +1626,7 @@ pub(crate) enum UserEventGlutin<UserEventType: 'static>
 {
     MouseGrabStatusChanged(bool),
     FullscreenStatusChanged(bool),
+    MinimizedStatusChanged(bool),
     UserEvent(UserEventType)
 }
 
@@ -981,3 +1661,145 @@ impl<UserEventType> UserEventSenderGlutin<UserEventType>
             })
     }
 }
+
+#[cfg(test)]
+mod test
+{
+    use std::time::{Duration, Instant};
+
+    use crate::window_internal_glutin::{FrameLimiter, HeldKeys};
+
+    #[test]
+    pub fn test_frame_limiter()
+    {
+        let start = Instant::now();
+        let mut frame_limiter = FrameLimiter::new(60);
+        let mut frames = 0;
+
+        // Simulate a loop which wakes up every millisecond for one second
+        for millis in 0..1000 {
+            let now = start + Duration::from_millis(millis);
+
+            if frame_limiter.wait_deadline(now).is_none() {
+                frame_limiter.frame_drawn(now);
+                frames += 1;
+            }
+        }
+
+        assert!((59..=60).contains(&frames), "Drew {} frames", frames);
+
+        // After falling behind, frames aren't drawn in a burst to catch up
+        let now = start + Duration::from_secs(5);
+        assert_eq!(None, frame_limiter.wait_deadline(now));
+        frame_limiter.frame_drawn(now);
+        assert!(frame_limiter
+            .wait_deadline(now + Duration::from_millis(1))
+            .is_some());
+    }
+
+    #[test]
+    pub fn test_held_keys_repeat()
+    {
+        let mut held_keys = HeldKeys::new();
+
+        assert!(!held_keys.press(30));
+        assert!(held_keys.press(30));
+        assert!(held_keys.press(30));
+
+        // Other keys are tracked independently
+        assert!(!held_keys.press(31));
+
+        held_keys.release(30);
+        assert!(!held_keys.press(30));
+        assert!(held_keys.press(31));
+
+        // Releases missed while unfocused don't cause a repeat
+        held_keys.clear();
+        assert!(!held_keys.press(30));
+        assert!(!held_keys.press(31));
+    }
+
+    // Requires an X server, and an event loop which can be created outside
+    // the main thread
+    #[cfg(target_os = "linux")]
+    #[test]
+    pub fn test_key_repeat_forwarded_to_handler()
+    {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        use glutin::event_loop::EventLoop;
+        use glutin::platform::unix::EventLoopExtUnix;
+        use glutin::window::WindowBuilder;
+
+        use crate::dimen::UVec2;
+        use crate::window::{
+            DrawingWindowHandler,
+            KeyScancode,
+            PresentMode,
+            VirtualKeyCode,
+            WindowHandler,
+            WindowHelper
+        };
+        use crate::window_internal_glutin::{UserEventGlutin, WindowHelperGlutin};
+        use crate::GLRenderer;
+
+        struct KeyRepeatRecorder
+        {
+            repeats: Rc<RefCell<Vec<bool>>>
+        }
+
+        impl WindowHandler for KeyRepeatRecorder
+        {
+            fn on_key_down(
+                &mut self,
+                helper: &mut WindowHelper,
+                _virtual_key_code: Option<VirtualKeyCode>,
+                _scancode: KeyScancode
+            )
+            {
+                self.repeats.borrow_mut().push(helper.is_key_repeat());
+            }
+        }
+
+        let event_loop: EventLoop<UserEventGlutin<()>> = EventLoop::new_any_thread();
+
+        let context = glutin::ContextBuilder::new()
+            .with_gl(glutin::GlRequest::Specific(glutin::Api::OpenGl, (2, 0)))
+            .build_windowed(WindowBuilder::new().with_visible(false), &event_loop)
+            .unwrap();
+
+        let context = Rc::new(unsafe { context.make_current() }.unwrap());
+
+        let renderer = unsafe {
+            GLRenderer::new_for_gl_context((64, 64), |name| {
+                context.get_proc_address(name) as *const _
+            })
+            .unwrap()
+        };
+
+        let mut helper = WindowHelper::new(WindowHelperGlutin::new(
+            &context,
+            event_loop.create_proxy(),
+            UVec2::new(64, 64),
+            PresentMode::Immediate
+        ));
+
+        let repeats = Rc::new(RefCell::new(Vec::new()));
+
+        let mut handler = DrawingWindowHandler::new(
+            KeyRepeatRecorder {
+                repeats: repeats.clone()
+            },
+            renderer
+        );
+
+        handler.on_key_down(&mut helper, Some(VirtualKeyCode::A), 30, true);
+        assert!(!helper.is_key_repeat());
+
+        handler.on_key_down(&mut helper, Some(VirtualKeyCode::A), 30, false);
+        assert!(!helper.is_key_repeat());
+
+        assert_eq!(vec![true, false], *repeats.borrow());
+    }
+}
@@ -24,23 +24,35 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{KeyboardEvent, MouseEvent, WheelEvent};
 
-use crate::dimen::{IVec2, UVec2, Vec2};
+use crate::dimen::{IVec2, UVec2, Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
 use crate::numeric::RoundFloat;
-use crate::web::{WebCanvasElement, WebCursorType, WebDocument, WebPending, WebWindow};
+use crate::web::{
+    read_file_bytes,
+    WebCanvasElement,
+    WebCursorType,
+    WebDocument,
+    WebPending,
+    WebWindow
+};
 use crate::window::{
     DrawingWindowHandler,
     EventLoopSendError,
     KeyScancode,
     ModifiersState,
+    Monitor,
     MouseButton,
     MouseScrollDistance,
+    PresentMode,
+    TouchPhase,
     UserEventSender,
     VirtualKeyCode,
     WindowFullscreenMode,
     WindowHandler,
     WindowHelper,
-    WindowStartupInfo
+    WindowStartupInfo,
+    WindowState,
+    SCROLL_SCALED_PIXELS_PER_LINE
 };
 use crate::GLRenderer;
 
@@ -385,6 +397,7 @@ where
     redraw_request_action: Option<Box<RefCell<dyn FnMut() -> WebPending>>>,
     post_user_event_action: Option<Rc<RefCell<UserEventSenderActionType<UserEventType>>>>,
     terminate_loop_action: Option<Box<dyn FnOnce()>>,
+    cursor_position: Option<Vec2>,
     canvas: WebCanvasElement,
     document: WebDocument,
     window: WebWindow
@@ -399,6 +412,7 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
             redraw_request_action: None,
             post_user_event_action: None,
             terminate_loop_action: None,
+            cursor_position: None,
             canvas,
             document,
             window
@@ -518,6 +532,76 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         }
     }
 
+    pub fn set_fullscreen_borderless_on_monitor(&self, _monitor: &Monitor)
+    {
+        self.set_fullscreen_mode(WindowFullscreenMode::FullscreenBorderless);
+    }
+
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        vec![self.get_primary_monitor()]
+    }
+
+    pub fn get_primary_monitor(&self) -> Monitor
+    {
+        let scale_factor = self.window.device_pixel_ratio();
+
+        let size_pixels = match self.window.screen_size_css_pixels() {
+            Some(size) => (size.into_f32() * scale_factor as f32)
+                .round_to_i32()
+                .into_u32_saturating(),
+            None => {
+                log::warn!("Unable to get screen size, using canvas size instead");
+                self.get_size_pixels()
+            }
+        };
+
+        Monitor::new(None, size_pixels, IVec2::ZERO, scale_factor, None, true)
+    }
+
+    pub fn set_cursor_position_pixels(
+        &self,
+        _position: Vec2
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        // Browsers don't allow the cursor to be moved
+        Ok(())
+    }
+
+    pub fn set_ime_position(&self, _position: Vec2)
+    {
+        // Browsers position the IME based on the focused element
+    }
+
+    pub fn get_cursor_position_pixels(&self) -> Option<Vec2>
+    {
+        self.cursor_position
+    }
+
+    pub fn minimize(&self)
+    {
+        // Do nothing
+    }
+
+    pub fn maximize(&self)
+    {
+        // Do nothing
+    }
+
+    pub fn restore(&self)
+    {
+        // Do nothing
+    }
+
+    pub fn get_window_state(&self) -> WindowState
+    {
+        if self.document.is_hidden() {
+            WindowState::Minimized
+        } else {
+            WindowState::Normal
+        }
+    }
+
     pub fn set_size_pixels<S: Into<UVec2>>(&self, _size: S)
     {
         // Do nothing
@@ -549,6 +633,35 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         self.window.device_pixel_ratio()
     }
 
+    pub fn normalize_scroll_distance(&self, distance: MouseScrollDistance)
+        -> Vector2<f64>
+    {
+        // Pixel distances are reported in CSS pixels, and browsers which use
+        // lines report three lines per notch
+        distance.to_lines(SCROLL_SCALED_PIXELS_PER_LINE, 3.0)
+    }
+
+    pub fn get_present_mode(&self) -> PresentMode
+    {
+        // Frames are presented when the browser decides
+        PresentMode::Vsync
+    }
+
+    pub fn get_clipboard_string(&self) -> Option<String>
+    {
+        // Browsers only allow the clipboard to be read asynchronously, and
+        // usually only after asking the user for permission
+        None
+    }
+
+    pub fn set_clipboard_string(
+        &self,
+        text: &str
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.window.set_clipboard_text(text)
+    }
+
     pub fn create_user_event_sender(&self) -> UserEventSender<UserEventType>
     {
         UserEventSender::new(UserEventSenderWeb::new(
@@ -623,7 +736,8 @@ impl WebCanvasImpl
                     KeyEventType::Down => handler.on_key_down(
                         helper.deref_mut(),
                         Some(virtual_key_code),
-                        scancode
+                        scancode,
+                        event.repeat()
                     ),
                     KeyEventType::Up => handler.on_key_up(
                         helper.deref_mut(),
@@ -790,6 +904,33 @@ impl WebCanvasImpl
             }
         }
 
+        {
+            let handler = handler.clone();
+            let helper = helper.clone();
+
+            event_listeners_to_clean_up.push(
+                window
+                    .clone()
+                    .dyn_into_event_target()?
+                    .register_event_listener_before_unload(
+                        "beforeunload",
+                        move |event| {
+                            let allow_close = RefCell::borrow_mut(Rc::borrow(&handler))
+                                .on_close_requested(
+                                    RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut()
+                                );
+
+                            if !allow_close {
+                                event.prevent_default();
+
+                                // Older browsers require the return value to be set
+                                event.set_return_value("");
+                            }
+                        }
+                    )?
+            );
+        }
+
         {
             let handler = handler.clone();
             let helper = helper.clone();
@@ -826,23 +967,21 @@ impl WebCanvasImpl
         {
             let handler = handler.clone();
             let helper = helper.clone();
-            let canvas = canvas.clone();
-            let is_pointer_locked = is_pointer_locked.clone();
+            let document_inner = document.clone();
 
             event_listeners_to_clean_up.push(
                 document
                     .clone()
                     .dyn_into_event_target()?
-                    .register_event_listener_void("pointerlockchange", move || {
-                        let mouse_grabbed = canvas.is_pointer_lock_active();
-
-                        is_pointer_locked.set(mouse_grabbed);
+                    .register_event_listener_void("visibilitychange", move || {
+                        let mut handler = RefCell::borrow_mut(Rc::borrow(&handler));
+                        let mut helper = RefCell::borrow_mut(Rc::borrow(&helper));
 
-                        RefCell::borrow_mut(Rc::borrow(&handler))
-                            .on_mouse_grab_status_changed(
-                                RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
-                                mouse_grabbed
-                            );
+                        if document_inner.is_hidden() {
+                            handler.on_window_minimized(helper.deref_mut());
+                        } else {
+                            handler.on_window_restored(helper.deref_mut());
+                        }
                     })?
             );
         }
@@ -850,17 +989,22 @@ impl WebCanvasImpl
         {
             let handler = handler.clone();
             let helper = helper.clone();
+            let canvas = canvas.clone();
+            let is_pointer_locked = is_pointer_locked.clone();
 
             event_listeners_to_clean_up.push(
                 document
+                    .clone()
                     .dyn_into_event_target()?
-                    .register_event_listener_void("fullscreenchange", move || {
-                        let fullscreen = canvas.is_fullscreen_active();
+                    .register_event_listener_void("pointerlockchange", move || {
+                        let mouse_grabbed = canvas.is_pointer_lock_active();
+
+                        is_pointer_locked.set(mouse_grabbed);
 
                         RefCell::borrow_mut(Rc::borrow(&handler))
-                            .on_fullscreen_status_changed(
+                            .on_mouse_grab_status_changed(
                                 RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
-                                fullscreen
+                                mouse_grabbed
                             );
                     })?
             );
@@ -877,20 +1021,82 @@ impl WebCanvasImpl
                     move |event| {
                         let current_dpr = Cell::get(Rc::borrow(&current_dpr)) as f32;
 
+                        let delta =
+                            IVec2::new(event.movement_x(), event.movement_y()).into_f32();
+
                         let position = if is_pointer_locked.get() {
-                            IVec2::new(event.movement_x(), event.movement_y())
-                                .into_f32()
-                                .mul(current_dpr)
+                            delta.mul(current_dpr)
                         } else {
                             IVec2::new(event.offset_x(), event.offset_y())
                                 .into_f32()
                                 .mul(current_dpr)
                         };
 
-                        RefCell::borrow_mut(Rc::borrow(&handler)).on_mouse_move(
-                            RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
-                            position
-                        );
+                        let mut helper = RefCell::borrow_mut(Rc::borrow(&helper));
+
+                        if !is_pointer_locked.get() {
+                            helper.inner().cursor_position = Some(position);
+                        }
+
+                        let mut handler = RefCell::borrow_mut(Rc::borrow(&handler));
+
+                        handler.on_mouse_move(helper.deref_mut(), position);
+                        handler.on_mouse_raw_motion(helper.deref_mut(), delta);
+                    }
+                )?
+            );
+        }
+
+        // The default action must be prevented for the canvas to accept drops
+        event_listeners_to_clean_up.push(
+            canvas_event_target.register_event_listener_drag("dragover", |event| {
+                event.prevent_default()
+            })?
+        );
+
+        {
+            let handler = handler.clone();
+            let helper = helper.clone();
+            let current_dpr = current_dpr.clone();
+
+            event_listeners_to_clean_up.push(
+                canvas_event_target.register_event_listener_drag(
+                    "drop",
+                    move |event| {
+                        event.prevent_default();
+
+                        let current_dpr = Cell::get(Rc::borrow(&current_dpr)) as f32;
+
+                        let position = IVec2::new(event.offset_x(), event.offset_y())
+                            .into_f32()
+                            .mul(current_dpr);
+
+                        let files =
+                            match event.data_transfer().and_then(|data| data.files()) {
+                                None => return,
+                                Some(files) => files
+                            };
+
+                        for file in (0..files.length()).filter_map(|i| files.get(i)) {
+                            let handler = handler.clone();
+                            let helper = helper.clone();
+                            let name = file.name();
+
+                            let result = read_file_bytes(&file, move |bytes| {
+                                RefCell::borrow_mut(Rc::borrow(&handler))
+                                    .on_file_dropped_bytes(
+                                        RefCell::borrow_mut(Rc::borrow(&helper))
+                                            .deref_mut(),
+                                        name,
+                                        bytes,
+                                        Some(position)
+                                    );
+                            });
+
+                            if let Err(err) = result {
+                                log::error!("Failed to read dropped file: {:?}", err);
+                            }
+                        }
                     }
                 )?
             );
@@ -988,6 +1194,69 @@ impl WebCanvasImpl
             );
         }
 
+        for (listener_type, phase) in [
+            ("touchstart", TouchPhase::Started),
+            ("touchmove", TouchPhase::Moved),
+            ("touchend", TouchPhase::Ended),
+            ("touchcancel", TouchPhase::Cancelled)
+        ] {
+            let handler = handler.clone();
+            let helper = helper.clone();
+            let canvas = canvas.clone();
+            let current_dpr = current_dpr.clone();
+
+            event_listeners_to_clean_up.push(
+                canvas_event_target.register_event_listener_touch(
+                    listener_type,
+                    move |event| {
+                        let current_dpr = Cell::get(Rc::borrow(&current_dpr));
+                        let canvas_position = canvas.html_element().element().position();
+                        let touches = event.changed_touches();
+
+                        for i in 0..touches.length() {
+                            let touch = match touches.get(i) {
+                                None => continue,
+                                Some(touch) => touch
+                            };
+
+                            let position = (Vector2::new(
+                                touch.client_x() as f64,
+                                touch.client_y() as f64
+                            ) - canvas_position)
+                                .mul(current_dpr)
+                                .into_f32();
+
+                            RefCell::borrow_mut(Rc::borrow(&handler)).on_touch(
+                                RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
+                                touch.identifier() as u64,
+                                phase,
+                                position
+                            );
+                        }
+                    }
+                )?
+            );
+        }
+
+        {
+            let handler = handler.clone();
+            let helper = helper.clone();
+
+            event_listeners_to_clean_up.push(
+                document
+                    .dyn_into_event_target()?
+                    .register_event_listener_void("fullscreenchange", move || {
+                        let fullscreen = canvas.is_fullscreen_active();
+
+                        RefCell::borrow_mut(Rc::borrow(&handler))
+                            .on_fullscreen_status_changed(
+                                RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
+                                fullscreen
+                            );
+                    })?
+            );
+        }
+
         let modifier_state = Rc::new(RefCell::new(ModifiersState::default()));
 
         {
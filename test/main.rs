@@ -27,11 +27,19 @@ use glutin::event_loop::EventLoop;
 use image::{ColorType, GenericImageView, ImageFormat};
 use num_traits::ToPrimitive;
 use speedy2d::color::Color;
-use speedy2d::dimen::{Vec2, Vector2};
-use speedy2d::font::{Font, TextAlignment, TextLayout, TextOptions};
-use speedy2d::image::{ImageDataType, ImageSmoothingMode};
-use speedy2d::shape::{Polygon, Rect, Rectangle};
-use speedy2d::GLRenderer;
+use speedy2d::dimen::{UVec2, Vec2, Vector2};
+use speedy2d::font::{Font, FormattedTextBlock, TextAlignment, TextLayout, TextOptions};
+use speedy2d::image::{
+    CaptureOptions,
+    ColorKey,
+    DrawImageOptions,
+    ImageDataType,
+    ImageSmoothingMode,
+    SpriteBatch,
+    TextureWrapMode
+};
+use speedy2d::shape::{IRect, Polygon, Rect, Rectangle};
+use speedy2d::{BlendMode, GLRenderer, Graphics2D};
 
 const NOTO_SANS_REGULAR_BYTES: &[u8] =
     include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
@@ -79,6 +87,7 @@ where
     let context_builder = glutin::ContextBuilder::new()
         .with_gl_debug_flag(true)
         .with_multisampling(0)
+        .with_depth_buffer(24)
         .with_gl(glutin::GlRequest::Specific(glutin::Api::OpenGl, (2, 0)));
 
     #[cfg(not(target_os = "linux"))]
@@ -1300,6 +1309,1711 @@ fn main()
         })
     });
 
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "blend_mode_additive".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::BLACK);
+                graphics.set_blend_mode(BlendMode::Additive);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((10.0, 10.0), (30.0, 30.0)),
+                    Color::RED
+                );
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((20.0, 20.0), (40.0, 40.0)),
+                    Color::GREEN
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "blend_mode_multiply".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::CYAN);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((5.0, 5.0), (25.0, 25.0)),
+                    Color::YELLOW
+                );
+
+                graphics.set_blend_mode(BlendMode::Multiply);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((20.0, 20.0), (45.0, 45.0)),
+                    Color::YELLOW
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 10,
+        height: 10,
+        name: "blend_mode_none_transparent".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let target = graphics
+                    .create_render_target(ImageSmoothingMode::NearestNeighbor, (4, 4))
+                    .unwrap();
+
+                // With blending disabled, transparent pixels replace the contents
+                graphics.draw_to_target(&target, |graphics| {
+                    graphics.clear_screen(Color::RED);
+                    graphics.set_blend_mode(BlendMode::None);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (4.0, 2.0)),
+                        Color::TRANSPARENT
+                    );
+                });
+
+                let pixels = graphics
+                    .read_image_pixels(target.image(), ImageDataType::RGBA)
+                    .unwrap();
+
+                let alpha = |x: usize, y: usize| pixels.data()[(y * 4 + x) * 4 + 3];
+
+                assert_eq!(0, alpha(0, 0));
+                assert_eq!(0, alpha(3, 1));
+                assert_eq!(255, alpha(0, 2));
+                assert_eq!(255, alpha(3, 3));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "transform_stack".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.translate((10.0, 10.0));
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (10.0, 10.0)),
+                    Color::RED
+                );
+
+                graphics.scale((2.0, 2.0));
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((5.0, 5.0), (10.0, 10.0)),
+                    Color::BLUE
+                );
+
+                graphics.pop_transform();
+                graphics.pop_transform();
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((40.0, 40.0), (45.0, 45.0)),
+                    Color::GREEN
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "render_target".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let target = graphics
+                    .create_render_target(ImageSmoothingMode::NearestNeighbor, (25, 25))
+                    .unwrap();
+
+                graphics.draw_to_target(&target, |graphics| {
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (25.0, 10.0)),
+                        Color::RED
+                    );
+                });
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((10.0, 10.0), (35.0, 35.0)),
+                    target.image()
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "custom_shader".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let shader = graphics
+                    .create_shader(
+                        None,
+                        r#"
+                            #version 110
+
+                            uniform vec4 in_Tint;
+
+                            varying vec4 pass_Color;
+
+                            void main(void) {
+                                gl_FragColor = pass_Color * in_Tint;
+                            }
+                        "#
+                    )
+                    .unwrap();
+
+                graphics
+                    .set_shader_uniform_color(&shader, "in_Tint", Color::RED)
+                    .unwrap();
+
+                graphics.set_shader(Some(&shader));
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((10.0, 10.0), (30.0, 30.0)),
+                    Color::YELLOW
+                );
+
+                graphics.set_shader(None);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((25.0, 25.0), (40.0, 40.0)),
+                    Color::BLUE
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "line_aa_pixel_aligned".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_line_aa((10.0, 20.5), (40.0, 20.5), 1.0, Color::BLACK);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "polyline_miter".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_polyline(
+                    &[
+                        Vec2::new(10.0, 20.0),
+                        Vec2::new(30.0, 20.0),
+                        Vec2::new(30.0, 40.0)
+                    ],
+                    4.0,
+                    Color::RED
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "triangle_image_tinted".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (2, 2),
+                        &[255, 255, 0, 255, 255, 0, 255, 255, 0, 255, 255, 0]
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                // Yellow texels tinted with cyan should produce green
+                let tint = Color::from_rgb(0.0, 1.0, 1.0);
+
+                graphics.draw_triangle_image_tinted_three_color(
+                    [
+                        Vec2::new(10.0, 10.0),
+                        Vec2::new(40.0, 10.0),
+                        Vec2::new(40.0, 40.0)
+                    ],
+                    [tint; 3],
+                    [
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(1.0, 0.0),
+                        Vec2::new(1.0, 1.0)
+                    ],
+                    &image
+                );
+
+                graphics.draw_triangle_image_tinted_three_color(
+                    [
+                        Vec2::new(40.0, 40.0),
+                        Vec2::new(10.0, 40.0),
+                        Vec2::new(10.0, 10.0)
+                    ],
+                    [tint; 3],
+                    [
+                        Vec2::new(1.0, 1.0),
+                        Vec2::new(0.0, 1.0),
+                        Vec2::new(0.0, 0.0)
+                    ],
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 50,
+        height: 50,
+        name: "image_premultiplied".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                // Red at 50% opacity, with the color already multiplied by the
+                // alpha value
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGBAPremultiplied,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (1, 1),
+                        &[128, 0, 0, 128]
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((10.0, 10.0), (40.0, 40.0)),
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 1400,
+        height: 500,
+        name: "text_cache_eviction".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            renderer.set_glyph_cache_limit(8);
+
+            // Fill the cache with other glyphs over several frames, so that
+            // the glyphs below must be rendered again after being evicted
+            for (i, filler) in ["abcdefgh", "ijklmnop", "0123456789"].iter().enumerate() {
+                let filler =
+                    typeface.layout_text(filler, 64.0 + i as f32, TextOptions::new());
+
+                renderer.draw_frame(|graphics| {
+                    graphics.draw_text(Vec2::new(0.0, 0.0), Color::BLACK, &filler);
+                });
+            }
+
+            // This should match the output of basic_text_white_background
+            let text = typeface.layout_text(
+                "The quick brown föx jumped over the lazy dog!",
+                64.0,
+                TextOptions::new()
+            );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples(
+                        (0.0, 0.0),
+                        (text.width().round(), text.height().round())
+                    ),
+                    Color::from_rgb(0.9, 0.9, 1.0)
+                );
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples(
+                        (0.0, 0.0),
+                        (
+                            text.width().round(),
+                            text.iter_lines().next().unwrap().ascent().round()
+                        )
+                    ),
+                    Color::from_rgb(0.8, 0.8, 1.0)
+                );
+
+                graphics.draw_text(Vec2::new(0.0, 0.0), Color::BLACK, &text);
+
+                graphics.draw_text(Vec2::new(0.0, 100.0), Color::RED, &text);
+
+                graphics.draw_text(Vec2::new(0.0, 200.0), Color::GREEN, &text);
+
+                graphics.draw_text(Vec2::new(0.0, 300.0), Color::BLUE, &text);
+
+                graphics.draw_text(Vec2::new(0.0, 400.0), Color::WHITE, &text);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 80,
+        height: 40,
+        name: "image_update_region".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (4, 4),
+                        &[255, 0, 0].repeat(16)
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                // Drawn before the update, so this copy should remain red
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((0.0, 0.0), (40.0, 40.0)),
+                    &image
+                );
+
+                graphics
+                    .update_image_region(&image, (1, 1), (2, 2), &[0, 0, 255].repeat(4))
+                    .unwrap();
+
+                assert!(graphics
+                    .update_image_region(&image, (3, 3), (2, 2), &[0, 0, 255].repeat(4))
+                    .is_err());
+
+                assert!(graphics
+                    .update_image_region(&image, (0, 0), (2, 2), &[0, 0, 255].repeat(3))
+                    .is_err());
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((40.0, 0.0), (80.0, 40.0)),
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 16,
+        height: 16,
+        name: "image_mipmap".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                // A single-pixel checkerboard, which aliases badly when drawn at
+                // a smaller size without mipmaps
+                let pixels: Vec<u8> = (0..128 * 128)
+                    .flat_map(|i| {
+                        let value = if (i % 128 + i / 128) % 2 == 0 { 0 } else { 255 };
+                        [value, value, value]
+                    })
+                    .collect();
+
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::LinearMipmap,
+                        (128, 128),
+                        &pixels
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((0.0, 0.0), (16.0, 16.0)),
+                    &image
+                );
+
+                // The exact result of averaging depends on the driver, so check
+                // the values here, rather than in the expected image
+                let capture = graphics.capture(ImageDataType::RGB);
+
+                for value in capture.data() {
+                    assert!(
+                        (112..=144).contains(value),
+                        "Expected mipmapped grey, got {}",
+                        value
+                    );
+                }
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    #[cfg(feature = "image-loading")]
+    tests.push(GLTest {
+        width: 20,
+        height: 10,
+        name: "image_sequence_gif".to_string(),
+        action: Box::new(|renderer| {
+            let frames = renderer
+                .create_image_sequence_from_file_bytes(
+                    None,
+                    ImageSmoothingMode::NearestNeighbor,
+                    std::io::Cursor::new(
+                        include_bytes!("assets/test_images/animated_red_blue.gif")
+                            .as_ref()
+                    )
+                )
+                .unwrap();
+
+            assert_eq!(2, frames.len());
+            assert_eq!(std::time::Duration::from_millis(100), frames[0].1);
+            assert_eq!(std::time::Duration::from_millis(200), frames[1].1);
+
+            for (image, _) in frames.iter() {
+                assert_eq!(Vector2::new(2, 2), *image.size());
+            }
+
+            // Static images are returned as a single frame
+            let static_frames = renderer
+                .create_image_sequence_from_file_bytes(
+                    None,
+                    ImageSmoothingMode::NearestNeighbor,
+                    std::io::Cursor::new(
+                        include_bytes!("assets/test_images/smiley_colormap.png").as_ref()
+                    )
+                )
+                .unwrap();
+
+            assert_eq!(1, static_frames.len());
+            assert_eq!(std::time::Duration::from_secs(0), static_frames[0].1);
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((0.0, 0.0), (10.0, 10.0)),
+                    &frames[0].0
+                );
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((10.0, 0.0), (20.0, 10.0)),
+                    &frames[1].0
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 30,
+        height: 20,
+        name: "image_read_pixels".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                // An odd width, so that the rows are not aligned to four bytes
+                let pixels: Vec<u8> = [
+                    [255, 0, 0],
+                    [0, 255, 0],
+                    [0, 0, 255],
+                    [255, 255, 0],
+                    [0, 255, 255],
+                    [255, 0, 255]
+                ]
+                .concat();
+
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (3, 2),
+                        &pixels
+                    )
+                    .unwrap();
+
+                let read_back = graphics
+                    .read_image_pixels(&image, ImageDataType::RGB)
+                    .unwrap();
+
+                assert_eq!(Vector2::new(3, 2), read_back.size());
+                assert_eq!(&pixels, read_back.data());
+
+                let read_back_rgba = graphics
+                    .read_image_pixels(&image, ImageDataType::RGBA)
+                    .unwrap();
+
+                assert_eq!(3 * 2 * 4, read_back_rgba.data().len());
+                assert_eq!(&[0, 255, 0, 255], &read_back_rgba.data()[4..8]);
+
+                // Render targets are read top row first, like other images
+                let target = graphics
+                    .create_render_target(ImageSmoothingMode::NearestNeighbor, (2, 2))
+                    .unwrap();
+
+                graphics.draw_to_target(&target, |graphics| {
+                    graphics.clear_screen(Color::BLUE);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (2.0, 1.0)),
+                        Color::RED
+                    );
+                });
+
+                let target_pixels = graphics
+                    .read_image_pixels(target.image(), ImageDataType::RGBA)
+                    .unwrap();
+
+                assert_eq!(
+                    &[255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 255, 0, 0, 255, 255],
+                    target_pixels.data().as_slice()
+                );
+
+                let copy = graphics
+                    .create_image_from_raw_pixels(
+                        read_back.format(),
+                        ImageSmoothingMode::NearestNeighbor,
+                        read_back.size(),
+                        read_back.data()
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((0.0, 0.0), (30.0, 20.0)),
+                    &copy
+                );
+            });
+        })
+    });
+
+    #[cfg(feature = "image-loading")]
+    tests.push(GLTest {
+        width: 10,
+        height: 10,
+        name: "image_size".to_string(),
+        action: Box::new(|renderer| {
+            // The size must be available outside of draw_frame()
+            let image = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGBA,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(7, 3),
+                    &[0; 7 * 3 * 4]
+                )
+                .unwrap();
+
+            assert_eq!(Vector2::new(7, 3), *image.size());
+
+            let file_image = renderer
+                .create_image_from_file_bytes(
+                    None,
+                    ImageSmoothingMode::NearestNeighbor,
+                    std::io::Cursor::new(
+                        include_bytes!("assets/test_images/smiley_colormap.png").as_ref()
+                    )
+                )
+                .unwrap();
+
+            let (file_width, file_height) =
+                image::open("test/assets/test_images/smiley_colormap.png")
+                    .unwrap()
+                    .dimensions();
+
+            assert_eq!(Vector2::new(file_width, file_height), *file_image.size());
+
+            let target = renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics
+                    .create_render_target(ImageSmoothingMode::NearestNeighbor, (13, 5))
+                    .unwrap()
+            });
+
+            assert_eq!(Vector2::new(13, 5), *target.size());
+            assert_eq!(Vector2::new(13, 5), *target.image().size());
+            assert_eq!(Vector2::new(7, 3), *image.size());
+        })
+    });
+
+    tests.push(GLTest {
+        width: 80,
+        height: 40,
+        name: "image_wrap_mode".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let pixels: Vec<u8> =
+                    [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]].concat();
+
+                let create_image = |graphics: &mut Graphics2D| {
+                    graphics
+                        .create_image_from_raw_pixels(
+                            ImageDataType::RGB,
+                            ImageSmoothingMode::NearestNeighbor,
+                            (2, 2),
+                            &pixels
+                        )
+                        .unwrap()
+                };
+
+                let repeat = create_image(graphics);
+                let mirror = create_image(graphics);
+
+                graphics.set_image_wrap_mode(&repeat, TextureWrapMode::Repeat);
+                graphics.set_image_wrap_mode(&mirror, TextureWrapMode::MirrorRepeat);
+
+                graphics.clear_screen(Color::BLACK);
+
+                graphics.draw_rectangle_image_subset_tinted(
+                    Rectangle::from_tuples((0.0, 0.0), (40.0, 40.0)),
+                    Color::WHITE,
+                    Rectangle::from_tuples((0.0, 0.0), (4.0, 4.0)),
+                    &repeat
+                );
+
+                graphics.draw_rectangle_image_subset_tinted(
+                    Rectangle::from_tuples((40.0, 0.0), (80.0, 40.0)),
+                    Color::WHITE,
+                    Rectangle::from_tuples((0.0, 0.0), (4.0, 4.0)),
+                    &mirror
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "image_rotated".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (2, 1),
+                        &[255, 0, 0, 0, 0, 255]
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                // The left (red) half of the image should end up at the top
+                graphics.draw_rectangle_image_rotated(
+                    (10.0, 10.0),
+                    (20.0, 10.0),
+                    std::f32::consts::FRAC_PI_2,
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 60,
+        height: 30,
+        name: "image_nine_patch".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let colors: [[u8; 3]; 9] = [
+                    [255, 0, 0],
+                    [0, 255, 0],
+                    [0, 0, 255],
+                    [255, 255, 0],
+                    [0, 255, 255],
+                    [255, 0, 255],
+                    [128, 0, 0],
+                    [0, 128, 0],
+                    [0, 0, 128]
+                ];
+
+                // A 6x6 image with a 2 pixel border, where each of the nine
+                // sections is a different color
+                let pixels: Vec<u8> = (0..36)
+                    .flat_map(|i| {
+                        let (x, y) = (i % 6, i / 6);
+                        colors[(y / 2) * 3 + (x / 2)]
+                    })
+                    .collect();
+
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (6, 6),
+                        &pixels
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+
+                let insets = Rectangle::from_tuples((2.0, 2.0), (4.0, 4.0));
+
+                graphics.draw_nine_patch(
+                    Rectangle::from_tuples((0.0, 0.0), (40.0, 30.0)),
+                    Color::WHITE,
+                    &insets,
+                    &image
+                );
+
+                // Smaller than the border, so the corners are scaled down
+                graphics.draw_nine_patch(
+                    Rectangle::from_tuples((50.0, 0.0), (52.0, 2.0)),
+                    Color::WHITE,
+                    &insets,
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 100,
+        height: 100,
+        name: "arc".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                // The bottom half of the circle, clockwise from the right
+                graphics.draw_arc(
+                    (50.0, 50.0),
+                    40.0,
+                    0.0,
+                    std::f32::consts::PI,
+                    6.0,
+                    Color::BLACK
+                );
+
+                // Anti-aliasing may vary between drivers, so check the pixels
+                // here, rather than in the expected image
+                let capture = graphics.capture(ImageDataType::RGB);
+                let pixel = |x: usize, y: usize| {
+                    let start = (y * 100 + x) * 3;
+                    capture.data()[start..start + 3].to_vec()
+                };
+
+                assert_eq!(vec![0, 0, 0], pixel(50, 90));
+                assert_eq!(vec![0, 0, 0], pixel(19, 78));
+                assert_eq!(vec![255, 255, 255], pixel(50, 10));
+                assert_eq!(vec![255, 255, 255], pixel(50, 50));
+                assert_eq!(vec![255, 255, 255], pixel(50, 98));
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_circle_outline((50.0, 50.0), 20.0, 4.0, Color::BLACK);
+
+                let capture = graphics.capture(ImageDataType::RGB);
+                let pixel = |x: usize, y: usize| {
+                    let start = (y * 100 + x) * 3;
+                    capture.data()[start..start + 3].to_vec()
+                };
+
+                assert_eq!(vec![0, 0, 0], pixel(50, 29));
+                assert_eq!(vec![0, 0, 0], pixel(70, 50));
+                assert_eq!(vec![255, 255, 255], pixel(50, 50));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 32,
+        height: 32,
+        name: "image_atlas".to_string(),
+        action: Box::new(|renderer| {
+            let cell_color = |x: u32, y: u32| [x as u8 * 32, y as u8 * 32, 200, 255];
+
+            let cell_pixels = |x: u32, y: u32| [cell_color(x, y); 16].concat();
+
+            let cell_rect = |x: u32, y: u32| {
+                Rectangle::from_tuples(
+                    (x as f32 * 4.0, y as f32 * 4.0),
+                    (x as f32 * 4.0 + 4.0, y as f32 * 4.0 + 4.0)
+                )
+            };
+
+            let mut separate_images = Vec::new();
+
+            for y in 0..8 {
+                for x in 0..8 {
+                    separate_images.push(
+                        renderer
+                            .create_image_from_raw_pixels(
+                                ImageDataType::RGBA,
+                                ImageSmoothingMode::NearestNeighbor,
+                                Vector2::new(4, 4),
+                                &cell_pixels(x, y)
+                            )
+                            .unwrap()
+                    );
+                }
+            }
+
+            let mut atlas = renderer
+                .create_image_atlas(
+                    ImageDataType::RGBA,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(64, 64)
+                )
+                .unwrap();
+
+            let mut regions = Vec::new();
+
+            for y in 0..8 {
+                for x in 0..8 {
+                    regions.push(
+                        renderer
+                            .add_image_to_atlas(
+                                &mut atlas,
+                                Vector2::new(4, 4),
+                                &cell_pixels(x, y)
+                            )
+                            .unwrap()
+                    );
+                }
+            }
+
+            // An image as large as the atlas can't fit, due to the border
+            assert!(renderer
+                .add_image_to_atlas(
+                    &mut atlas,
+                    Vector2::new(64, 64),
+                    &vec![0; 64 * 64 * 4]
+                )
+                .is_err());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                for y in 0..8 {
+                    for x in 0..8 {
+                        graphics.draw_rectangle_image(
+                            cell_rect(x, y),
+                            &separate_images[(y * 8 + x) as usize]
+                        );
+                    }
+                }
+            });
+
+            assert_eq!(64, renderer.last_frame_draw_call_count());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                for y in 0..8 {
+                    for x in 0..8 {
+                        graphics.draw_rectangle_image_subset_tinted(
+                            cell_rect(x, y),
+                            Color::WHITE,
+                            &regions[(y * 8 + x) as usize],
+                            atlas.image()
+                        );
+                    }
+                }
+            });
+
+            assert_eq!(1, renderer.last_frame_draw_call_count());
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "begin_end_frame".to_string(),
+        action: Box::new(|renderer| {
+            let graphics = renderer.begin_frame();
+            graphics.clear_screen(Color::WHITE);
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((0.0, 0.0), (10.0, 20.0)),
+                Color::RED
+            );
+
+            // This transform should not affect the second pass
+            graphics.translate((5.0, 5.0));
+            renderer.end_frame();
+
+            let graphics = renderer.begin_frame();
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((10.0, 0.0), (20.0, 20.0)),
+                Color::BLUE
+            );
+            renderer.end_frame();
+        })
+    });
+
+    tests.push(GLTest {
+        width: 21,
+        height: 21,
+        name: "rectangle_four_color".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_four_color(
+                    Rectangle::from_tuples((0.0, 0.0), (21.0, 21.0)),
+                    [Color::RED, Color::GREEN, Color::BLUE, Color::BLACK]
+                );
+
+                // The exact result of interpolation depends on the driver, so
+                // check the values here, rather than in the expected image
+                let capture = graphics.capture(ImageDataType::RGBA);
+
+                let pixel = |x: usize, y: usize| {
+                    let index = (y * 21 + x) * 4;
+                    capture.data()[index..index + 4].to_vec()
+                };
+
+                let assert_near = |actual: Vec<u8>, expected: [u8; 4]| {
+                    for (actual, expected) in actual.iter().zip(expected.iter()) {
+                        assert!(
+                            (*actual as i32 - *expected as i32).abs() <= 16,
+                            "Expected {:?}, got {:?}",
+                            expected,
+                            actual
+                        );
+                    }
+                };
+
+                // The center lies on the diagonal between red and blue
+                assert_near(pixel(10, 10), [128, 0, 128, 255]);
+
+                assert_near(pixel(0, 0), [255, 0, 0, 255]);
+                assert_near(pixel(20, 0), [0, 255, 0, 255]);
+                assert_near(pixel(20, 20), [0, 0, 255, 255]);
+                assert_near(pixel(0, 20), [0, 0, 0, 255]);
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 40,
+        height: 20,
+        name: "outline_rectangle_polygon".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_outline(
+                    Rectangle::from_tuples((5.0, 5.0), (15.0, 15.0)),
+                    2.0,
+                    Color::RED
+                );
+
+                let poly = Polygon::new(&[
+                    (0.0, 0.0),
+                    (10.0, 0.0),
+                    (10.0, 4.0),
+                    (4.0, 4.0),
+                    (4.0, 10.0),
+                    (0.0, 10.0)
+                ]);
+
+                graphics.draw_polygon_outline(&poly, (25.0, 5.0), 2.0, Color::BLUE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 32,
+        height: 32,
+        name: "sprite_batch".to_string(),
+        action: Box::new(|renderer| {
+            let create_image = |renderer: &mut GLRenderer, color: [u8; 4]| {
+                renderer
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGBA,
+                        ImageSmoothingMode::NearestNeighbor,
+                        Vector2::new(1, 1),
+                        &color
+                    )
+                    .unwrap()
+            };
+
+            let red = create_image(renderer, [255, 0, 0, 255]);
+            let blue = create_image(renderer, [0, 0, 255, 255]);
+
+            let cell_rect = |x: u32, y: u32| {
+                Rectangle::from_tuples(
+                    (x as f32 * 4.0, y as f32 * 4.0),
+                    (x as f32 * 4.0 + 4.0, y as f32 * 4.0 + 4.0)
+                )
+            };
+
+            let is_red = |x: u32, y: u32| (x + y) % 2 == 0;
+
+            // Drawing a checkerboard one image at a time requires a draw call
+            // for every change of image
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                for y in 0..8 {
+                    for x in 0..8 {
+                        let image = if is_red(x, y) { &red } else { &blue };
+                        graphics.draw_rectangle_image(cell_rect(x, y), image);
+                    }
+                }
+            });
+
+            assert_eq!(64, renderer.last_frame_draw_call_count());
+
+            // Grouping the sprites by image needs only one draw call per image
+            let mut batch = SpriteBatch::new();
+            let whole_image = Rectangle::from_tuples((0.0, 0.0), (1.0, 1.0));
+
+            for &image in [&red, &blue].iter() {
+                for y in 0..8 {
+                    for x in 0..8 {
+                        if is_red(x, y) == (image == &red) {
+                            batch.add(
+                                image,
+                                cell_rect(x, y),
+                                &whole_image,
+                                Color::WHITE,
+                                std::f32::consts::FRAC_PI_2 * x as f32
+                            );
+                        }
+                    }
+                }
+            }
+
+            assert_eq!(64, batch.len());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_sprite_batch(&batch);
+            });
+
+            assert_eq!(2, renderer.last_frame_draw_call_count());
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "flush".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (10.0, 20.0)),
+                    Color::RED
+                );
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((10.0, 0.0), (20.0, 20.0)),
+                    Color::BLUE
+                );
+            });
+
+            assert_eq!(1, renderer.last_frame_draw_call_count());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (10.0, 20.0)),
+                    Color::RED
+                );
+                graphics.flush();
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((10.0, 0.0), (20.0, 20.0)),
+                    Color::BLUE
+                );
+
+                // Flushing an empty queue has no effect
+                graphics.flush();
+                graphics.flush();
+            });
+
+            assert_eq!(2, renderer.last_frame_draw_call_count());
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "depth".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                assert_eq!(None, graphics.get_depth());
+
+                graphics.set_depth(Some(0.75));
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((5.0, 5.0), (15.0, 15.0)),
+                    Color::BLUE
+                );
+
+                // Drawn later, but underneath the blue square
+                graphics.set_depth(Some(0.25));
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (20.0, 20.0)),
+                    Color::RED
+                );
+
+                graphics.set_depth(Some(5.0));
+                assert_eq!(Some(1.0), graphics.get_depth());
+
+                // Drawn on top of everything
+                graphics.set_depth(None);
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (5.0, 20.0)),
+                    Color::GREEN
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 120,
+        height: 50,
+        name: "text_underline_strikethrough".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let layout =
+                |options: TextOptions| typeface.layout_text("xxxx", 30.0, options);
+
+            let line = layout(TextOptions::new())
+                .iter_lines()
+                .next()
+                .unwrap()
+                .clone();
+            let baseline = 10.0 + line.baseline_position() + line.ascent();
+            let left = 12;
+            let right = 10 + line.width() as usize - 2;
+
+            // Returns the rows between `top` and `bottom` which are solid black
+            // across the width of the text
+            let mut find_solid_rows = |options: TextOptions, top: f32, bottom: f32| {
+                renderer.draw_frame(|graphics| {
+                    graphics.clear_screen(Color::WHITE);
+                    graphics.draw_text((10.0, 10.0), Color::BLACK, &layout(options));
+
+                    let capture = graphics.capture(ImageDataType::RGB);
+                    let width = capture.size().x as usize;
+
+                    (top.floor() as usize..bottom.ceil() as usize)
+                        .filter(|y| {
+                            (left..right)
+                                .all(|x| capture.data()[(y * width + x) * 3] < 64)
+                        })
+                        .collect::<Vec<usize>>()
+                })
+            };
+
+            let below_baseline = (baseline + 1.0, baseline - line.descent());
+            let above_baseline = (baseline - line.ascent(), baseline - 1.0);
+
+            for (top, bottom) in [below_baseline, above_baseline].iter() {
+                assert!(find_solid_rows(TextOptions::new(), *top, *bottom).is_empty());
+            }
+
+            assert!(!find_solid_rows(
+                TextOptions::new().with_underline(true),
+                below_baseline.0,
+                below_baseline.1
+            )
+            .is_empty());
+
+            assert!(!find_solid_rows(
+                TextOptions::new().with_strikethrough(true),
+                above_baseline.0,
+                above_baseline.1
+            )
+            .is_empty());
+
+            renderer.draw_frame(|graphics| graphics.clear_screen(Color::WHITE));
+        })
+    });
+
+    tests.push(GLTest {
+        width: 100,
+        height: 60,
+        name: "text_spans".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_spans(
+                &[("III", Color::RED), ("III", Color::BLUE)],
+                40.0,
+                TextOptions::new()
+            );
+
+            let split_x =
+                10 + typeface.measure_text("III", 40.0, TextOptions::new()).x as usize;
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_text((10.0, 10.0), Color::BLACK, &text);
+
+                let capture = graphics.capture(ImageDataType::RGB);
+                let width = capture.size().x as usize;
+
+                let any_pixel =
+                    |x_range: std::ops::Range<usize>, rgb: fn(&[u8]) -> bool| {
+                        capture.data().chunks(3).enumerate().any(|(i, pixel)| {
+                            x_range.contains(&(i % width)) && rgb(pixel)
+                        })
+                    };
+
+                let is_red: fn(&[u8]) -> bool = |p| p[0] > 200 && p[1] < 50 && p[2] < 50;
+                let is_blue: fn(&[u8]) -> bool = |p| p[0] < 50 && p[1] < 50 && p[2] > 200;
+
+                assert!(any_pixel(0..split_x, is_red));
+                assert!(any_pixel(split_x..width, is_blue));
+
+                // The text color is only used for text without spans
+                assert!(capture
+                    .data()
+                    .chunks(3)
+                    .all(|p| !(p[0] < 50 && p[1] < 50 && p[2] < 50)));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 60,
+        name: "text_pixel_snapping".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text("Hello world", 32.0, TextOptions::new());
+
+            let snapped_text = typeface.layout_text(
+                "Hello world",
+                32.0,
+                TextOptions::new().with_pixel_snapping(true)
+            );
+
+            let mut draw = |position: Vec2, offset: Vec2, text: &FormattedTextBlock| {
+                renderer.draw_frame(|graphics| {
+                    graphics.clear_screen(Color::WHITE);
+                    graphics.translate(offset);
+                    graphics.draw_text(position, Color::BLACK, text);
+                    graphics.capture(ImageDataType::RGBA).data().clone()
+                })
+            };
+
+            let expected = draw(Vec2::new(10.0, 10.0), Vec2::ZERO, &text);
+
+            assert_eq!(
+                expected,
+                draw(Vec2::new(10.3, 9.7), Vec2::ZERO, &snapped_text)
+            );
+
+            assert_eq!(
+                expected,
+                draw(Vec2::new(9.6, 9.9), Vec2::new(0.45, 0.2), &snapped_text)
+            );
+
+            renderer.draw_frame(|graphics| graphics.clear_screen(Color::WHITE));
+        })
+    });
+
+    tests.push(GLTest {
+        width: 60,
+        height: 160,
+        name: "text_rotated".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text("Hello", 32.0, TextOptions::new());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_text_rotated(
+                    (50.0, 10.0),
+                    std::f32::consts::FRAC_PI_2,
+                    Color::BLACK,
+                    &text
+                );
+
+                let capture = graphics.capture(ImageDataType::RGB);
+                let width = capture.size().x as usize;
+
+                let dark_pixels: Vec<(usize, usize)> = capture
+                    .data()
+                    .chunks(3)
+                    .enumerate()
+                    .filter(|(_, p)| p[0] < 100 && p[1] < 100 && p[2] < 100)
+                    .map(|(i, _)| (i % width, i / width))
+                    .collect();
+
+                assert!(!dark_pixels.is_empty());
+
+                let min_x = dark_pixels.iter().map(|(x, _)| *x).min().unwrap();
+                let max_x = dark_pixels.iter().map(|(x, _)| *x).max().unwrap();
+                let min_y = dark_pixels.iter().map(|(_, y)| *y).min().unwrap();
+                let max_y = dark_pixels.iter().map(|(_, y)| *y).max().unwrap();
+
+                // Rotated clockwise around the position, so the text runs
+                // downwards, to the left of the position
+                assert!(max_x <= 51);
+                assert!(min_y >= 9);
+                assert!(max_y - min_y > max_x - min_x);
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 120,
+        height: 60,
+        name: "text_bounded".to_string(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text("Hello", 32.0, TextOptions::new());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let bounds =
+                    graphics.draw_text_bounded((10.0, 10.0), Color::BLACK, &text);
+
+                assert_eq!(
+                    Rect::from_tuples(
+                        (10.0, 10.0),
+                        (10.0 + text.width(), 10.0 + text.height())
+                    ),
+                    bounds
+                );
+
+                let ink_bounds = text.ink_bounds().unwrap().with_offset((10.0, 10.0));
+
+                let capture = graphics.capture(ImageDataType::RGB);
+                let width = capture.size().x as usize;
+
+                for (i, pixel) in capture.data().chunks(3).enumerate() {
+                    if pixel[0] < 200 {
+                        let position = Vec2::new((i % width) as f32, (i / width) as f32);
+                        assert!(ink_bounds.with_margin(1.0).contains(position));
+                    }
+                }
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "capture_offscreen_supersampled".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let draw = |graphics: &mut Graphics2D| {
+                    graphics.clear_screen(Color::WHITE);
+                    graphics.draw_triangle(
+                        [
+                            Vec2::new(0.0, 0.0),
+                            Vec2::new(20.0, 0.0),
+                            Vec2::new(0.0, 20.0)
+                        ],
+                        Color::BLACK
+                    );
+                };
+
+                let is_partial = |p: &[u8]| p[0] > 20 && p[0] < 235;
+
+                let aliased = graphics
+                    .capture_offscreen(
+                        (20, 20),
+                        &CaptureOptions::new(ImageDataType::RGB),
+                        draw
+                    )
+                    .unwrap();
+
+                assert_eq!(UVec2::new(20, 20), aliased.size());
+                assert!(!aliased.data().chunks(3).any(is_partial));
+
+                let supersampled = graphics
+                    .capture_offscreen(
+                        (20, 20),
+                        &CaptureOptions::new(ImageDataType::RGB).with_supersampling(4),
+                        draw
+                    )
+                    .unwrap();
+
+                assert_eq!(UVec2::new(20, 20), supersampled.size());
+                assert!(supersampled.data().chunks(3).any(is_partial));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "scale_factor".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                assert_eq!(1.0, graphics.get_scale_factor());
+            });
+
+            renderer.set_scale_factor(2.0);
+
+            renderer.draw_frame(|graphics| {
+                assert_eq!(2.0, graphics.get_scale_factor());
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 10,
+        name: "draw_image_with_options_flip".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                // Left half red, right half blue
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (2, 1),
+                        &[255, 0, 0, 0, 0, 255]
+                    )
+                    .unwrap();
+
+                let options = DrawImageOptions::new((0.0, 0.0)).with_size((20.0, 10.0));
+
+                let pixel = |graphics: &mut Graphics2D, x: usize| {
+                    let capture = graphics.capture(ImageDataType::RGB);
+                    capture.data()[(5 * 20 + x) * 3..][..3].to_vec()
+                };
+
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image_with_options(&image, &options);
+
+                let left = pixel(graphics, 2);
+                let right = pixel(graphics, 17);
+
+                assert_eq!(vec![255, 0, 0], left);
+                assert_eq!(vec![0, 0, 255], right);
+
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image_with_options(&image, &options.with_flip_x(true));
+
+                assert_eq!(right, pixel(graphics, 2));
+                assert_eq!(left, pixel(graphics, 17));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 10,
+        name: "image_color_key".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                // Left half magenta, right half green
+                let image = graphics
+                    .create_image_from_raw_pixels_with_color_key(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (2, 1),
+                        &[255, 0, 255, 0, 255, 0],
+                        &ColorKey::new(Color::MAGENTA)
+                    )
+                    .unwrap();
+
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((0.0, 0.0), (20.0, 10.0)),
+                    &image
+                );
+
+                let capture = graphics.capture(ImageDataType::RGBA);
+                let pixel = |x: usize| &capture.data()[(5 * 20 + x) * 4..][..4];
+
+                assert_eq!(&[255, 255, 255, 255], pixel(2));
+                assert_eq!(&[0, 255, 0, 255], pixel(17));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 10,
+        name: "image_destination_rect".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (4, 2),
+                        &[0; 4 * 2 * 3]
+                    )
+                    .unwrap();
+
+                let rect = Graphics2D::image_destination_rect((3.0, 4.0), &image);
+
+                assert_eq!(Rectangle::from_tuples((3.0, 4.0), (7.0, 6.0)), rect);
+                assert!(rect.contains(Vec2::new(3.0, 4.0)));
+                assert!(rect.contains(Vec2::new(6.5, 5.5)));
+                assert!(!rect.contains(Vec2::new(7.0, 5.0)));
+
+                // The image covers exactly the pixels in the rectangle
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image((3.0, 4.0), &image);
+
+                let capture = graphics.capture(ImageDataType::RGB);
+
+                for y in 0..10 {
+                    for x in 0..20 {
+                        let expected = match rect.contains(Vec2::new(x as f32, y as f32))
+                        {
+                            true => [0, 0, 0],
+                            false => [255, 255, 255]
+                        };
+
+                        assert_eq!(&expected, &capture.data()[(y * 20 + x) * 3..][..3]);
+                    }
+                }
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 20,
+        height: 20,
+        name: "image_subset_pixels".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (4, 2),
+                        &[
+                            255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, //
+                            255, 255, 0, 0, 255, 255, 255, 0, 255, 255, 255, 255
+                        ]
+                    )
+                    .unwrap();
+
+                let dest = Rectangle::from_tuples((0.0, 0.0), (20.0, 20.0));
+
+                let capture = |graphics: &mut Graphics2D| {
+                    graphics.capture(ImageDataType::RGB).into_data()
+                };
+
+                let cases = [
+                    // The whole image
+                    (
+                        Rect::from_tuples((0.0, 0.0), (1.0, 1.0)),
+                        IRect::from_tuples((0, 0), (4, 2))
+                    ),
+                    // A single cell
+                    (
+                        Rect::from_tuples((0.25, 0.5), (0.5, 1.0)),
+                        IRect::from_tuples((1, 1), (2, 2))
+                    )
+                ];
+
+                for (normalized, pixels) in cases.iter() {
+                    graphics.clear_screen(Color::WHITE);
+                    graphics.draw_rectangle_image_subset_tinted(
+                        &dest,
+                        Color::WHITE,
+                        normalized,
+                        &image
+                    );
+                    let expected = capture(graphics);
+
+                    graphics.clear_screen(Color::WHITE);
+                    graphics.draw_rectangle_image_subset_pixels_tinted(
+                        &dest,
+                        Color::WHITE,
+                        pixels,
+                        &image
+                    );
+
+                    assert_eq!(expected, capture(graphics));
+                }
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 40,
+        height: 40,
+        name: "image_quad_repeat_uv".to_string(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                let image = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        (2, 2),
+                        &[[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0]].concat()
+                    )
+                    .unwrap();
+
+                graphics.set_image_wrap_mode(&image, TextureWrapMode::Repeat);
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_quad_image_tinted_four_color(
+                    [
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(40.0, 0.0),
+                        Vec2::new(40.0, 40.0),
+                        Vec2::new(0.0, 40.0)
+                    ],
+                    [Color::WHITE; 4],
+                    [
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(4.0, 0.0),
+                        Vec2::new(4.0, 4.0),
+                        Vec2::new(0.0, 4.0)
+                    ],
+                    &image
+                );
+
+                // Each tile is 10x10 pixels, and each texel is 5x5 pixels
+                let capture = graphics.capture(ImageDataType::RGB);
+                let pixel = |x: usize, y: usize| &capture.data()[(y * 40 + x) * 3..][..3];
+
+                assert_eq!(pixel(2, 2), pixel(12, 2));
+                assert_eq!(pixel(2, 2), pixel(32, 22));
+                assert_eq!(pixel(7, 7), pixel(37, 37));
+                assert_ne!(pixel(2, 2), pixel(7, 2));
+                assert_ne!(pixel(2, 2), pixel(2, 7));
+
+                graphics.clear_screen(Color::WHITE);
+            });
+        })
+    });
+
     for test in tests {
         log::info!("Running test {}", test.name);
 